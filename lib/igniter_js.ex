@@ -1,3 +1,21 @@
 defmodule IgniterJs do
-  @moduledoc false
+  @moduledoc """
+  Javascript and CSS patching functionality for Igniter. The codemods live in
+  `IgniterJs.Parsers.Javascript.Parser` and `IgniterJs.Parsers.CSS.Parser`.
+  """
+  alias IgniterJs.Native
+
+  @doc """
+  Report the version of the native library, the syntax its parsers accept, the versions of
+  the SWC and biome crates and the list of supported NIFs. Attach it to bug reports about
+  parsing. It returns a tuple.
+
+  ```elixir
+  IgniterJs.capabilities()
+  ```
+  """
+  def capabilities do
+    {status, _fn_atom, result} = Native.capabilities_nif()
+    {status, :capabilities, Map.drop(result, [:__struct__])}
+  end
 end
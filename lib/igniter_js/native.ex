@@ -30,15 +30,17 @@ defmodule IgniterJs.Native do
   # When your NIF is loaded, it will override this function.
   def is_module_imported_from_ast_nif(_file_content, _module_name), do: error()
 
-  def insert_import_to_ast_nif(_file_content, _import_lines), do: error()
+  def insert_import_to_ast_nif(_file_content, _import_lines, _options), do: error()
 
-  def remove_import_from_ast_nif(_file_content, _modules), do: error()
+  def insert_import_to_ast_with_spans_nif(_file_content, _import_lines), do: error()
+
+  def remove_import_from_ast_nif(_file_content, _modules, _options), do: error()
 
   def find_live_socket_node_from_ast_nif(_file_content), do: error()
 
   def contains_variable_from_ast_nif(_file_content, _variable_name), do: error()
 
-  def extend_hook_object_to_ast_nif(_file_content, _names), do: error()
+  def extend_hook_object_to_ast_nif(_file_content, _names, _options), do: error()
 
   def remove_objects_of_hooks_from_ast_nif(_file_content, _object_names), do: error()
 
@@ -51,9 +53,179 @@ defmodule IgniterJs.Native do
 
   def is_js_formatted_nif(_file_content), do: error()
 
+  def format_js_batch_nif(_files_content), do: error()
+
+  def format_js_file_nif(_path), do: error()
+
+  def format_js_range_nif(_file_content, _start_byte, _end_byte), do: error()
+
   def format_css_nif(_file_content), do: error()
 
   def is_css_formatted_nif(_file_content), do: error()
 
+  def format_css_file_nif(_path), do: error()
+
+  def minify_js_nif(_file_content), do: error()
+
+  def insert_css_import_to_ast_nif(_file_content, _import_lines), do: error()
+
+  def css_class_has_declaration_from_ast_nif(_file_content, _class_name, _property, _value),
+    do: error()
+
+  def contains_export_from_ast_nif(_file_content, _name), do: error()
+
+  def wrap_in_try_catch_ast_nif(_file_content, _target_fn_name), do: error()
+
+  def remove_debugger_statements_ast_nif(_file_content), do: error()
+
+  def rename_function_ast_nif(_file_content, _old_name, _new_name), do: error()
+
+  def insert_import_before_ast_nif(_file_content, _import_lines, _before_source), do: error()
+
+  def insert_import_to_ast_would_change_nif(_file_content, _import_lines), do: error()
+
+  def remove_import_from_ast_would_change_nif(_file_content, _modules), do: error()
+
+  def extend_hook_object_to_ast_would_change_nif(_file_content, _names), do: error()
+
+  def remove_objects_of_hooks_from_ast_would_change_nif(_file_content, _names), do: error()
+
+  def detect_module_kind_from_ast_nif(_file_content), do: error()
+
+  def statistics_detailed_from_ast_nif(_file_content), do: error()
+
+  def normalize_imports_ast_nif(_file_content), do: error()
+
+  def count_node_kinds_from_ast_nif(_file_content, _kinds), do: error()
+
+  def css_count_selectors_matching_from_ast_nif(_file_content, _prefix), do: error()
+
+  def css_list_media_queries_from_ast_nif(_file_content), do: error()
+
+  def css_rename_class_in_ast_nif(_file_content, _old_name, _new_name), do: error()
+
+  def validate_js_from_ast_nif(_file_content), do: error()
+
+  def css_validate_css_from_ast_nif(_file_content), do: error()
+
+  def extend_var_objects_batch_ast_nif(_file_content, _extensions), do: error()
+
+  def extend_var_object_properties_to_ast_nif(_file_content, _var_name, _properties), do: error()
+
+  def insert_statement_after_imports_ast_nif(_file_content, _statement_source), do: error()
+
+  def add_hook_ast_nif(_file_content, _import_line, _hook_name), do: error()
+
+  def extend_hook_object_to_ast_with_report_nif(_file_content, _names), do: error()
+
+  def strip_comments_js_nif(_file_content), do: error()
+
+  def has_socket_connect_from_ast_nif(_file_content, _socket_var_name), do: error()
+
+  def ensure_window_event_listener_ast_nif(_file_content, _event_name, _handler_source),
+    do: error()
+
+  def css_contains_class_from_ast_nif(_file_content, _class_name), do: error()
+
+  def css_statistics_from_ast_nif(_file_content), do: error()
+
+  def css_remove_empty_rules_from_ast_nif(_file_content), do: error()
+
+  def css_merge_duplicate_rules_from_ast_nif(_file_content), do: error()
+
+  def module_import_presence_from_ast_nif(_file_content, _module_source, _include_dynamic),
+    do: error()
+
+  def replace_hook_spread_ast_nif(_file_content, _old_spread, _new_spread), do: error()
+
+  def get_function_source_from_ast_nif(_file_content, _fn_name), do: error()
+
+  def list_todo_comments_from_ast_nif(_file_content, _keywords), do: error()
+
+  def detect_js_features_from_ast_nif(_file_content), do: error()
+
+  def insert_import_to_ast_with_quote_style_nif(_file_content, _import_lines, _quote_style),
+    do: error()
+
+  def css_contains_custom_property_from_ast_nif(_file_content, _var_name, _root_only),
+    do: error()
+
+  def css_set_custom_property_from_ast_nif(_file_content, _selector, _var_name, _value),
+    do: error()
+
+  def remove_objects_of_hooks_from_ast_with_report_nif(_file_content, _object_names),
+    do: error()
+
+  def ensure_global_assignment_ast_nif(_file_content, _lhs, _rhs), do: error()
+
+  def hook_exists_in_ast_nif(_file_content, _hook_name, _target), do: error()
+
+  def find_import_by_local_ast_nif(_file_content, _local_name), do: error()
+
+  def convert_default_export_to_named_ast_nif(_file_content, _name), do: error()
+
+  def parse_with_diagnostics_nif(_file_content), do: error()
+
+  def css_list_selectors_from_ast_nif(_file_content), do: error()
+
+  def get_live_socket_options_from_ast_nif(_file_content, _target), do: error()
+
+  def extend_hook_object_to_ast_for_socket_nif(_file_content, _names, _target), do: error()
+
+  def insert_import_if_missing_ast_nif(_file_content, _import_lines), do: error()
+
+  def has_csrf_token_setup_from_ast_nif(_file_content), do: error()
+
+  def extend_object_at_path_ast_nif(_file_content, _var_name, _path, _names), do: error()
+
+  def count_sloc_from_ast_nif(_file_content), do: error()
+
+  def format_js_with_options_nif(_file_content, _organize_imports), do: error()
+
+  def extend_hook_object_to_ast_strict_nif(_file_content, _names), do: error()
+
+  def rename_identifier_ast_nif(_file_content, _old_name, _new_name), do: error()
+
+  def extend_var_object_property_by_names_to_ast_with_status_nif(_file_content, _var, _names),
+    do: error()
+
+  def remove_export_from_ast_nif(_file_content, _name), do: error()
+
+  def guard_console_logs_ast_nif(_file_content, _flag_expr), do: error()
+
+  def import_edges_from_ast_nif(_file_content, _from_label), do: error()
+
+  def css_is_imported_from_ast_nif(_file_content, _import_line, _strict), do: error()
+
+  def diagnose_live_socket_from_ast_nif(_file_content, _target), do: error()
+
+  def css_insert_rule_to_ast_nif(_file_content, _rule_source, _position), do: error()
+
+  def css_set_declaration_in_ast_nif(_file_content, _selector, _property, _value),
+    do: error()
+
+  def find_duplicate_import_sources_from_ast_nif(_file_content), do: error()
+
+  def extract_jsdoc_from_ast_nif(_file_content), do: error()
+
+  def css_selector_specificity_from_ast_nif(_file_content), do: error()
+
+  def normalize_hooks_order_ast_nif(_file_content), do: error()
+
+  def is_import_used_ast_nif(_file_content, _source), do: error()
+
+  def css_rename_custom_property_in_ast_nif(_file_content, _old_name, _new_name),
+    do: error()
+
+  def ast_fingerprint_nif(_file_content), do: error()
+
+  def hoist_imports_ast_nif(_file_content), do: error()
+
+  def capabilities_nif(), do: error()
+
+  def has_mixed_indentation_nif(_file_content), do: error()
+
+  def extract_string_literals_from_ast_nif(_file_content), do: error()
+
   defp error, do: :erlang.nif_error(:nif_not_loaded)
 end
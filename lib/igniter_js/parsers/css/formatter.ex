@@ -8,7 +8,7 @@ defmodule IgniterJs.Parsers.CSS.Formatter do
   # We set version of biemojs based on https://github.com/brioche-dev/brioche/pull/184
 
   alias IgniterJs.Native
-  import IgniterJs.Helpers, only: [call_nif_fn: 4]
+  import IgniterJs.Helpers, only: [call_nif_fn: 4, normalize_output: 2]
 
   @doc """
   Checks if the provided CSS content or file is formatted.
@@ -68,4 +68,19 @@ defmodule IgniterJs.Parsers.CSS.Formatter do
       type
     )
   end
+
+  @doc """
+  Formats the file at the given path. The file is read on the Rust side, so its content
+  never lands on the Elixir heap.
+
+  ## Examples
+
+      iex> IgniterJs.Parsers.CSS.Formatter.format_file("path/to/file.css")
+      {:ok, :format_file, formatted}
+
+  """
+  def format_file(path) do
+    Native.format_css_file_nif(path)
+    |> normalize_output(__ENV__.function)
+  end
 end
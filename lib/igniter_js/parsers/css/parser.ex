@@ -0,0 +1,510 @@
+defmodule IgniterJs.Parsers.CSS.Parser do
+  @moduledoc """
+  Codemods for CSS files.
+  """
+
+  alias IgniterJs.Native
+  import IgniterJs.Helpers, only: [call_nif_fn: 4]
+
+  @doc """
+  Insert `@import` rules to the given file or content and returns tuple. Imports that
+  already exist are skipped, and the layer and conditions of the new imports are kept.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.insert_imports(css_content, ~s(@import "theme.css" layer(base);))
+  Parser.insert_imports("/path/to/file.css", imports_lines, :path)
+  ```
+  """
+  def insert_imports(file_path_or_content, imports_lines, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_css_import_to_ast_nif(file_content, imports_lines)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Insert a full rule, like `.new { color: red; }`, into the given file or content and
+  returns tuple. The rule is checked with the CSS parser and inserted as written, with
+  comments and the rest of the formatting kept. The position is `:top` (after `@charset`
+  and the `@import` rules), `:bottom`, or `{:after, selector}` to place it right after the
+  first top-level rule with that selector.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.insert_rule(css_content, ".new { color: red; }")
+  Parser.insert_rule(css_content, ".new { color: red; }", {:after, ".btn"})
+  Parser.insert_rule("/path/to/file.css", ".new { color: red; }", :top, :path)
+  ```
+  """
+  def insert_rule(file_path_or_content, rule_source, position \\ :bottom, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_insert_rule_to_ast_nif(file_content, rule_source, position)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if the given file or content already has the `@import` rule, and return a boolean.
+  Only the hrefs are compared by default; pass `strict` as `true` to also match the layer
+  and the `supports()` and media conditions.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.imported?(css_content, ~s(@import "theme.css";))
+  Parser.imported?("/path/to/file.css", ~s(@import "print.css" print;), :path, true)
+  ```
+  """
+  def imported?(file_path_or_content, import_line, type \\ :content, strict \\ false) do
+    elem(exist_import(file_path_or_content, import_line, type, strict), 0) == :ok
+  end
+
+  @doc """
+  Check if the given file or content already has the `@import` rule, like `imported?/4`,
+  and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.exist_import(css_content, ~s(@import "theme.css";))
+  Parser.exist_import("/path/to/file.css", ~s(@import "print.css" print;), :path, true)
+  ```
+  """
+  def exist_import(file_path_or_content, import_line, type \\ :content, strict \\ false) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_is_imported_from_ast_nif(file_content, import_line, strict)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if a rule selecting the given class declares the property with the given value,
+  and returns boolean. The value comparison ignores whitespace.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.class_has_declaration?(css_content, "btn", "display", "flex")
+  Parser.class_has_declaration?("/path/to/file.css", "btn", "display", "flex", :path)
+  ```
+  """
+  def class_has_declaration?(
+        file_path_or_content,
+        class_name,
+        property,
+        value,
+        type \\ :content
+      ) do
+    elem(class_has_declaration(file_path_or_content, class_name, property, value, type), 0) == :ok
+  end
+
+  @doc """
+  Check if a rule selecting the given class declares the property with the given value,
+  and returns tuple. When the content can not be parsed, the error tuple holds the parser
+  message instead of `false`.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.class_has_declaration(css_content, "btn", "display", "flex")
+  Parser.class_has_declaration("/path/to/file.css", "btn", "display", "flex", :path)
+  ```
+  """
+  def class_has_declaration(
+        file_path_or_content,
+        class_name,
+        property,
+        value,
+        type \\ :content
+      ) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_class_has_declaration_from_ast_nif(file_content, class_name, property, value)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if any selector of the given file or content uses the class, including nested
+  rules, and returns boolean.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.class_exists?(css_content, "btn")
+  Parser.class_exists?("/path/to/file.css", ".btn", :path)
+  ```
+  """
+  def class_exists?(file_path_or_content, class_name, type \\ :content) do
+    elem(exist_class(file_path_or_content, class_name, type), 0) == :ok
+  end
+
+  @doc """
+  Check if any selector of the given file or content uses the class, including nested
+  rules, and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.exist_class(css_content, "btn")
+  Parser.exist_class("/path/to/file.css", ".btn", :path)
+  ```
+  """
+  def exist_class(file_path_or_content, class_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_contains_class_from_ast_nif(file_content, class_name)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if the given file or content declares the custom property (CSS variable), with or
+  without its leading `--`, and return a boolean. Pass `root_only` as `true` to only look at
+  `:root` rules.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.custom_property_exists?(css_content, "--color-primary")
+  Parser.custom_property_exists?("/path/to/file.css", "color-primary", :path, true)
+  ```
+  """
+  def custom_property_exists?(
+        file_path_or_content,
+        var_name,
+        type \\ :content,
+        root_only \\ false
+      ) do
+    elem(exist_custom_property(file_path_or_content, var_name, type, root_only), 0) == :ok
+  end
+
+  @doc """
+  Check if the given file or content declares the custom property (CSS variable), like
+  `custom_property_exists?/4`, and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.exist_custom_property(css_content, "--color-primary")
+  Parser.exist_custom_property("/path/to/file.css", "--color-primary", :path, true)
+  ```
+  """
+  def exist_custom_property(
+        file_path_or_content,
+        var_name,
+        type \\ :content,
+        root_only \\ false
+      ) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_contains_custom_property_from_ast_nif(file_content, var_name, root_only)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Set the value of a custom property (CSS variable) in the first top-level rule with the
+  given selector (`:root` by default) of the given file or content. A missing declaration
+  is added to the rule, and a missing rule is appended. Comments and other declarations are
+  kept. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.set_custom_property(css_content, "--color-primary", "#000")
+  Parser.set_custom_property("/path/to/file.css", "--radius", "8px", ".dark", :path)
+  ```
+  """
+  def set_custom_property(
+        file_path_or_content,
+        var_name,
+        value,
+        selector \\ ":root",
+        type \\ :content
+      ) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_set_custom_property_from_ast_nif(file_content, selector, var_name, value)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Set the value of a declaration in the first top-level rule with the given selector of the
+  given file or content. A missing declaration is added to the rule, and a missing rule is
+  appended. The whole value is replaced, so `!important` is only kept when the new value
+  includes it. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.set_declaration(css_content, ".btn", "color", "blue")
+  Parser.set_declaration("/path/to/file.css", "body", "margin", "0 !important", :path)
+  ```
+  """
+  def set_declaration(file_path_or_content, selector, property, value, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_set_declaration_in_ast_nif(file_content, selector, property, value)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  List the distinct class and id names used by the selectors of the given file or content,
+  including nested selectors and rules inside `@media` blocks. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+
+  {:ok, :selectors, %{classes: ["hide-scrollbar"], ids: ["sidebar"]}} =
+    Parser.selectors(css_content)
+
+  Parser.selectors("/path/to/file.css", :path)
+  ```
+  """
+  def selectors(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.css_list_selectors_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, summary} -> {:ok, fn_atom, Map.drop(summary, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  Compute the specificity of every top-level rule of the given file or content, as a
+  `{ids, classes, elements}` tuple, e.g. to audit overly specific selectors. A selector
+  list gets the highest specificity of its selectors. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+
+  {:ok, :specificity, [%{selector: "#main .btn a", specificity: {1, 1, 1}}]} =
+    Parser.specificity(css_content)
+
+  Parser.specificity("/path/to/file.css", :path)
+  ```
+  """
+  def specificity(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.css_selector_specificity_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, rules} -> {:ok, fn_atom, Enum.map(rules, &Map.drop(&1, [:__struct__]))}
+      error -> error
+    end
+  end
+
+  @doc """
+  Count the class selectors whose name starts with the given prefix, e.g. to measure how
+  many `.tw-` utility classes a stylesheet uses. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  {:ok, :count_selectors_matching, 4} = Parser.count_selectors_matching(css_content, "tw-")
+  Parser.count_selectors_matching("/path/to/file.css", ".tw-", :path)
+  ```
+  """
+  def count_selectors_matching(file_path_or_content, prefix, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_count_selectors_matching_from_ast_nif(file_content, prefix)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Remove every style rule with an empty block, e.g. `.foo {}`, from the given file or
+  content. At-rules like `@media` and comments are kept. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.remove_empty_rules(css_content)
+  Parser.remove_empty_rules("/path/to/file.css", :path)
+  ```
+  """
+  def remove_empty_rules(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_remove_empty_rules_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Merge the style rules with the same selector list of the given file or content into the
+  first of them. A later declaration of the same property overrides the earlier one, but
+  never an `!important` one, and fallbacks within one rule are kept. Rules separated by a
+  rule declaring the same property, or by an at-rule, are not merged, so the cascade stays
+  the same. Comments are kept. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.merge_duplicate_rules(css_content)
+  Parser.merge_duplicate_rules("/path/to/file.css", :path)
+  ```
+  """
+  def merge_duplicate_rules(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_merge_duplicate_rules_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  List the conditions of every `@media` rule in the given file or content, e.g. to
+  inventory the breakpoints of a stylesheet. Identical conditions are listed once.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  {:ok, :media_queries, ["(min-width: 768px)"]} = Parser.media_queries(css_content)
+  Parser.media_queries("/path/to/file.css", :path)
+  ```
+  """
+  def media_queries(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_list_media_queries_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Rename a class in every selector of the given file or content, including compound and
+  grouped selectors. Comments, strings and custom properties are kept as they are.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.rename_class(css_content, "hide-scrollbar", "no-scrollbar")
+  Parser.rename_class("/path/to/file.css", "hide-scrollbar", "no-scrollbar", :path)
+  ```
+  """
+  def rename_class(file_path_or_content, old_name, new_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_rename_class_in_ast_nif(file_content, old_name, new_name)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Rename a custom property (CSS variable) of the given file or content, with or without
+  the leading `--`, in its declarations and every `var()` that uses it. Fallback values
+  such as `var(--old, 4px)` are kept. It returns a tuple, with an error when the property
+  is neither declared nor used.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.rename_custom_property(css_content, "--color-primary", "--brand")
+  Parser.rename_custom_property("/path/to/file.css", "spacing", "space", :path)
+  ```
+  """
+  def rename_custom_property(file_path_or_content, old_name, new_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_rename_custom_property_in_ast_nif(file_content, old_name, new_name)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check that the given file or content is valid CSS without modifying it. It returns
+  `{:ok, :validate, true}`, or an error tuple with the message and position of the first
+  parser error.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+  Parser.validate(css_content)
+  Parser.validate("/path/to/file.css", :path)
+  ```
+  """
+  def validate(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.css_validate_css_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Retrieve statistical information about the given CSS file or content: the number of
+  style `rules`, `at_rules` and `declarations`, the `max_declarations` of a single rule and
+  the `rule_declarations` of every rule as `{selector, count}` tuples, e.g. to find bloated
+  selectors. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.CSS.Parser
+
+  {:ok, :statistics, %{rules: 3, max_declarations: 3, rule_declarations: [{".btn", 3} | _]}} =
+    Parser.statistics(css_content)
+
+  Parser.statistics("/path/to/file.css", :path)
+  ```
+  """
+  def statistics(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.css_statistics_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, result} -> {:ok, fn_atom, Map.drop(result, [:__struct__])}
+      error -> error
+    end
+  end
+end
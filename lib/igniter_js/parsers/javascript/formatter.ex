@@ -6,7 +6,7 @@ defmodule IgniterJs.Parsers.Javascript.Formatter do
   """
 
   alias IgniterJs.Native
-  import IgniterJs.Helpers, only: [call_nif_fn: 4]
+  import IgniterJs.Helpers, only: [call_nif_fn: 4, normalize_output: 2]
 
   @doc """
   Checks if the provided JavaScript content or file is formatted.
@@ -56,12 +56,132 @@ defmodule IgniterJs.Parsers.Javascript.Formatter do
     )
   end
 
-  def format(file_path_or_content, type \\ :content) do
+  @doc """
+  Checks if the indentation of the provided JavaScript content or file mixes tabs and
+  spaces, some lines indented with tabs and others with spaces. Spaces used for alignment
+  after the leading tabs don't count.
+
+  ## Examples
+
+      iex> IgniterJs.Parsers.Javascript.Formatter.mixed_indentation?("path/to/file.js", :path)
+      false
+
+  """
+  def mixed_indentation?(file_path_or_content, type \\ :content) do
+    elem(mixed_indentation(file_path_or_content, type), 0) == :ok
+  end
+
+  @doc """
+  Checks if the indentation of the provided JavaScript content or file mixes tabs and
+  spaces, returning the status of the check.
+
+  ## Examples
+
+      iex> IgniterJs.Parsers.Javascript.Formatter.mixed_indentation("if (a) {\\n \\tb();\\n}")
+      {:ok, :mixed_indentation, true}
+
+  """
+  def mixed_indentation(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.has_mixed_indentation_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Formats the provided JavaScript content or file.
+
+  ## Options
+
+    - `:organize_imports` - sorts the imports at the top of the file by their source
+      before formatting, side-effect imports such as `import "phoenix_html"` keep their
+      place. Defaults to `false`.
+
+  The options can also be given in place of the type, for content.
+
+  ## Examples
+
+      iex> js = "import b from \\"b\\";import a from \\"a\\";"
+      iex> IgniterJs.Parsers.Javascript.Formatter.format(js, :content, organize_imports: true)
+      {:ok, :format, "import a from \\"a\\";\\nimport b from \\"b\\";\\n"}
+      iex> IgniterJs.Parsers.Javascript.Formatter.format(js, organize_imports: true)
+      {:ok, :format, "import a from \\"a\\";\\nimport b from \\"b\\";\\n"}
+
+  """
+  def format(file_path_or_content, type_or_opts \\ :content, opts \\ [])
+
+  def format(file_path_or_content, opts, []) when is_list(opts) do
+    format(file_path_or_content, :content, opts)
+  end
+
+  def format(file_path_or_content, type, opts) do
+    organize_imports = Keyword.get(opts, :organize_imports, false)
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        if organize_imports,
+          do: Native.format_js_with_options_nif(file_content, organize_imports),
+          else: Native.format_js_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Formats a list of JavaScript contents in a single NIF call.
+
+  The result keeps the order of the given list and every item is a tagged tuple, so one
+  broken source does not fail the whole batch.
+
+  ## Examples
+
+      iex> IgniterJs.Parsers.Javascript.Formatter.format_batch(["let a=1", "let b = ;"])
+      {:ok, :format_batch, [{:ok, "let a = 1;\\n"}, {:error, "Parsing failed due to syntax errors."}]}
+
+  """
+  def format_batch(files_content) when is_list(files_content) do
+    Native.format_js_batch_nif(files_content)
+    |> normalize_output(__ENV__.function)
+  end
+
+  @doc """
+  Formats the file at the given path. The file is read on the Rust side, so its content
+  never lands on the Elixir heap.
+
+  ## Examples
+
+      iex> IgniterJs.Parsers.Javascript.Formatter.format_file("path/to/file.js")
+      {:ok, :format_file, formatted}
+
+  """
+  def format_file(path) do
+    Native.format_js_file_nif(path)
+    |> normalize_output(__ENV__.function)
+  end
+
+  @doc """
+  Formats only the top-level statements overlapping the `start_byte..end_byte` range of the
+  given content or file, leaving the rest of it byte-for-byte identical. Handy to keep the
+  diff of a small edit small.
+
+  ## Examples
+
+      iex> IgniterJs.Parsers.Javascript.Formatter.format_range("let a=1\\nlet b=2", 0, 3)
+      {:ok, :format_range, "let a = 1;\\nlet b=2"}
+
+  """
+  def format_range(file_path_or_content, start_byte, end_byte, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.format_js_nif(file_content)
+        Native.format_js_range_nif(file_content, start_byte, end_byte)
       end,
       type
     )
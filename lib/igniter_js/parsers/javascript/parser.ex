@@ -41,258 +41,1845 @@ defmodule IgniterJs.Parsers.Javascript.Parser do
     )
   end
 
+  @doc """
+  Check if a binding imported from the source (default, namespace or named) is used
+  anywhere else in the given file or content, and returns boolean. It is `false` when
+  nothing is imported from the source.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.import_used?(js_content, "../vendor/topbar")
+  Parser.import_used?("/path/to/file.js", "../vendor/topbar", :path)
+  ```
+  """
+  def import_used?(file_path_or_content, source, type \\ :content) do
+    elem(import_used(file_path_or_content, source, type), 0) == :ok
+  end
+
+  @doc """
+  Check if a binding imported from the source is used anywhere else in the given file or
+  content, like `import_used?/3`, and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.import_used(js_content, "../vendor/topbar")
+  Parser.import_used("/path/to/file.js", "../vendor/topbar", :path)
+  ```
+  """
+  def import_used(file_path_or_content, source, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.is_import_used_ast_nif(file_content, source)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check how a module is imported in the given file or content, by its source (e.g.
+  `"phoenix"`). It returns `:static` for an `import ... from` declaration, or `:dynamic`
+  when the module is only loaded with an `import("module")` call. Dynamic imports are
+  ignored when `include_dynamic` is `false`.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :module_import_kind, :dynamic} = Parser.module_import_kind(js_content, "./chart.js")
+  Parser.module_import_kind("/path/to/file.js", "phoenix", :path)
+  Parser.module_import_kind(js_content, "./chart.js", :content, false)
+  ```
+  """
+  def module_import_kind(
+        file_path_or_content,
+        module_source,
+        type \\ :content,
+        include_dynamic \\ true
+      ) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.module_import_presence_from_ast_nif(file_content, module_source, include_dynamic)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Find the import of the given file or content that introduces the local name, with its
+  `source`, its `kind` (`:default`, `:named` or `:namespace`) and, for a named import, the
+  `imported` name. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :import_by_local, %{source: "phoenix", kind: :named, imported: "Socket"}} =
+    Parser.import_by_local(js_content, "Socket")
+
+  Parser.import_by_local("/path/to/file.js", "topbar", :path)
+  ```
+  """
+  def import_by_local(file_path_or_content, local_name, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.find_import_by_local_ast_nif(file_content, local_name)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, result} -> {:ok, fn_atom, Map.drop(result, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  List one `%{from: from_label, to: source}` edge per static import of the given file or
+  content, in source order, to build a dependency graph. Dynamic `import()` calls are not
+  included. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :import_edges, [%{from: "app.js", to: "phoenix"}]} =
+    Parser.import_edges(js_content, "app.js")
+
+  Parser.import_edges("/path/to/file.js", "/path/to/file.js", :path)
+  ```
+  """
+  def import_edges(file_path_or_content, from_label, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.import_edges_from_ast_nif(file_content, from_label)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, edges} -> {:ok, fn_atom, Enum.map(edges, &Map.drop(&1, [:__struct__]))}
+      error -> error
+    end
+  end
+
   @doc """
   Insert imports to the given file or content and returns tuple.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.insert_imports(js_content, imports_lines)
-  Parser.insert_imports(js_content, imports_lines, :content)
-  Parser.insert_imports("/path/to/file.js", imports_lines, :path)
+  Parser.insert_imports(js_content, imports_lines)
+  Parser.insert_imports(js_content, imports_lines, :content)
+  Parser.insert_imports("/path/to/file.js", imports_lines, :path)
+  Parser.insert_imports(js_content, imports_lines, semicolons: :keep)
+  Parser.insert_imports(js_content, imports_lines, new_line: :crlf)
+  ```
+
+  ## Options
+
+    * `:semicolons` - `:always` (default) ends every statement with a semicolon, `:keep`
+      follows the original source and `:as_needed` leaves them out where they are optional.
+    * `:new_line` - `:lf` (default) or `:crlf`, the line ending of the output.
+    * `:preserve_blank_lines` - keeps the blank lines between groups of top-level imports,
+      e.g. vendor and local imports, which are collapsed by default.
+    * `:ascii_only` - escapes the non-ASCII characters of strings and identifiers, e.g. for
+      build tools that only accept ASCII source.
+    * `:preserve_formatting` - indents the output like the input (tabs or its space width)
+      instead of with four spaces.
+  """
+  def insert_imports(file_path_or_content, imports_lines, type_or_opts \\ :content, opts \\ [])
+
+  def insert_imports(file_path_or_content, imports_lines, opts, []) when is_list(opts) do
+    insert_imports(file_path_or_content, imports_lines, :content, opts)
+  end
+
+  def insert_imports(file_path_or_content, imports_lines, type, opts) do
+    options = emit_options(opts)
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_import_to_ast_nif(file_content, imports_lines, options)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Insert imports to the given file or content only when the module is not imported yet,
+  in a single parse. It returns a tuple with the code and whether an import was inserted;
+  the original content is returned untouched when every import is already present.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :insert_imports_if_missing, {code, true}} =
+    Parser.insert_imports_if_missing(js_content, "import topbar from \"../vendor/topbar\";")
+
+  Parser.insert_imports_if_missing("/path/to/file.js", imports_lines, :path)
+  ```
+  """
+  def insert_imports_if_missing(file_path_or_content, imports_lines, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_import_if_missing_ast_nif(file_content, imports_lines)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Insert imports to the given file or content like `insert_imports/4`, writing the source
+  of every inserted import with the given quotes: `:single`, `:double` or `:as_written`.
+  Imports already in the file keep their own quotes. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.insert_imports_with_quotes(js_content, imports_lines, :single)
+  Parser.insert_imports_with_quotes("/path/to/file.js", imports_lines, :double, :path)
+  ```
+  """
+  def insert_imports_with_quotes(
+        file_path_or_content,
+        imports_lines,
+        quote_style,
+        type \\ :content
+      )
+      when quote_style in [:single, :double, :as_written] do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_import_to_ast_with_quote_style_nif(
+          file_content,
+          imports_lines,
+          quote_style
+        )
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Insert imports to the given file or content like `insert_imports/4`, and also return the
+  byte ranges of the inserted import lines inside the output, so a UI can highlight them.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :insert_imports_with_spans, {code, [{start, stop}]}} =
+    Parser.insert_imports_with_spans(js_content, imports_lines)
+
+  Parser.insert_imports_with_spans("/path/to/file.js", imports_lines, :path)
+  ```
+  """
+  def insert_imports_with_spans(file_path_or_content, imports_lines, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_import_to_ast_with_spans_nif(file_content, imports_lines)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Insert imports to the given file or content right before the import of `before_source`,
+  e.g. to keep vendor imports grouped. If no import of `before_source` exists, the imports
+  are appended like `insert_imports/4`. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.insert_imports_before(js_content, imports_lines, "../vendor/topbar")
+  Parser.insert_imports_before("/path/to/file.js", imports_lines, "../vendor/topbar", :path)
+  ```
+  """
+  def insert_imports_before(
+        file_path_or_content,
+        imports_lines,
+        before_source,
+        type \\ :content
+      ) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_import_before_ast_nif(file_content, imports_lines, before_source)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Normalize the imports of the given file or content: specifiers are sorted and deduplicated,
+  and default/named imports from the same source are collapsed into one. Running it again
+  gives the same output. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.normalize_imports(js_content)
+  Parser.normalize_imports("/path/to/file.js", :path)
+  ```
+  """
+  def normalize_imports(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.normalize_imports_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Move the imports of the given file or content that appear after other statements to the
+  top, keeping their relative order. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.hoist_imports(js_content)
+  Parser.hoist_imports("/path/to/file.js", :path)
+  ```
+  """
+  def hoist_imports(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.hoist_imports_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  List the sources imported by more than one import statement in the given file or
+  content, in the order of their first import. `normalize_imports/2` merges most of them.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :duplicate_import_sources, ["react"]} = Parser.duplicate_import_sources(js_content)
+  Parser.duplicate_import_sources("/path/to/file.js", :path)
+  ```
+  """
+  def duplicate_import_sources(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.find_duplicate_import_sources_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Remove imports from the given file or content. it accepts a single module or a list of modules.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.remove_imports(js_content, "SomeModule")
+  Parser.remove_imports("/path/to/file.js", "SomeModule", :path)
+  Parser.remove_imports(js_content, "SomeModule", semicolons: :keep)
+  ```
+
+  It accepts the same options as `insert_imports/4`.
+  """
+  def remove_imports(file_path_or_content, module, type_or_opts \\ :content, opts \\ [])
+
+  def remove_imports(file_path_or_content, modules, opts, []) when is_list(opts) do
+    remove_imports(file_path_or_content, modules, :content, opts)
+  end
+
+  def remove_imports(file_path_or_content, modules, type, opts) do
+    options = emit_options(opts)
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.remove_import_from_ast_nif(file_content, modules, options)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if a LiveSocket var exists in the given file or content and returns boolean.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_live_socket?(js_content)
+  Parser.exist_live_socket?(js_content, :content)
+  Parser.exist_live_socket?("/path/to/file.js", :path)
+  ```
+  """
+  def exist_live_socket?(file_path_or_content, type \\ :content) do
+    elem(exist_live_socket(file_path_or_content, type), 0) == :ok
+  end
+
+  @doc """
+  Check if a LiveSocket var exists in the given file or content and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_live_socket(js_content)
+  Parser.exist_live_socket(js_content, :content)
+  Parser.exist_live_socket("/path/to/file.js", :path)
+  ```
+  """
+  def exist_live_socket(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.find_live_socket_node_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  List the top-level keys of the options object of `new LiveSocket(...)` in the given file
+  or content, each with the source form of its value when it is a string, number, boolean
+  or `null`, and `nil` otherwise. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :live_socket_options, [{"hooks", nil}, {"longPollFallbackMs", "2500"}]} =
+    Parser.live_socket_options(js_content)
+
+  Parser.live_socket_options("/path/to/file.js", :path)
+  Parser.live_socket_options(js_content, socket: "adminSocket")
+  ```
+
+  It accepts the same `:socket` option as `hook_exists?/4`.
+  """
+  def live_socket_options(file_path_or_content, type_or_opts \\ :content, opts \\ [])
+
+  def live_socket_options(file_path_or_content, opts, []) when is_list(opts) do
+    live_socket_options(file_path_or_content, :content, opts)
+  end
+
+  def live_socket_options(file_path_or_content, type, opts) do
+    target = socket_target(Keyword.get(opts, :socket, "liveSocket"))
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.get_live_socket_options_from_ast_nif(file_content, target)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Diagnose the LiveSocket initialization of the given file or content stage by stage:
+  whether the variable is declared, initialized with `new LiveSocket(...)`, given an
+  options object, and whether that object has a `hooks` key. `problem` is the reason
+  `extend_hook_object/4` would fail, or `nil`. Unparsable code returns an error, and so
+  does a `:socket` option (see `hook_exists?/4`) that matches several declarations.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :diagnose_live_socket, %{has_options: true, problem: nil}} =
+    Parser.diagnose_live_socket(js_content)
+
+  Parser.diagnose_live_socket("/path/to/file.js", :path)
+  Parser.diagnose_live_socket(js_content, socket: "adminSocket")
+  ```
+  """
+  def diagnose_live_socket(file_path_or_content, type_or_opts \\ :content, opts \\ [])
+
+  def diagnose_live_socket(file_path_or_content, opts, []) when is_list(opts) do
+    diagnose_live_socket(file_path_or_content, :content, opts)
+  end
+
+  def diagnose_live_socket(file_path_or_content, type, opts) do
+    target = socket_target(Keyword.get(opts, :socket, "liveSocket"))
+
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.diagnose_live_socket_from_ast_nif(file_content, target)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, diagnosis} -> {:ok, fn_atom, Map.drop(diagnosis, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  Check if the `hooks` object of `liveSocket` in the given file or content registers the
+  hook, as a shorthand, a key or a spread (`"...Hooks"` only matches the spread), and
+  returns boolean.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.hook_exists?(js_content, "CopyMixInstallationHook")
+  Parser.hook_exists?("/path/to/file.js", "...Hooks", :path)
+  Parser.hook_exists?(js_content, "AdminHook", socket: "adminSocket")
+  ```
+
+  ## Options
+
+    * `:socket` - the `new LiveSocket(...)` to look in, picked like the socket of
+      `extend_socket_hook_object/4`. Defaults to `"liveSocket"`.
+  """
+  def hook_exists?(file_path_or_content, hook_name, type_or_opts \\ :content, opts \\ []) do
+    elem(exist_hook(file_path_or_content, hook_name, type_or_opts, opts), 0) == :ok
+  end
+
+  @doc """
+  Check if the `hooks` object of `liveSocket` in the given file or content registers the
+  hook, like `hook_exists?/4`, and returns tuple. It accepts the same options.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_hook(js_content, "CopyMixInstallationHook")
+  Parser.exist_hook("/path/to/file.js", "CopyMixInstallationHook", :path)
+  ```
+  """
+  def exist_hook(file_path_or_content, hook_name, type_or_opts \\ :content, opts \\ [])
+
+  def exist_hook(file_path_or_content, hook_name, opts, []) when is_list(opts) do
+    exist_hook(file_path_or_content, hook_name, :content, opts)
+  end
+
+  def exist_hook(file_path_or_content, hook_name, type, opts) do
+    target = socket_target(Keyword.get(opts, :socket, "liveSocket"))
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.hook_exists_in_ast_nif(file_content, hook_name, target)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if the given file or content calls `liveSocket.connect()` and returns boolean.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.live_socket_connected?(js_content)
+  Parser.live_socket_connected?("/path/to/file.js", :path)
+  ```
+  """
+  def live_socket_connected?(file_path_or_content, type \\ :content) do
+    elem(exist_live_socket_connect(file_path_or_content, type), 0) == :ok
+  end
+
+  @doc """
+  Check if the given file or content calls `liveSocket.connect()` and returns tuple.
+  A member path such as `window.liveSocket.connect()` counts as well.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_live_socket_connect(js_content)
+  Parser.exist_live_socket_connect("/path/to/file.js", :path)
+  ```
+  """
+  def exist_live_socket_connect(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.has_socket_connect_from_ast_nif(file_content, "liveSocket")
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if the given file or content sets up the Phoenix csrf token, a variable initialized
+  from `document.querySelector("meta[name='csrf-token']")`, and returns boolean.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.csrf_token_setup?(js_content)
+  Parser.csrf_token_setup?("/path/to/file.js", :path)
+  ```
+  """
+  def csrf_token_setup?(file_path_or_content, type \\ :content) do
+    elem(exist_csrf_token_setup(file_path_or_content, type), 0) == :ok
+  end
+
+  @doc """
+  Check if the given file or content sets up the Phoenix csrf token and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_csrf_token_setup(js_content)
+  Parser.exist_csrf_token_setup("/path/to/file.js", :path)
+  ```
+  """
+  def exist_csrf_token_setup(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.has_csrf_token_setup_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if the given file or content calls `connect()` on the socket stored in `var_name`
+  and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_socket_connect(js_content, "socket")
+  Parser.exist_socket_connect("/path/to/file.js", "socket", :path)
+  ```
+  """
+  def exist_socket_connect(file_path_or_content, var_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.has_socket_connect_from_ast_nif(file_content, var_name)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Check if a specific var exists in the given file or content and returns boolean.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_live_socket?(js_content)
+  Parser.exist_live_socket?(js_content, :content)
+  Parser.exist_live_socket?("/path/to/file.js", :path)
+  ```
+  """
+  def var_exists?(file_path_or_content, type \\ :content) do
+    elem(exist_var(file_path_or_content, type), 0) == :ok
+  end
+
+  @doc """
+  Check if an specific var exists in the given file or content and returns tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.exist_live_socket(js_content, var_name)
+  Parser.exist_live_socket(js_content, var_name, :content)
+  Parser.exist_live_socket("/path/to/file.js", var_name, :path)
+  ```
+  """
+  def exist_var(file_path_or_content, var_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.contains_variable_from_ast_nif(file_content, var_name)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend the hook object in the given file or content. It accepts a single object
+  or a list of objects.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.extend_hook_object(js_content, "SomeObject")
+  Parser.extend_hook_object(js_content, ["SomeObject", "AnotherObject"], :content)
+  Parser.extend_hook_object("/path/to/file.js", "SomeObject", :path)
+  Parser.extend_hook_object(js_content, "SomeObject", semicolons: :keep)
+  ```
+
+  It accepts the same options as `insert_imports/4`.
+  """
+  def extend_hook_object(file_path_or_content, object_name, type_or_opts \\ :content, opts \\ [])
+
+  def extend_hook_object(file_path_or_content, object_name, opts, []) when is_list(opts) do
+    extend_hook_object(file_path_or_content, object_name, :content, opts)
+  end
+
+  def extend_hook_object(file_path_or_content, object_name, type, opts)
+      when is_binary(object_name) do
+    extend_hook_object(file_path_or_content, [object_name], type, opts)
+  end
+
+  def extend_hook_object(file_path_or_content, objects_names, type, opts)
+      when is_list(objects_names) do
+    options = emit_options(opts)
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_hook_object_to_ast_nif(file_content, objects_names, options)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend the hook object like `extend_hook_object/4` in strict mode: when a name is already
+  in the `hooks` object (a shorthand, or the same `...Name` spread) nothing is changed and
+  an error listing the conflicting names is returned instead of skipping them.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:error, :extend_hook_object_strict, "Hook names already in the hooks object: SomeObject"} =
+    Parser.extend_hook_object_strict(js_content, ["SomeObject", "AnotherObject"])
+
+  Parser.extend_hook_object_strict("/path/to/file.js", "AnotherObject", :path)
+  ```
+  """
+  def extend_hook_object_strict(file_path_or_content, objects_names, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_hook_object_to_ast_strict_nif(file_content, List.wrap(objects_names))
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend the hook object of one `new LiveSocket(...)` when the given file or content
+  initializes several. The socket is picked by its variable name or by its position
+  (starting at `0`); with `nil` the file must have exactly one socket, otherwise an error
+  is returned because the target is ambiguous.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.extend_socket_hook_object(js_content, "SomeObject", "adminSocket")
+  Parser.extend_socket_hook_object(js_content, ["SomeObject", "AnotherObject"], 1)
+  Parser.extend_socket_hook_object("/path/to/file.js", "SomeObject", nil, :path)
+  ```
+  """
+  def extend_socket_hook_object(file_path_or_content, objects_names, socket, type \\ :content)
+      when is_nil(socket) or is_binary(socket) or (is_integer(socket) and socket >= 0) do
+    target = socket_target(socket)
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_hook_object_to_ast_for_socket_nif(
+          file_content,
+          List.wrap(objects_names),
+          target
+        )
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend the hook object like `extend_hook_object/4` and report which hooks were `added`
+  and which ones were `skipped` because they already exist.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :extend_hook_object_with_report, %{code: code, added: added, skipped: skipped}} =
+    Parser.extend_hook_object_with_report(js_content, ["SomeObject", "AnotherObject"])
+
+  Parser.extend_hook_object_with_report("/path/to/file.js", "SomeObject", :path)
+  ```
+  """
+  def extend_hook_object_with_report(file_path_or_content, objects_names, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.extend_hook_object_to_ast_with_report_nif(
+               file_content,
+               List.wrap(objects_names)
+             )
+           end,
+           type
+         ) do
+      {:ok, fn_atom, result} -> {:ok, fn_atom, Map.drop(result, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  Remove objects from the hooks in the given file or content. It accepts a single o
+  bject or a list of objects.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.remove_objects_from_hooks(js_content, "SomeObject")
+  Parser.remove_objects_from_hooks(js_content, ["SomeObject", "AnotherObject"], :content)
+  Parser.remove_objects_from_hooks("/path/to/file.js", "SomeObject", :path)
+  ```
+  """
+  def remove_objects_from_hooks(file_path_or_content, object_name, type \\ :content)
+
+  def remove_objects_from_hooks(file_path_or_content, object_name, type)
+      when is_binary(object_name) do
+    remove_objects_from_hooks(file_path_or_content, [object_name], type)
+  end
+
+  def remove_objects_from_hooks(file_path_or_content, objects_names, type)
+      when is_list(objects_names) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.remove_objects_of_hooks_from_ast_nif(file_content, objects_names)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Remove objects from the hooks like `remove_objects_from_hooks/3` and report which names
+  were `removed` and which ones were `not_found`, e.g. on a second run of a generator.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :remove_objects_from_hooks_with_report, %{code: code, removed: [], not_found: ["A"]}} =
+    Parser.remove_objects_from_hooks_with_report(js_content, "A")
+
+  Parser.remove_objects_from_hooks_with_report("/path/to/file.js", ["A", "B"], :path)
+  ```
+  """
+  def remove_objects_from_hooks_with_report(
+        file_path_or_content,
+        objects_names,
+        type \\ :content
+      ) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.remove_objects_of_hooks_from_ast_with_report_nif(
+               file_content,
+               List.wrap(objects_names)
+             )
+           end,
+           type
+         ) do
+      {:ok, fn_atom, result} -> {:ok, fn_atom, Map.drop(result, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  Replace a spread of the hooks object in the given file or content, e.g. rename
+  `...MishkaComponents` to `...Components`. It returns a tuple, with an error when the
+  spread does not exist.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.replace_hook_spread(js_content, "MishkaComponents", "Components")
+  Parser.replace_hook_spread("/path/to/file.js", "...MishkaComponents", "...Components", :path)
+  ```
+  """
+  def replace_hook_spread(file_path_or_content, old_spread, new_spread, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.replace_hook_spread_ast_nif(file_content, old_spread, new_spread)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Reorder the hooks object of `liveSocket` in the given file or content to the
+  `hooks: { ...Hooks, NamedHook }` convention: spreads first, then the named hooks sorted
+  alphabetically. It returns a tuple, with an error when there is no hooks object.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.normalize_hooks_order(js_content)
+  Parser.normalize_hooks_order("/path/to/file.js", :path)
+  ```
+  """
+  def normalize_hooks_order(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.normalize_hooks_order_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Import a hook module and register the hook in the hooks object of the given file or
+  content in one call. The import is skipped when it already exists.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.add_hook(js_content, "import CopyHook from \"./copy_hook\";", "CopyHook")
+  Parser.add_hook("/path/to/file.js", import_line, "CopyHook", :path)
+  ```
+  """
+  def add_hook(file_path_or_content, import_line, hook_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.add_hook_ast_nif(file_content, import_line, hook_name)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Retrieve statistical information about the JavaScript source code, such as the number of
+  functions, classes, debugger statements, imports, try-catch blocks, throw statements,
+  export declarations, `const`/`let`/`var` bindings and `//` (`line_comments`) or `/* */`
+  (`block_comments`) comments.
+
+  This function accepts either the content of the JavaScript file or the path to the file,
+  and returns a tuple with the status, function atom, and the extracted data as a map, or
+  an error tuple when the source can not be parsed.
+
+  ## Examples
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  # Analyze a JavaScript source file by providing its content
+  Parser.statistics(js_content)
+
+  # Analyze a JavaScript source file by providing its file path
+  Parser.statistics("/path/to/file.js", :path)
+  ```
+  """
+  def statistics(file_path_or_content, type \\ :content) do
+    {status, fn_atom, {_, data}} =
+      call_nif_fn(
+        file_path_or_content,
+        __ENV__.function,
+        fn file_content ->
+          Native.statistics_from_ast_nif(file_content)
+        end,
+        type
+      )
+
+    converted = if is_map(data), do: Map.drop(data, [:__struct__]), else: data
+    {status, fn_atom, converted}
+  end
+
+  @doc """
+  Get the statistics of `statistics/2` together with the source, line and column (both
+  1-based) of every import in the given file or content, e.g. for jumping to an import
+  from an editor.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :statistics_detailed, %{statistics: statistics, imports: imports}} =
+    Parser.statistics_detailed(js_content)
+
+  [%{source: "phoenix", line: 1, column: 1} | _] = imports
+  Parser.statistics_detailed("/path/to/file.js", :path)
+  ```
+  """
+  def statistics_detailed(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.statistics_detailed_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, %{statistics: statistics, imports: imports}} ->
+        {:ok, fn_atom,
+         %{
+           statistics: Map.drop(statistics, [:__struct__]),
+           imports: Enum.map(imports, &Map.drop(&1, [:__struct__]))
+         }}
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Count the source lines of code of the given file or content: every line minus the blank
+  lines and the lines that only hold comments. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :sloc, 42} = Parser.sloc(js_content)
+  Parser.sloc("/path/to/file.js", :path)
+  ```
+  """
+  def sloc(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.count_sloc_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  List the comments of the given file or content that contain one of the keywords
+  (`TODO` and `FIXME` by default, case-sensitive), with the keyword found, the comment
+  text and its 1-based line, e.g. for a tech-debt report. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :todo_comments, [%{keyword: "TODO", text: "TODO: split", line: 3}]} =
+    Parser.todo_comments(js_content)
+
+  Parser.todo_comments("/path/to/file.js", :path, ["TODO", "HACK"])
+  ```
+  """
+  def todo_comments(file_path_or_content, type \\ :content, keywords \\ ["TODO", "FIXME"]) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.list_todo_comments_from_ast_nif(file_content, keywords)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, todos} -> {:ok, fn_atom, Enum.map(todos, &Map.drop(&1, [:__struct__]))}
+      error -> error
+    end
+  end
+
+  @doc """
+  List the string literals of the given file or content with their line, e.g. to find the
+  texts to translate. Import sources and object keys are not included. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :string_literals, [%{value: "Saved!", line: 2}]} =
+    Parser.string_literals("import \"phoenix_html\";\nalert(\"Saved!\");")
+
+  Parser.string_literals("/path/to/file.js", :path)
+  ```
+  """
+  def string_literals(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.extract_string_literals_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, literals} ->
+        {:ok, fn_atom, Enum.map(literals, &Map.drop(&1, [:__struct__]))}
+
+      error ->
+        error
+    end
+  end
+
+  @doc """
+  Detect which newer syntax features the given file or content uses: optional chaining
+  (`?.`), nullish coalescing (`??` and `??=`), top-level await and dynamic `import()`.
+  Handy to warn when a source needs a newer build target. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :features, %{optional_chaining: true, top_level_await: false}} =
+    Parser.features("const a = b?.c ?? 1;")
+  ```
+  """
+  def features(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.detect_js_features_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, features} -> {:ok, fn_atom, Map.drop(features, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  Count arbitrary node kinds in the given file or content. Kinds are named after the SWC
+  node types, like `"ArrowExpr"` or `"AwaitExpr"`; an unsupported name returns an error.
+  On success it returns a map of every requested kind to its count.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :count_node_kinds, %{"ArrowExpr" => 2}} =
+    Parser.count_node_kinds(js_content, ["ArrowExpr"])
+
+  Parser.count_node_kinds("/path/to/file.js", ["AwaitExpr", "TryStmt"], :path)
+  ```
+  """
+  def count_node_kinds(file_path_or_content, kinds, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.count_node_kinds_from_ast_nif(file_content, List.wrap(kinds))
+      end,
+      type
+    )
+  end
+
+  @doc """
+    Extend a variable of object type in the given file or content by adding additional objects to it,
+    based on their names.
+
+    This function ensures that duplicate entries are not added during the process.
+
+    This function accepts:
+    - The content or path of the JavaScript file.
+    - The name of the variable to be extended.
+    - A single object name or a list of object names to be added.
+    - The type indicating whether it's content (`:content`) or a path (`:path`).
+
+    It returns a tuple with the status, function atom, and the updated content or an error message
+    if the variable could not be found or modified.
+
+    ## Examples
+
+    ```elixir
+    alias IgniterJs.Parsers.Javascript.Parser
+
+    objects_names = ["OXCTestHook", "MishkaHooks", "MishkaHooks", "OXCTestHook"]
+
+    Parser.extend_var_object_by_object_names(js_content, "Components", "TestHook")
+    Parser.extend_var_object_by_object_names("/path/to/file.js", "Components", objects_names, :path)
+
+    {:error, :extend_var_object_by_object_names, _output} =
+      Parser.extend_var_object_by_object_names("None", "Components", objects_names)
+    ```
+  """
+  def extend_var_object_by_object_names(file_path_or_content, var, object_names, type \\ :content)
+
+  def extend_var_object_by_object_names(file_path_or_content, var, object_name, type)
+      when is_binary(object_name) do
+    extend_var_object_by_object_names(file_path_or_content, var, [object_name], type)
+  end
+
+  def extend_var_object_by_object_names(file_path_or_content, var, object_names, type) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_var_object_property_by_names_to_ast_nif(file_content, var, object_names)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend a var object like `extend_var_object_by_object_names/4`, but always return code
+  next to the message, e.g. for a preview: the updated code when the variable is found,
+  otherwise the original content untouched. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:error, :extend_var_object_preview, {^js_content, _message}} =
+    Parser.extend_var_object_preview(js_content, "Missing", "TestHook")
+
+  Parser.extend_var_object_preview("/path/to/file.js", "Components", ["TestHook"], :path)
+  ```
+  """
+  def extend_var_object_preview(file_path_or_content, var, object_names, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_var_object_property_by_names_to_ast_with_status_nif(
+          file_content,
+          var,
+          List.wrap(object_names)
+        )
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend a nested object of a var object in the given file or content, e.g. `params` of
+  `const config = { params: {} }`. The path is a list of keys (or a dotted string) leading
+  to the object to extend; missing objects on the path are created. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.extend_var_object_at_path(js_content, "config", ["params", "user"], "userId")
+  Parser.extend_var_object_at_path("/path/to/file.js", "config", "params", ["locale"], :path)
+  ```
+  """
+  def extend_var_object_at_path(
+        file_path_or_content,
+        var,
+        path,
+        object_names,
+        type \\ :content
+      ) do
+    path = if is_binary(path), do: String.split(path, ".", trim: true), else: path
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_object_at_path_ast_nif(file_content, var, path, List.wrap(object_names))
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Insert one or more statements right after the last import of the given file or content,
+  or at the top when there is no import. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.insert_statement_after_imports(js_content, "window.liveSocket = liveSocket;")
+  Parser.insert_statement_after_imports("/path/to/file.js", statements, :path)
+  ```
+  """
+  def insert_statement_after_imports(file_path_or_content, statements, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_statement_after_imports_ast_nif(file_content, statements)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Add a `window.addEventListener` call for the event to the end of the given file or
+  content, unless a listener for that event already exists. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.ensure_window_event_listener(js_content, "phx:page-loading-stop", "() => topbar.hide()")
+  Parser.ensure_window_event_listener("/path/to/file.js", event, handler, :path)
+  ```
+  """
+  def ensure_window_event_listener(file_path_or_content, event, handler, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.ensure_window_event_listener_ast_nif(file_content, event, handler)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Append `lhs = rhs;` to the end of the given file or content, unless an assignment to the
+  same left-hand side (e.g. `window.liveSocket`) already exists. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.ensure_global_assignment(js_content, "window.liveSocket", "liveSocket")
+  Parser.ensure_global_assignment("/path/to/file.js", "window.liveSocket", "liveSocket", :path)
+  ```
+  """
+  def ensure_global_assignment(file_path_or_content, lhs, rhs, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.ensure_global_assignment_ast_nif(file_content, lhs, rhs)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Wrap every top-level `console.log(...)` statement of the given file or content in
+  `if (flag) { ... }`, so the logs can be turned off in production. Other console methods
+  and logs already inside an `if` are left as they are. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.guard_console_logs(js_content, "window.DEBUG")
+  Parser.guard_console_logs("/path/to/file.js", "process.env.DEBUG", :path)
+  ```
+  """
+  def guard_console_logs(file_path_or_content, flag_expr, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.guard_console_logs_ast_nif(file_content, flag_expr)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend a variable of object type in the given file or content with shorthand and
+  `key: value` properties. Each property is either a name (added as shorthand) or a
+  `{key, value_source}` tuple whose value is parsed as a JavaScript expression.
+  Keys that already exist are skipped. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.extend_var_object_properties(js_content, "opts", ["Hooks", {"timeout", "3000"}])
+  Parser.extend_var_object_properties("/path/to/file.js", "opts", [{"timeout", "3000"}], :path)
+  ```
+  """
+  def extend_var_object_properties(file_path_or_content, var, properties, type \\ :content) do
+    properties =
+      Enum.map(List.wrap(properties), fn
+        {key, value} -> {to_string(key), value}
+        key -> {to_string(key), nil}
+      end)
+
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_var_object_properties_to_ast_nif(file_content, var, properties)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Extend several object variables of the given file or content in one pass, like
+  `extend_var_object_by_object_names/4` for each of them. It accepts a map or a list of
+  `{var_name, object_names}` tuples. On success it returns the updated code together with
+  the variables that were `extended` and the ones that `failed`.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+
+  {:ok, :extend_var_objects, %{code: code, extended: ["Components"], failed: []}} =
+    Parser.extend_var_objects(js_content, %{"Components" => ["Dialog", "ScrollArea"]})
+
+  Parser.extend_var_objects("/path/to/file.js", [{"Hooks", "CopyHook"}], :path)
+  ```
+  """
+  def extend_var_objects(file_path_or_content, extensions, type \\ :content) do
+    extensions =
+      Enum.map(extensions, fn {var, object_names} -> {var, List.wrap(object_names)} end)
+
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.extend_var_objects_batch_ast_nif(file_content, extensions)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, result} -> {:ok, fn_atom, Map.drop(result, [:__struct__])}
+      error -> error
+    end
+  end
+
+  @doc """
+  Minify the given file or content by re-emitting it without formatting whitespace and
+  comments. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.minify(js_content)
+  Parser.minify("/path/to/file.js", :path)
+  ```
+  """
+  def minify(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.minify_js_nif(file_content)
+      end,
+      type
+    )
+  end
+
+  @doc """
+  Remove every comment from the given file or content, keeping the code formatted.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.strip_comments(js_content)
+  Parser.strip_comments("/path/to/file.js", :path)
   ```
   """
-  def insert_imports(file_path_or_content, imports_lines, type \\ :content) do
+  def strip_comments(file_path_or_content, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.insert_import_to_ast_nif(file_content, imports_lines)
+        Native.strip_comments_js_nif(file_content)
       end,
       type
     )
   end
 
   @doc """
-  Remove imports from the given file or content. it accepts a single module or a list of modules.
-  It returns a tuple.
+  Compute a fingerprint of the code in the given file or content, which does not change
+  when only whitespace or comments change. It returns a tuple.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.remove_imports(js_content, "SomeModule")
-  Parser.remove_imports("/path/to/file.js", "SomeModule", :path)
+  Parser.fingerprint(js_content)
+  Parser.fingerprint("/path/to/file.js", :path)
   ```
   """
-  def remove_imports(file_path_or_content, module, type \\ :content)
-
-  def remove_imports(file_path_or_content, modules, type) do
+  def fingerprint(file_path_or_content, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.remove_import_from_ast_nif(file_content, modules)
+        Native.ast_fingerprint_nif(file_content)
       end,
       type
     )
   end
 
   @doc """
-  Check if a LiveSocket var exists in the given file or content and returns boolean.
+  Check if a name is exported from the given file or content and returns boolean.
+  Use `"default"` to check for a default export.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.exist_live_socket?(js_content)
-  Parser.exist_live_socket?(js_content, :content)
-  Parser.exist_live_socket?("/path/to/file.js", :path)
+  Parser.export_exists?(js_content, "Components")
+  Parser.export_exists?("/path/to/file.js", "default", :path)
   ```
   """
-  def exist_live_socket?(file_path_or_content, type \\ :content) do
-    elem(exist_live_socket(file_path_or_content, type), 0) == :ok
+  def export_exists?(file_path_or_content, name, type \\ :content) do
+    elem(exist_export(file_path_or_content, name, type), 0) == :ok
   end
 
   @doc """
-  Check if a LiveSocket var exists in the given file or content and returns tuple.
+  Check if a name is exported from the given file or content and returns tuple.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.exist_live_socket(js_content)
-  Parser.exist_live_socket(js_content, :content)
-  Parser.exist_live_socket("/path/to/file.js", :path)
+  Parser.exist_export(js_content, "Components")
+  Parser.exist_export("/path/to/file.js", "default", :path)
   ```
   """
-  def exist_live_socket(file_path_or_content, type \\ :content) do
+  def exist_export(file_path_or_content, name, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.find_live_socket_node_from_ast_nif(file_content)
+        Native.contains_export_from_ast_nif(file_content, name)
       end,
       type
     )
   end
 
   @doc """
-  Check if a specific var exists in the given file or content and returns boolean.
+  List the exported functions and classes of the given file or content with the text of
+  the `/** ... */` comment right before each export (`nil` when undocumented), for API
+  docs generation. It returns a tuple.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.exist_live_socket?(js_content)
-  Parser.exist_live_socket?(js_content, :content)
-  Parser.exist_live_socket?("/path/to/file.js", :path)
+
+  {:ok, :jsdoc, [%{name: "connect", doc: "Connects the socket."}]} =
+    Parser.jsdoc(js_content)
+
+  Parser.jsdoc("/path/to/file.js", :path)
   ```
   """
-  def var_exists?(file_path_or_content, type \\ :content) do
-    elem(exist_var(file_path_or_content, type), 0) == :ok
+  def jsdoc(file_path_or_content, type \\ :content) do
+    case call_nif_fn(
+           file_path_or_content,
+           __ENV__.function,
+           fn file_content ->
+             Native.extract_jsdoc_from_ast_nif(file_content)
+           end,
+           type
+         ) do
+      {:ok, fn_atom, docs} -> {:ok, fn_atom, Enum.map(docs, &Map.drop(&1, [:__struct__]))}
+      error -> error
+    end
   end
 
   @doc """
-  Check if an specific var exists in the given file or content and returns tuple.
+  Turn the default export of the given file or content into a named export, e.g.
+  `export default Components;` into `export { Components };` and
+  `export default function Foo() {}` into `export function Foo() {}`. It returns a tuple,
+  an error one when there is no default export.
+
+  An anonymous default export is named after the `:name` option (`defaultExport` by
+  default): `export default function () {}` becomes `export function setup() {}` and
+  `export default {}` becomes `const setup = {};` followed by `export { setup };`.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.exist_live_socket(js_content, var_name)
-  Parser.exist_live_socket(js_content, var_name, :content)
-  Parser.exist_live_socket("/path/to/file.js", var_name, :path)
+  Parser.convert_default_export_to_named(js_content)
+  Parser.convert_default_export_to_named(js_content, name: "setup")
+  Parser.convert_default_export_to_named("/path/to/file.js", :path)
   ```
   """
-  def exist_var(file_path_or_content, var_name, type \\ :content) do
+  def convert_default_export_to_named(file_path_or_content, type_or_opts \\ :content, opts \\ [])
+
+  def convert_default_export_to_named(file_path_or_content, opts, []) when is_list(opts) do
+    convert_default_export_to_named(file_path_or_content, :content, opts)
+  end
+
+  def convert_default_export_to_named(file_path_or_content, type, opts) do
+    name = Keyword.get(opts, :name)
+
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.contains_variable_from_ast_nif(file_content, var_name)
+        Native.convert_default_export_to_named_ast_nif(file_content, name)
       end,
       type
     )
   end
 
   @doc """
-  Extend the hook object in the given file or content. It accepts a single object
-  or a list of objects.
-  It returns a tuple.
+  Stop exporting a name inside the given file or content: it is removed from
+  `export { ... }` lists and the `export` keyword is removed from an inline declaration
+  such as `export const name = 1;`, which stays in place. It returns a tuple.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.extend_hook_object(js_content, "SomeObject")
-  Parser.extend_hook_object(js_content, ["SomeObject", "AnotherObject"], :content)
-  Parser.extend_hook_object("/path/to/file.js", "SomeObject", :path)
+  Parser.remove_export(js_content, "Components")
+  Parser.remove_export("/path/to/file.js", "setup", :path)
   ```
   """
-  def extend_hook_object(file_path_or_content, object_name, type \\ :content)
-
-  def extend_hook_object(file_path_or_content, object_name, type) when is_binary(object_name) do
-    extend_hook_object(file_path_or_content, [object_name], type)
+  def remove_export(file_path_or_content, name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.remove_export_from_ast_nif(file_content, name)
+      end,
+      type
+    )
   end
 
-  def extend_hook_object(file_path_or_content, objects_names, type) when is_list(objects_names) do
+  @doc """
+  Wrap the body of a top-level function in a `try { ... } catch (e) { console.error(e) }`
+  block inside the given file or content. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.wrap_in_try_catch(js_content, "connect")
+  Parser.wrap_in_try_catch("/path/to/file.js", "connect", :path)
+  ```
+  """
+  def wrap_in_try_catch(file_path_or_content, function_name, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.extend_hook_object_to_ast_nif(file_content, objects_names)
+        Native.wrap_in_try_catch_ast_nif(file_content, function_name)
       end,
       type
     )
   end
 
   @doc """
-  Remove objects from the hooks in the given file or content. It accepts a single o
-  bject or a list of objects.
-  It returns a tuple.
+  Get the source of a top-level function of the given file or content, e.g. for
+  documentation tooling. An arrow or function expression assigned to a `const` is returned
+  as its declaration. It returns a tuple, with an error when the function does not exist.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
-  Parser.remove_objects_from_hooks(js_content, "SomeObject")
-  Parser.remove_objects_from_hooks(js_content, ["SomeObject", "AnotherObject"], :content)
-  Parser.remove_objects_from_hooks("/path/to/file.js", "SomeObject", :path)
+  {:ok, :function_source, "function add(a, b) {" <> _} = Parser.function_source(js_content, "add")
+  Parser.function_source("/path/to/file.js", "add", :path)
   ```
   """
-  def remove_objects_from_hooks(file_path_or_content, object_name, type \\ :content)
-
-  def remove_objects_from_hooks(file_path_or_content, object_name, type)
-      when is_binary(object_name) do
-    remove_objects_from_hooks(file_path_or_content, [object_name], type)
+  def function_source(file_path_or_content, fn_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.get_function_source_from_ast_nif(file_content, fn_name)
+      end,
+      type
+    )
   end
 
-  def remove_objects_from_hooks(file_path_or_content, objects_names, type)
-      when is_list(objects_names) do
+  @doc """
+  Remove all `debugger;` statements from the given file or content. On success it returns
+  the updated code together with the number of removed statements.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.remove_debuggers(js_content)
+  Parser.remove_debuggers("/path/to/file.js", :path)
+  ```
+  """
+  def remove_debuggers(file_path_or_content, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.remove_objects_of_hooks_from_ast_nif(file_content, objects_names)
+        Native.remove_debugger_statements_ast_nif(file_content)
       end,
       type
     )
   end
 
   @doc """
-  Retrieve statistical information about the JavaScript source code, such as the number of
-  functions, classes, debugger statements, imports, try-catch blocks, and throw statements.
+  Rename a top-level function declaration and its references inside the given file or
+  content. References in nested scopes that redeclare the old name are left untouched.
+  It returns a tuple.
 
-  This function accepts either the content of the JavaScript file or the path to the file,
-  and returns a tuple with the status, function atom, and the extracted data as a map.
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.rename_function(js_content, "add", "sum")
+  Parser.rename_function("/path/to/file.js", "add", "sum", :path)
+  ```
+  """
+  def rename_function(file_path_or_content, old_name, new_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.rename_function_ast_nif(file_content, old_name, new_name)
+      end,
+      type
+    )
+  end
 
-  ## Examples
+  @doc """
+  Rename a top-level binding declared with `const`, `let`, `var`, `function` or `class`
+  and its references inside the given file or content, like `rename_function/4`.
+  References in nested scopes that redeclare the old name are left untouched.
+  It returns a tuple.
 
   ```elixir
   alias IgniterJs.Parsers.Javascript.Parser
+  Parser.rename_identifier(js_content, "csrfToken", "token")
+  Parser.rename_identifier("/path/to/file.js", "Hooks", "AppHooks", :path)
+  ```
+  """
+  def rename_identifier(file_path_or_content, old_name, new_name, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.rename_identifier_ast_nif(file_content, old_name, new_name)
+      end,
+      type
+    )
+  end
 
-  # Analyze a JavaScript source file by providing its content
-  Parser.statistics(js_content)
+  @doc """
+  Dry run of `insert_imports/4`. It reports whether inserting the imports would change the
+  given file or content, without the caller diffing strings. It returns a tuple.
 
-  # Analyze a JavaScript source file by providing its file path
-  Parser.statistics("/path/to/file.js", :path)
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :would_insert_imports, changed?} = Parser.would_insert_imports(js_content, imports)
+  Parser.would_insert_imports("/path/to/file.js", imports_lines, :path)
   ```
   """
-  def statistics(file_path_or_content, type \\ :content) do
-    {status, fn_atom, {_, data}} =
-      call_nif_fn(
-        file_path_or_content,
-        __ENV__.function,
-        fn file_content ->
-          Native.statistics_from_ast_nif(file_content)
-        end,
-        type
-      )
+  def would_insert_imports(file_path_or_content, imports_lines, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.insert_import_to_ast_would_change_nif(file_content, imports_lines)
+      end,
+      type
+    )
+  end
 
-    converted = if is_map(data), do: Map.drop(data, [:__struct__]), else: data
-    {status, fn_atom, converted}
+  @doc """
+  Dry run of `remove_imports/4`. It reports whether removing the imports would change the
+  given file or content. It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.would_remove_imports(js_content, "SomeModule")
+  Parser.would_remove_imports("/path/to/file.js", "SomeModule", :path)
+  ```
+  """
+  def would_remove_imports(file_path_or_content, modules, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.remove_import_from_ast_would_change_nif(file_content, modules)
+      end,
+      type
+    )
   end
 
   @doc """
-    Extend a variable of object type in the given file or content by adding additional objects to it,
-    based on their names.
+  Dry run of `extend_hook_object/4`. It reports whether extending the hook object would
+  change the given file or content. It accepts a single object or a list of objects.
+  It returns a tuple.
 
-    This function ensures that duplicate entries are not added during the process.
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.would_extend_hook_object(js_content, "SomeObject")
+  Parser.would_extend_hook_object("/path/to/file.js", ["SomeObject"], :path)
+  ```
+  """
+  def would_extend_hook_object(file_path_or_content, object_name, type \\ :content)
 
-    This function accepts:
-    - The content or path of the JavaScript file.
-    - The name of the variable to be extended.
-    - A single object name or a list of object names to be added.
-    - The type indicating whether it's content (`:content`) or a path (`:path`).
+  def would_extend_hook_object(file_path_or_content, object_name, type)
+      when is_binary(object_name) do
+    would_extend_hook_object(file_path_or_content, [object_name], type)
+  end
 
-    It returns a tuple with the status, function atom, and the updated content or an error message
-    if the variable could not be found or modified.
+  def would_extend_hook_object(file_path_or_content, objects_names, type)
+      when is_list(objects_names) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.extend_hook_object_to_ast_would_change_nif(file_content, objects_names)
+      end,
+      type
+    )
+  end
 
-    ## Examples
+  @doc """
+  Dry run of `remove_objects_from_hooks/3`. It reports whether removing the objects would
+  change the given file or content. It accepts a single object or a list of objects.
+  It returns a tuple.
 
-    ```elixir
-    alias IgniterJs.Parsers.Javascript.Parser
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.would_remove_objects_from_hooks(js_content, "SomeObject")
+  Parser.would_remove_objects_from_hooks("/path/to/file.js", ["SomeObject"], :path)
+  ```
+  """
+  def would_remove_objects_from_hooks(file_path_or_content, object_name, type \\ :content)
 
-    objects_names = ["OXCTestHook", "MishkaHooks", "MishkaHooks", "OXCTestHook"]
+  def would_remove_objects_from_hooks(file_path_or_content, object_name, type)
+      when is_binary(object_name) do
+    would_remove_objects_from_hooks(file_path_or_content, [object_name], type)
+  end
 
-    Parser.extend_var_object_by_object_names(js_content, "Components", "TestHook")
-    Parser.extend_var_object_by_object_names("/path/to/file.js", "Components", objects_names, :path)
+  def would_remove_objects_from_hooks(file_path_or_content, objects_names, type)
+      when is_list(objects_names) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.remove_objects_of_hooks_from_ast_would_change_nif(file_content, objects_names)
+      end,
+      type
+    )
+  end
 
-    {:error, :extend_var_object_by_object_names, _output} =
-      Parser.extend_var_object_by_object_names("None", "Components", objects_names)
-    ```
+  @doc """
+  Detect the module system of the given file or content. On success the result is one of
+  `:esm`, `:common_js` or `:script`; a file with any `import`/`export` is `:esm`.
+  It returns a tuple.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :module_kind, :esm} = Parser.module_kind(js_content)
+  Parser.module_kind("/path/to/file.js", :path)
+  ```
   """
-  def extend_var_object_by_object_names(file_path_or_content, var, object_names, type \\ :content)
+  def module_kind(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.detect_module_kind_from_ast_nif(file_content)
+      end,
+      type
+    )
+  end
 
-  def extend_var_object_by_object_names(file_path_or_content, var, object_name, type)
-      when is_binary(object_name) do
-    extend_var_object_by_object_names(file_path_or_content, var, [object_name], type)
+  @doc """
+  Check that the given file or content is valid JavaScript without modifying it. It returns
+  `{:ok, :validate, true}`, or an error tuple with the positions of the syntax errors,
+  including the ones the parser can recover from such as `with` in a module.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  Parser.validate(js_content)
+  Parser.validate("/path/to/file.js", :path)
+  ```
+  """
+  def validate(file_path_or_content, type \\ :content) do
+    call_nif_fn(
+      file_path_or_content,
+      __ENV__.function,
+      fn file_content ->
+        Native.validate_js_from_ast_nif(file_content)
+      end,
+      type
+    )
   end
 
-  def extend_var_object_by_object_names(file_path_or_content, var, object_names, type) do
+  @doc """
+  Parse and re-emit the given file or content, also returning the non-fatal issues the
+  parser recovered from (e.g. a `with` statement, which modules forbid) as warnings with
+  their positions. It returns `{:ok, :parse_with_diagnostics, {code, warnings}}`, or an
+  error tuple when the code can not be parsed.
+
+  ```elixir
+  alias IgniterJs.Parsers.Javascript.Parser
+  {:ok, :parse_with_diagnostics, {code, warnings}} = Parser.parse_with_diagnostics(js_content)
+  Parser.parse_with_diagnostics("/path/to/file.js", :path)
+  ```
+  """
+  def parse_with_diagnostics(file_path_or_content, type \\ :content) do
     call_nif_fn(
       file_path_or_content,
       __ENV__.function,
       fn file_content ->
-        Native.extend_var_object_property_by_names_to_ast_nif(file_content, var, object_names)
+        Native.parse_with_diagnostics_nif(file_content)
       end,
       type
     )
   end
+
+  # The `new LiveSocket(...)` picked by a variable name, a position or, with `nil`, the
+  # only socket of the file
+  defp socket_target(nil), do: :only
+  defp socket_target(socket) when is_binary(socket), do: {:name, socket}
+  defp socket_target(socket) when is_integer(socket) and socket >= 0, do: {:index, socket}
+
+  # Options of the NIFs that emit code, see the options of `insert_imports/4`
+  defp emit_options(opts) do
+    %{
+      semicolons: Keyword.get(opts, :semicolons, :always),
+      new_line: Keyword.get(opts, :new_line, :lf),
+      preserve_blank_lines: Keyword.get(opts, :preserve_blank_lines, false),
+      ascii_only: Keyword.get(opts, :ascii_only, false),
+      preserve_formatting: Keyword.get(opts, :preserve_formatting, false)
+    }
+  end
 end
@@ -9,6 +9,7 @@ rustler::atoms! {
     source_to_ast_nif,
     is_module_imported_from_ast_nif,
     insert_import_to_ast_nif,
+    insert_import_to_ast_with_spans_nif,
     remove_import_from_ast_nif,
     find_live_socket_node_from_ast,
     extend_hook_object_to_ast_nif,
@@ -19,6 +20,86 @@ rustler::atoms! {
     format_css_nif,
     is_css_formatted_nif,
     format_js_nif,
-    is_js_formatted_nif
+    is_js_formatted_nif,
+    format_js_batch_nif,
+    format_js_file_nif,
+    format_css_file_nif,
+    minify_js_nif,
+    insert_css_import_to_ast_nif,
+    css_class_has_declaration_from_ast_nif,
+    contains_export_from_ast_nif,
+    wrap_in_try_catch_ast_nif,
+    remove_debugger_statements_ast_nif,
+    rename_function_ast_nif,
+    insert_import_before_ast_nif,
+    insert_import_to_ast_would_change_nif,
+    remove_import_from_ast_would_change_nif,
+    extend_hook_object_to_ast_would_change_nif,
+    remove_objects_of_hooks_from_ast_would_change_nif,
+    detect_module_kind_from_ast_nif,
+    statistics_detailed_from_ast_nif,
+    normalize_imports_ast_nif,
+    count_node_kinds_from_ast_nif,
+    css_count_selectors_matching_from_ast_nif,
+    css_list_media_queries_from_ast_nif,
+    css_rename_class_in_ast_nif,
+    validate_js_from_ast_nif,
+    css_validate_css_from_ast_nif,
+    extend_var_objects_batch_ast_nif,
+    extend_var_object_properties_to_ast_nif,
+    insert_statement_after_imports_ast_nif,
+    add_hook_ast_nif,
+    extend_hook_object_to_ast_with_report_nif,
+    strip_comments_js_nif,
+    has_socket_connect_from_ast_nif,
+    ensure_window_event_listener_ast_nif,
+    css_contains_class_from_ast_nif,
+    css_statistics_from_ast_nif,
+    css_remove_empty_rules_from_ast_nif,
+    css_merge_duplicate_rules_from_ast_nif,
+    module_import_presence_from_ast_nif,
+    replace_hook_spread_ast_nif,
+    get_function_source_from_ast_nif,
+    list_todo_comments_from_ast_nif,
+    format_js_range_nif,
+    detect_js_features_from_ast_nif,
+    insert_import_to_ast_with_quote_style_nif,
+    css_contains_custom_property_from_ast_nif,
+    css_set_custom_property_from_ast_nif,
+    remove_objects_of_hooks_from_ast_with_report_nif,
+    ensure_global_assignment_ast_nif,
+    hook_exists_in_ast_nif,
+    find_import_by_local_ast_nif,
+    convert_default_export_to_named_ast_nif,
+    parse_with_diagnostics_nif,
+    css_list_selectors_from_ast_nif,
+    get_live_socket_options_from_ast_nif,
+    extend_hook_object_to_ast_for_socket_nif,
+    insert_import_if_missing_ast_nif,
+    has_csrf_token_setup_from_ast_nif,
+    extend_object_at_path_ast_nif,
+    count_sloc_from_ast_nif,
+    format_js_with_options_nif,
+    extend_hook_object_to_ast_strict_nif,
+    rename_identifier_ast_nif,
+    extend_var_object_property_by_names_to_ast_with_status_nif,
+    remove_export_from_ast_nif,
+    guard_console_logs_ast_nif,
+    import_edges_from_ast_nif,
+    css_is_imported_from_ast_nif,
+    diagnose_live_socket_from_ast_nif,
+    css_insert_rule_to_ast_nif,
+    css_set_declaration_in_ast_nif,
+    find_duplicate_import_sources_from_ast_nif,
+    extract_jsdoc_from_ast_nif,
+    css_selector_specificity_from_ast_nif,
+    normalize_hooks_order_ast_nif,
+    is_import_used_ast_nif,
+    css_rename_custom_property_in_ast_nif,
+    ast_fingerprint_nif,
+    hoist_imports_ast_nif,
+    capabilities_nif,
+    has_mixed_indentation_nif,
+    extract_string_literals_from_ast_nif
     // Resource Atoms
 }
@@ -0,0 +1,230 @@
+//! Describes what this build of the NIF library supports, for diagnostics across the NIF
+//! boundary, e.g. to attach to a bug report about parsing.
+
+use crate::atoms;
+use crate::helpers::encode_response;
+use rustler::{Env, NifResult, NifStruct, Term};
+
+/// Version of the SWC parser crates, keep in sync with `Cargo.toml`.
+const SWC_ECMA_PARSER_VERSION: &str = "8.0.2";
+const SWC_CSS_PARSER_VERSION: &str = "6.0.0";
+/// Version of the biome crates used by the formatters, keep in sync with `Cargo.toml`.
+const BIOME_VERSION: &str = "0.5.7";
+
+/// Every NIF exposed by the library, checked against the `*_ex.rs` sources in the tests.
+const OPERATIONS: &[&str] = &[
+    "is_module_imported_from_ast_nif",
+    "module_import_presence_from_ast_nif",
+    "insert_import_to_ast_nif",
+    "insert_import_to_ast_with_spans_nif",
+    "remove_import_from_ast_nif",
+    "find_live_socket_node_from_ast_nif",
+    "has_socket_connect_from_ast_nif",
+    "has_csrf_token_setup_from_ast_nif",
+    "contains_variable_from_ast_nif",
+    "extend_hook_object_to_ast_nif",
+    "insert_import_if_missing_ast_nif",
+    "extend_hook_object_to_ast_for_socket_nif",
+    "remove_objects_of_hooks_from_ast_nif",
+    "statistics_from_ast_nif",
+    "extend_var_object_property_by_names_to_ast_with_status_nif",
+    "extend_object_at_path_ast_nif",
+    "extend_var_object_property_by_names_to_ast_nif",
+    "minify_js_nif",
+    "strip_comments_js_nif",
+    "ast_fingerprint_nif",
+    "contains_export_from_ast_nif",
+    "wrap_in_try_catch_ast_nif",
+    "remove_debugger_statements_ast_nif",
+    "rename_identifier_ast_nif",
+    "rename_function_ast_nif",
+    "insert_import_before_ast_nif",
+    "insert_import_to_ast_would_change_nif",
+    "remove_import_from_ast_would_change_nif",
+    "extend_hook_object_to_ast_would_change_nif",
+    "remove_objects_of_hooks_from_ast_would_change_nif",
+    "detect_module_kind_from_ast_nif",
+    "statistics_detailed_from_ast_nif",
+    "count_sloc_from_ast_nif",
+    "list_todo_comments_from_ast_nif",
+    "extract_string_literals_from_ast_nif",
+    "detect_js_features_from_ast_nif",
+    "insert_import_to_ast_with_quote_style_nif",
+    "find_import_by_local_ast_nif",
+    "import_edges_from_ast_nif",
+    "extract_jsdoc_from_ast_nif",
+    "convert_default_export_to_named_ast_nif",
+    "remove_export_from_ast_nif",
+    "parse_with_diagnostics_nif",
+    "normalize_imports_ast_nif",
+    "hoist_imports_ast_nif",
+    "find_duplicate_import_sources_from_ast_nif",
+    "is_import_used_ast_nif",
+    "count_node_kinds_from_ast_nif",
+    "validate_js_from_ast_nif",
+    "extend_var_objects_batch_ast_nif",
+    "extend_var_object_properties_to_ast_nif",
+    "insert_statement_after_imports_ast_nif",
+    "add_hook_ast_nif",
+    "extend_hook_object_to_ast_with_report_nif",
+    "extend_hook_object_to_ast_strict_nif",
+    "remove_objects_of_hooks_from_ast_with_report_nif",
+    "guard_console_logs_ast_nif",
+    "ensure_global_assignment_ast_nif",
+    "hook_exists_in_ast_nif",
+    "get_live_socket_options_from_ast_nif",
+    "diagnose_live_socket_from_ast_nif",
+    "ensure_window_event_listener_ast_nif",
+    "replace_hook_spread_ast_nif",
+    "normalize_hooks_order_ast_nif",
+    "get_function_source_from_ast_nif",
+    "format_js_nif",
+    "format_js_with_options_nif",
+    "is_js_formatted_nif",
+    "has_mixed_indentation_nif",
+    "format_js_batch_nif",
+    "format_js_file_nif",
+    "format_js_range_nif",
+    "insert_css_import_to_ast_nif",
+    "css_insert_rule_to_ast_nif",
+    "css_set_declaration_in_ast_nif",
+    "css_is_imported_from_ast_nif",
+    "css_class_has_declaration_from_ast_nif",
+    "css_contains_class_from_ast_nif",
+    "css_contains_custom_property_from_ast_nif",
+    "css_set_custom_property_from_ast_nif",
+    "css_list_selectors_from_ast_nif",
+    "css_selector_specificity_from_ast_nif",
+    "css_count_selectors_matching_from_ast_nif",
+    "css_list_media_queries_from_ast_nif",
+    "css_rename_class_in_ast_nif",
+    "css_rename_custom_property_in_ast_nif",
+    "css_remove_empty_rules_from_ast_nif",
+    "css_merge_duplicate_rules_from_ast_nif",
+    "css_validate_css_from_ast_nif",
+    "css_statistics_from_ast_nif",
+    "format_css_nif",
+    "is_css_formatted_nif",
+    "format_css_file_nif",
+    "capabilities_nif",
+];
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Capabilities"]
+pub struct Capabilities {
+    pub version: String,
+    pub typescript: bool,
+    pub jsx: bool,
+    pub css_nesting: bool,
+    pub swc_ecma_parser_version: String,
+    pub swc_css_parser_version: String,
+    pub biome_version: String,
+    pub operations: Vec<String>,
+}
+
+/// Reports the crate version, the syntax the parsers accept and the supported operations.
+///
+/// JavaScript is parsed as plain ECMAScript (no TypeScript or JSX, see
+/// [`crate::parsers::javascript::helpers::parse`]), CSS with nesting enabled.
+pub fn capabilities() -> Capabilities {
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION").to_string(),
+        typescript: false,
+        jsx: false,
+        css_nesting: true,
+        swc_ecma_parser_version: SWC_ECMA_PARSER_VERSION.to_string(),
+        swc_css_parser_version: SWC_CSS_PARSER_VERSION.to_string(),
+        biome_version: BIOME_VERSION.to_string(),
+        operations: OPERATIONS.iter().map(|name| name.to_string()).collect(),
+    }
+}
+
+#[rustler::nif]
+pub fn capabilities_nif(env: Env) -> NifResult<Term> {
+    encode_response(env, atoms::ok(), atoms::capabilities_nif(), capabilities())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nif_names(source: &str) -> Vec<String> {
+        source
+            .split("#[rustler::nif")
+            .skip(1)
+            .filter_map(|chunk| chunk.split_once(']'))
+            .map(|(_, rest)| rest.trim_start())
+            .filter_map(|rest| {
+                rest.strip_prefix("pub ")
+                    .unwrap_or(rest)
+                    .strip_prefix("fn ")
+            })
+            .filter_map(|rest| rest.split('(').next())
+            .map(|name| name.trim().to_string())
+            .collect()
+    }
+
+    /// The `name = "version"` (or `name = { version = "version" }`) lines of `Cargo.toml`,
+    /// with the leading `=` of a version requirement removed.
+    fn manifest_versions(manifest: &str) -> Vec<(&str, &str)> {
+        manifest
+            .lines()
+            .filter_map(|line| line.split_once('='))
+            .filter_map(|(key, value)| Some((key.trim(), value.split('"').nth(1)?)))
+            .map(|(key, version)| (key, version.trim_start_matches('=')))
+            .collect()
+    }
+
+    #[test]
+    fn test_versions_match_cargo_toml() {
+        let versions = manifest_versions(include_str!("../Cargo.toml"));
+        let version_of = |name: &str| {
+            versions
+                .iter()
+                .find(|(key, _)| *key == name)
+                .map(|(_, version)| *version)
+        };
+
+        assert_eq!(version_of("swc_ecma_parser"), Some(SWC_ECMA_PARSER_VERSION));
+        assert_eq!(version_of("swc_css_parser"), Some(SWC_CSS_PARSER_VERSION));
+
+        let biome_versions: Vec<&str> = versions
+            .iter()
+            .filter(|(key, _)| key.starts_with("biome_"))
+            .map(|(_, version)| *version)
+            .collect();
+        assert!(!biome_versions.is_empty());
+        assert!(biome_versions
+            .iter()
+            .all(|version| *version == BIOME_VERSION));
+    }
+
+    #[test]
+    fn test_capabilities() {
+        let capabilities = capabilities();
+
+        assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+        assert!(!capabilities.typescript);
+        assert!(!capabilities.jsx);
+        assert!(capabilities.css_nesting);
+        assert!(!capabilities.swc_ecma_parser_version.is_empty());
+        assert!(!capabilities.swc_css_parser_version.is_empty());
+        assert!(!capabilities.biome_version.is_empty());
+
+        let mut expected: Vec<String> = [
+            include_str!("parsers/javascript/ast_ex.rs"),
+            include_str!("parsers/javascript/formatter_ex.rs"),
+            include_str!("parsers/css/ast_ex.rs"),
+            include_str!("parsers/css/formatter_ex.rs"),
+            include_str!("capabilities.rs"),
+        ]
+        .iter()
+        .flat_map(|source| nif_names(source))
+        .collect();
+        expected.sort();
+
+        let mut operations = capabilities.operations;
+        operations.sort();
+        assert_eq!(operations, expected);
+    }
+}
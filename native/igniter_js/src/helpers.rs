@@ -1,8 +1,8 @@
-//! Helper functions for encoding and formatting responses.
+//! Helper functions shared by the JavaScript and CSS parsers.
 //!
 //! This module provides utility functions for encoding consistent responses
-//! in Elixir NIFs using Rust. It leverages the Rustler library for seamless
-//! integration with the Erlang VM.
+//! in Elixir NIFs using Rust, and the checks every editor runs on its input. It leverages
+//! the Rustler library for seamless integration with the Erlang VM.
 
 use rustler::{Encoder, Env, NifResult, Term};
 
@@ -53,3 +53,26 @@ where
 {
     Ok((status, source, message).encode(env))
 }
+
+/// Error of the editors that need an edit target, see [`ensure_source_not_empty`].
+pub const EMPTY_SOURCE_ERROR: &str = "The source code is empty, there is nothing to edit";
+
+/// Fails when `file_content` is empty or only whitespace.
+///
+/// An empty source parses to an empty module, so editors behave consistently on it:
+/// - Editors that only clean up or reorder code (e.g. removing imports or minifying)
+///   have nothing to do and return `Ok("")`.
+/// - Editors that add code (e.g. inserting imports or statements) return only the added
+///   code.
+/// - Editors that need an edit target (e.g. the `liveSocket` hooks object, a variable or
+///   a function to rename) call this first and return [`EMPTY_SOURCE_ERROR`], instead of
+///   the generic not found message.
+///
+/// The CSS editors follow the same rules.
+pub fn ensure_source_not_empty(file_content: &str) -> Result<(), String> {
+    if file_content.trim().is_empty() {
+        Err(EMPTY_SOURCE_ERROR.to_string())
+    } else {
+        Ok(())
+    }
+}
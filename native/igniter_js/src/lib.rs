@@ -1,8 +1,13 @@
 pub mod atoms;
+pub mod capabilities;
 pub mod helpers;
 pub mod parsers {
     pub mod css;
     pub mod javascript;
 }
 
+// Every NIF parses (and most re-emit) a whole JS/CSS source, so all of them are scheduled
+// as `DirtyCpu`. We deliberately don't split on input size: a typical Phoenix `app.js`
+// (~2 KB) already takes around 1ms to parse and emit, so the only safe threshold would be
+// a few hundred bytes, and the dirty-scheduler hop for such tiny inputs costs microseconds.
 rustler::init!("Elixir.IgniterJs.Native");
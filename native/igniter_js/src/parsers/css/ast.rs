@@ -0,0 +1,2497 @@
+//! Utility functions for manipulating CSS Abstract Syntax Trees (ASTs).
+//!
+//! The stylesheet is parsed with SWC to find the nodes an operation cares about, and the
+//! edits are then applied to the original source text at the spans of those nodes. This
+//! keeps comments and the user's formatting intact outside of the edited regions.
+//!
+//! An empty stylesheet is handled like an empty JavaScript source, see
+//! [`ensure_source_not_empty`]: the cleanup editors return it as is, the editors that add
+//! code return only the added code, and the renames fail with [`EMPTY_SOURCE_ERROR`].
+//!
+//! [`EMPTY_SOURCE_ERROR`]: crate::helpers::EMPTY_SOURCE_ERROR
+
+use std::ops::Range;
+
+use crate::helpers::ensure_source_not_empty;
+
+use swc_common::{sync::Lrc, FileName, SourceFile, SourceMap, Span, Spanned};
+use swc_css_ast::*;
+use swc_css_parser::{
+    error::{Error, ErrorKind},
+    parse_file,
+    parser::ParserConfig,
+};
+use swc_css_visit::{Visit, VisitWith};
+
+/// Parses CSS source code for the editing functions.
+///
+/// The parser recovers from most errors, and those are tolerated so stylesheets with syntax
+/// SWC doesn't know can still be edited. An unexpected end of file, e.g. an unterminated
+/// `body { color: red`, is an error though: the edits are applied to the source text, and
+/// anything appended after an unterminated block would end up inside of it.
+pub fn parse(file_content: &str) -> Result<(Stylesheet, Lrc<SourceFile>), String> {
+    let (stylesheet, fm, cm, errors) = parse_stylesheet(file_content)?;
+
+    match errors
+        .into_iter()
+        .find(|error| matches!(error.kind(), ErrorKind::Eof))
+    {
+        Some(error) => Err(format!(
+            "Failed to parse stylesheet: {}",
+            describe_parse_error(&cm, error)
+        )),
+        None => Ok((stylesheet, fm)),
+    }
+}
+
+/// Same as [`parse`], also returning the errors the parser recovered from, described like
+/// the fatal error, in source order.
+fn parse_with_recovered_errors(
+    file_content: &str,
+) -> Result<(Stylesheet, Lrc<SourceFile>, Vec<String>), String> {
+    let (stylesheet, fm, cm, errors) = parse_stylesheet(file_content)?;
+
+    let errors = errors
+        .into_iter()
+        .map(|error| describe_parse_error(&cm, error))
+        .collect();
+
+    Ok((stylesheet, fm, errors))
+}
+
+/// Parses the stylesheet, keeping the errors the parser recovered from as they are.
+#[allow(clippy::type_complexity)]
+fn parse_stylesheet(
+    file_content: &str,
+) -> Result<(Stylesheet, Lrc<SourceFile>, Lrc<SourceMap>, Vec<Error>), String> {
+    let cm: Lrc<SourceMap> = Default::default();
+
+    let fm = cm.new_source_file(
+        FileName::Custom("virtual_file.css".into()).into(),
+        file_content.into(),
+    );
+
+    let mut errors = vec![];
+
+    let stylesheet =
+        parse_file::<Stylesheet>(&fm, None, parser_config(), &mut errors).map_err(|error| {
+            format!(
+                "Failed to parse stylesheet: {}",
+                describe_parse_error(&cm, error)
+            )
+        })?;
+
+    Ok((stylesheet, fm, cm, errors))
+}
+
+/// Parser settings shared by every function of this module.
+///
+/// Native nesting (`.card { & .title { } }`) is part of the default grammar; legacy nesting
+/// is enabled as well so older nested stylesheets parse. Nested rules are children of their
+/// parent block, so every visitor of this module reaches them.
+fn parser_config() -> ParserConfig {
+    ParserConfig {
+        legacy_nesting: true,
+        ..Default::default()
+    }
+}
+
+/// Turns a parser error into a `message at line L, column C` string (both 1-based).
+fn describe_parse_error(cm: &Lrc<SourceMap>, error: Error) -> String {
+    let message = error.message().to_string();
+    let (span, _kind) = *error.into_inner();
+    let loc = cm.lookup_char_pos(span.lo);
+
+    format!("{} at line {}, column {}", message, loc.line, loc.col.0 + 1)
+}
+
+/// Replaces the given source ranges, which must not overlap, and keeps everything else of
+/// the source (including comments and formatting) untouched.
+fn apply_edits(file_content: &str, mut edits: Vec<(Range<usize>, String)>) -> String {
+    edits.sort_by_key(|(range, _)| range.start);
+
+    let mut output = String::with_capacity(file_content.len());
+    let mut position = 0;
+    for (range, replacement) in edits {
+        output.push_str(&file_content[position..range.start]);
+        output.push_str(&replacement);
+        position = range.end;
+    }
+    output.push_str(&file_content[position..]);
+    output
+}
+
+/// Widens the range of a node being removed to the indentation before it and the blank
+/// space after it up to the end of its line, so no empty line is left behind.
+fn removal_range(file_content: &str, range: Range<usize>) -> Range<usize> {
+    let before = &file_content[..range.start];
+    let indent = before.len() - before.trim_end_matches([' ', '\t']).len();
+    let start = if before[..before.len() - indent].ends_with('\n') || indent == before.len() {
+        range.start - indent
+    } else {
+        range.start
+    };
+
+    let after = &file_content[range.end..];
+    let trailing = after.len() - after.trim_start_matches([' ', '\t']).len();
+    let end = if after[trailing..].starts_with('\n') {
+        range.end + trailing + 1
+    } else if after[trailing..].starts_with("\r\n") {
+        range.end + trailing + 2
+    } else {
+        range.end
+    };
+
+    start..end
+}
+
+/// Converts a node span into a byte range of the source the file was created from.
+pub fn source_range(fm: &SourceFile, span: Span) -> Range<usize> {
+    (span.lo - fm.start_pos).0 as usize..(span.hi - fm.start_pos).0 as usize
+}
+
+// ###################################################################################
+// ####################### (▰˘◡˘▰) Work with AST import (▰˘◡˘▰) ######################
+// ###################################################################################
+
+#[derive(Debug)]
+struct CssImport {
+    span: Span,
+    href: String,
+    href_span: Span,
+    prelude_span: Span,
+}
+
+#[derive(Debug, Default)]
+struct ImportCollector {
+    imports: Vec<CssImport>,
+}
+
+impl Visit for ImportCollector {
+    fn visit_at_rule(&mut self, node: &AtRule) {
+        if let Some(prelude) = &node.prelude {
+            if let AtRulePrelude::ImportPrelude(import) = &**prelude {
+                self.imports.push(CssImport {
+                    span: node.span,
+                    href: import_href(import),
+                    href_span: import.href.span(),
+                    prelude_span: import.span,
+                });
+            }
+        }
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Returns the href of an import without its `url()` wrapper or quotes, so that
+/// `@import url("x.css")`, `@import url(x.css)` and `@import "x.css"` compare as equal.
+fn import_href(import: &ImportPrelude) -> String {
+    match &*import.href {
+        ImportHref::Str(href) => href.value.to_string(),
+        ImportHref::Url(url) => match url.value.as_deref() {
+            Some(UrlValue::Str(href)) => href.value.to_string(),
+            Some(UrlValue::Raw(raw)) => raw.value.to_string(),
+            None => String::new(),
+        },
+    }
+    .trim()
+    .to_string()
+}
+
+fn collect_imports(stylesheet: &Stylesheet) -> Vec<CssImport> {
+    let mut collector = ImportCollector::default();
+    stylesheet.visit_with(&mut collector);
+    collector.imports
+}
+
+/// Returns the `layer(...)`, `supports()` and media conditions written after the href of an
+/// import, with whitespace collapsed, e.g. `layer(base) screen and (min-width: 40em)`.
+fn import_conditions(source: &str, fm: &SourceFile, import: &CssImport) -> String {
+    let start = source_range(fm, import.href_span).end;
+    let end = source_range(fm, import.prelude_span).end.max(start);
+
+    source[start..end]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+/// Checks whether the stylesheet already has the given `@import`.
+///
+/// By default only the hrefs are compared, whichever of the `url()` or string forms either
+/// side uses, like `insert_import_to_ast` does. In strict mode the layer and the `supports()`
+/// and media conditions must match as well (ignoring whitespace), so `@import "x.css" print;`
+/// and `@import "x.css";` are different imports.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `import_line`: The `@import` rule to look for, e.g. `@import "theme.css" layer(base);`.
+/// - `strict`: Also compare the layer and the conditions of the imports.
+///
+/// # Returns
+/// A `Result` containing `true` when the import exists and `false` otherwise, or an error
+/// message if parsing fails or `import_line` has no `@import` rule.
+pub fn is_imported_from_ast(
+    file_content: &str,
+    import_line: &str,
+    strict: bool,
+) -> Result<bool, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+    let (wanted, wanted_fm) = parse(import_line)?;
+
+    let wanted = collect_imports(&wanted)
+        .into_iter()
+        .next()
+        .ok_or_else(|| "No @import rule found in the given import line".to_string())?;
+    let wanted_conditions = import_conditions(import_line, &wanted_fm, &wanted);
+
+    Ok(collect_imports(&stylesheet).iter().any(|import| {
+        import.href == wanted.href
+            && (!strict || import_conditions(file_content, &fm, import) == wanted_conditions)
+    }))
+}
+
+/// Inserts new `@import` rules into CSS source code.
+///
+/// Every `@import` found in `import_lines` is parsed with the CSS parser, and its source
+/// text is inserted as written, so the `url()`/string form, the `layer(...)` name and the
+/// media or `supports()` conditions are kept. Imports whose href is already imported by
+/// the stylesheet are skipped, whichever of the `url()` or string forms either side uses.
+///
+/// New rules are placed right after the last existing `@import`, or at the top of the file
+/// (after a leading `@charset`) when there is none, since `@import` must precede other rules.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `import_lines`: The `@import` rules to be added, separated by newlines.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if parsing fails.
+pub fn insert_import_to_ast(file_content: &str, import_lines: &str) -> Result<String, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+    let (new_imports, imports_fm) = parse(import_lines)?;
+
+    let existing_imports = collect_imports(&stylesheet);
+    let mut known_hrefs: Vec<String> = existing_imports
+        .iter()
+        .map(|import| import.href.clone())
+        .collect();
+
+    let mut lines = vec![];
+    for import in collect_imports(&new_imports) {
+        if known_hrefs.contains(&import.href) {
+            continue;
+        }
+
+        let text = import_lines[source_range(&imports_fm, import.span)].trim();
+        let text = text.strip_suffix(';').unwrap_or(text);
+        lines.push(format!("{};", text));
+        known_hrefs.push(import.href);
+    }
+
+    if lines.is_empty() {
+        return Ok(file_content.to_string());
+    }
+
+    let anchor = existing_imports
+        .last()
+        .map(|import| import.span)
+        .or_else(|| leading_charset_span(&stylesheet));
+
+    match anchor {
+        Some(span) => {
+            let position = rule_end(file_content, source_range(&fm, span).end);
+            Ok(format!(
+                "{}\n{}{}",
+                &file_content[..position],
+                lines.join("\n"),
+                &file_content[position..]
+            ))
+        }
+        None => Ok(format!("{}\n{}", lines.join("\n"), file_content)),
+    }
+}
+
+fn leading_charset_span(stylesheet: &Stylesheet) -> Option<Span> {
+    match stylesheet.rules.first() {
+        Some(Rule::AtRule(at_rule)) => match &at_rule.name {
+            AtRuleName::Ident(name) if name.value.eq_ignore_ascii_case("charset") => {
+                Some(at_rule.span)
+            }
+            _ => None,
+        },
+        _ => None,
+    }
+}
+
+/// Moves `position` past the `;` that terminates a statement at-rule, when the rule span
+/// stopped right before it.
+fn rule_end(file_content: &str, position: usize) -> usize {
+    let rest = &file_content[position..];
+    let trimmed = rest.trim_start();
+    if trimmed.starts_with(';') {
+        position + (rest.len() - trimmed.len()) + 1
+    } else {
+        position
+    }
+}
+
+// ###################################################################################
+// ################### (▰˘◡˘▰) Work with AST Rules and Classes (▰˘◡˘▰) ###############
+// ###################################################################################
+
+struct ClassSelectorFinder<'a> {
+    class_name: &'a str,
+    found: bool,
+}
+
+impl Visit for ClassSelectorFinder<'_> {
+    fn visit_class_selector(&mut self, node: &ClassSelector) {
+        if node.text.value == *self.class_name {
+            self.found = true;
+        }
+    }
+}
+
+/// Whether the subject of a complex selector, its last compound selector, has the class
+/// itself: `.card .btn` is a rule for `btn` but not for `card`. Classes in the arguments of
+/// a pseudo-class, such as `:not(.card)`, are not counted.
+fn complex_selector_has_class(selector: &ComplexSelector, class_name: &str) -> bool {
+    let subject = selector
+        .children
+        .iter()
+        .rev()
+        .find_map(|child| match child {
+            ComplexSelectorChildren::CompoundSelector(compound) => Some(compound),
+            _ => None,
+        });
+
+    subject.is_some_and(|compound| {
+        compound.subclass_selectors.iter().any(|subclass| {
+            matches!(subclass, SubclassSelector::Class(class) if class.text.value == *class_name)
+        })
+    })
+}
+
+fn selector_has_class(prelude: &QualifiedRulePrelude, class_name: &str) -> bool {
+    match prelude {
+        QualifiedRulePrelude::SelectorList(list) => list
+            .children
+            .iter()
+            .any(|selector| complex_selector_has_class(selector, class_name)),
+        QualifiedRulePrelude::RelativeSelectorList(list) => list
+            .children
+            .iter()
+            .any(|relative| complex_selector_has_class(&relative.selector, class_name)),
+        QualifiedRulePrelude::ListOfComponentValues(_) => false,
+    }
+}
+
+/// Checks if any selector of the stylesheet uses `class_name`, including selectors of
+/// nested rules and of rules inside `@media` and other blocks.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `class_name`: The class name, with or without the leading dot.
+///
+/// # Returns
+/// A `Result` containing `true` when the class is used and `false` otherwise, or an error
+/// message if parsing fails.
+pub fn contains_class_from_ast(file_content: &str, class_name: &str) -> Result<bool, String> {
+    let (stylesheet, _fm) = parse(file_content)?;
+
+    let mut finder = ClassSelectorFinder {
+        class_name: class_name.trim_start_matches('.'),
+        found: false,
+    };
+    stylesheet.visit_with(&mut finder);
+
+    Ok(finder.found)
+}
+
+/// Splits a declaration into its property name and value source text, leaving out the
+/// `!important` flag.
+fn declaration_parts<'s>(
+    source: &'s str,
+    fm: &SourceFile,
+    declaration: &Declaration,
+) -> Option<(&'s str, &'s str)> {
+    let mut range = source_range(fm, declaration.span);
+    if let Some(important) = &declaration.important {
+        range.end = source_range(fm, important.span).start;
+    }
+
+    let text = source[range].trim().trim_end_matches(';');
+    let (property, value) = text.split_once(':')?;
+    Some((property.trim(), value.trim()))
+}
+
+/// The source text of a selector list with its whitespace collapsed, e.g. `.btn, .button`.
+fn selector_text(source: &str, fm: &SourceFile, prelude: &QualifiedRulePrelude) -> String {
+    source[source_range(fm, prelude.span())]
+        .split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+}
+
+fn without_whitespace(value: &str) -> String {
+    value.split_whitespace().collect()
+}
+
+struct DeclarationFinder<'a> {
+    source: &'a str,
+    fm: &'a SourceFile,
+    class_name: &'a str,
+    property: &'a str,
+    value: String,
+    found: bool,
+}
+
+impl Visit for DeclarationFinder<'_> {
+    fn visit_qualified_rule(&mut self, node: &QualifiedRule) {
+        if selector_has_class(&node.prelude, self.class_name) {
+            for component in &node.block.value {
+                if let ComponentValue::Declaration(declaration) = component {
+                    if let Some((property, value)) =
+                        declaration_parts(self.source, self.fm, declaration)
+                    {
+                        if property.eq_ignore_ascii_case(self.property)
+                            && without_whitespace(value) == self.value
+                        {
+                            self.found = true;
+                        }
+                    }
+                }
+            }
+        }
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Checks if a rule selecting `class_name` declares `property` with the given `value`.
+///
+/// Only rules whose selector targets the class itself are considered: `.card .btn` is a
+/// rule for `btn`, not for `card`, and neither is `.item:not(.card)`.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `class_name`: The class name without the leading dot, e.g. `btn`.
+/// - `property`: The property name, compared case-insensitively.
+/// - `value`: The expected value; whitespace is ignored when comparing, and the
+///   `!important` flag of the declaration is not part of its value.
+///
+/// # Returns
+/// A `Result` containing `true` when such a declaration exists and `false` otherwise,
+/// or an error message if parsing fails.
+pub fn class_has_declaration_from_ast(
+    file_content: &str,
+    class_name: &str,
+    property: &str,
+    value: &str,
+) -> Result<bool, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut finder = DeclarationFinder {
+        source: file_content,
+        fm: &fm,
+        class_name: class_name.trim_start_matches('.'),
+        property,
+        value: without_whitespace(value),
+        found: false,
+    };
+    stylesheet.visit_with(&mut finder);
+
+    Ok(finder.found)
+}
+
+struct ClassPrefixCounter<'a> {
+    prefix: &'a str,
+    count: usize,
+}
+
+impl Visit for ClassPrefixCounter<'_> {
+    fn visit_class_selector(&mut self, node: &ClassSelector) {
+        if node.text.value.starts_with(self.prefix) {
+            self.count += 1;
+        }
+    }
+}
+
+/// Counts the class selectors whose name starts with `prefix`, e.g. every `.tw-` utility
+/// class. Each occurrence in a selector is counted, also inside `@media` and other blocks.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `prefix`: The class name prefix, with or without the leading dot.
+///
+/// # Returns
+/// A `Result` containing the number of matching class selectors, or an error message if
+/// parsing fails.
+pub fn count_selectors_matching_from_ast(
+    file_content: &str,
+    prefix: &str,
+) -> Result<usize, String> {
+    let (stylesheet, _fm) = parse(file_content)?;
+
+    let mut counter = ClassPrefixCounter {
+        prefix: prefix.trim_start_matches('.'),
+        count: 0,
+    };
+    stylesheet.visit_with(&mut counter);
+
+    Ok(counter.count)
+}
+
+struct ClassSelectorCollector<'a> {
+    class_name: &'a str,
+    spans: Vec<Span>,
+}
+
+impl Visit for ClassSelectorCollector<'_> {
+    fn visit_class_selector(&mut self, node: &ClassSelector) {
+        if node.text.value == *self.class_name {
+            self.spans.push(node.text.span);
+        }
+    }
+}
+
+/// Renames a class in every selector of the stylesheet, including compound (`.a.b`,
+/// `.a::-webkit-scrollbar`), grouped and nested selectors such as `:not(.a)`.
+///
+/// Only the class names are replaced in the source text, so comments and formatting are
+/// kept. Strings, custom properties and other values are never touched.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `old_name`: The class name to rename, with or without the leading dot.
+/// - `new_name`: The new class name, with or without the leading dot.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if the source is empty,
+/// parsing fails or no selector uses `old_name`.
+pub fn rename_class_in_ast(
+    file_content: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut collector = ClassSelectorCollector {
+        class_name: old_name.trim_start_matches('.'),
+        spans: Vec::new(),
+    };
+    stylesheet.visit_with(&mut collector);
+
+    if collector.spans.is_empty() {
+        return Err(format!(
+            "No selector uses the class `{}`",
+            collector.class_name
+        ));
+    }
+
+    let new_name = new_name.trim_start_matches('.');
+    let edits = collector
+        .spans
+        .iter()
+        .map(|span| (source_range(&fm, *span), new_name.to_string()))
+        .collect();
+
+    Ok(apply_edits(file_content, edits))
+}
+
+/// Distinct class and id names used by the selectors of a stylesheet, in order of first use.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectorSummary {
+    pub classes: Vec<String>,
+    pub ids: Vec<String>,
+}
+
+impl Visit for SelectorSummary {
+    fn visit_class_selector(&mut self, node: &ClassSelector) {
+        if !self.classes.iter().any(|class| node.text.value == **class) {
+            self.classes.push(node.text.value.to_string());
+        }
+    }
+
+    fn visit_id_selector(&mut self, node: &IdSelector) {
+        if !self.ids.iter().any(|id| node.text.value == **id) {
+            self.ids.push(node.text.value.to_string());
+        }
+    }
+}
+
+/// Lists the distinct class and id names used by the selectors of the stylesheet,
+/// including nested selectors such as `:not(.a)` and rules inside `@media` blocks, e.g. to
+/// build a map of the CSS.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the [`SelectorSummary`], names without their `.` or `#`, or an
+/// error message if parsing fails.
+pub fn list_selectors_from_ast(file_content: &str) -> Result<SelectorSummary, String> {
+    let (stylesheet, _fm) = parse(file_content)?;
+
+    let mut summary = SelectorSummary::default();
+    stylesheet.visit_with(&mut summary);
+
+    Ok(summary)
+}
+
+/// A specificity as `(ids, classes, elements)`, where classes also counts attribute and
+/// pseudo-class selectors, and elements counts type and pseudo-element selectors.
+pub type Specificity = (usize, usize, usize);
+
+/// The specificity of a top-level rule, see [`selector_specificity_from_ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SelectorSpecificity {
+    /// The selector list of the rule, with its whitespace collapsed.
+    pub selector: String,
+    pub specificity: Specificity,
+}
+
+/// Keeps the highest specificity of the complex selectors it visits, e.g. the selectors of
+/// a selector list or of the arguments of `:is()`.
+#[derive(Default)]
+struct MaxSpecificity {
+    max: Specificity,
+}
+
+impl Visit for MaxSpecificity {
+    fn visit_complex_selector(&mut self, node: &ComplexSelector) {
+        let mut counter = SpecificityCounter::default();
+        node.visit_children_with(&mut counter);
+        self.max = self.max.max(counter.specificity);
+    }
+}
+
+/// Adds up the specificity of the simple selectors of one complex selector.
+#[derive(Default)]
+struct SpecificityCounter {
+    specificity: Specificity,
+}
+
+impl Visit for SpecificityCounter {
+    fn visit_id_selector(&mut self, _node: &IdSelector) {
+        self.specificity.0 += 1;
+    }
+
+    fn visit_class_selector(&mut self, _node: &ClassSelector) {
+        self.specificity.1 += 1;
+    }
+
+    fn visit_attribute_selector(&mut self, _node: &AttributeSelector) {
+        self.specificity.1 += 1;
+    }
+
+    fn visit_tag_name_selector(&mut self, _node: &TagNameSelector) {
+        self.specificity.2 += 1;
+    }
+
+    fn visit_pseudo_element_selector(&mut self, _node: &PseudoElementSelector) {
+        self.specificity.2 += 1;
+    }
+
+    fn visit_pseudo_class_selector(&mut self, node: &PseudoClassSelector) {
+        match node.name.value.to_ascii_lowercase().as_str() {
+            // Takes the specificity of its most specific argument
+            "is" | "not" | "has" | "matches" => {
+                let mut arguments = MaxSpecificity::default();
+                node.visit_children_with(&mut arguments);
+                let (ids, classes, elements) = arguments.max;
+                self.specificity.0 += ids;
+                self.specificity.1 += classes;
+                self.specificity.2 += elements;
+            }
+            "where" => {}
+            // Pseudo-elements from CSS 2 may still be written with a single colon
+            "before" | "after" | "first-line" | "first-letter" => self.specificity.2 += 1,
+            _ => self.specificity.1 += 1,
+        }
+    }
+}
+
+/// Computes the specificity of every top-level rule, e.g. to audit overly specific
+/// selectors. The specificity of a selector list such as `.a, #b` is the highest one of
+/// its selectors; `:is()`, `:not()` and `:has()` count as their most specific argument, and
+/// `:where()` counts as nothing.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing a [`SelectorSpecificity`] per top-level style rule in source order,
+/// or an error message if parsing fails.
+pub fn selector_specificity_from_ast(
+    file_content: &str,
+) -> Result<Vec<SelectorSpecificity>, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let specificities = stylesheet
+        .rules
+        .iter()
+        .filter_map(|rule| match rule {
+            Rule::QualifiedRule(rule) => {
+                let mut max = MaxSpecificity::default();
+                rule.prelude.visit_with(&mut max);
+
+                Some(SelectorSpecificity {
+                    selector: selector_text(file_content, &fm, &rule.prelude),
+                    specificity: max.max,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(specificities)
+}
+
+struct EmptyRuleCollector<'a> {
+    source: &'a str,
+    fm: &'a SourceFile,
+    ranges: Vec<Range<usize>>,
+}
+
+impl Visit for EmptyRuleCollector<'_> {
+    fn visit_qualified_rule(&mut self, node: &QualifiedRule) {
+        let block = &self.source[source_range(self.fm, node.block.span)];
+        let inner = block
+            .strip_prefix('{')
+            .and_then(|block| block.strip_suffix('}'))
+            .unwrap_or(block);
+
+        // A block holding only a comment is kept, so the comment is not lost
+        if node.block.value.is_empty() && inner.trim().is_empty() {
+            self.ranges
+                .push(removal_range(self.source, source_range(self.fm, node.span)));
+        }
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Removes every style rule whose block is empty, e.g. `.foo {}` left behind after its
+/// declarations were removed.
+///
+/// At-rules such as `@media` are never removed, even when all their rules are. A rule whose
+/// block only holds a comment is kept. The rest of the source, comments included, is left
+/// untouched.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if parsing fails.
+pub fn remove_empty_rules_from_ast(file_content: &str) -> Result<String, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut collector = EmptyRuleCollector {
+        source: file_content,
+        fm: &fm,
+        ranges: Vec::new(),
+    };
+    stylesheet.visit_with(&mut collector);
+
+    let edits = collector
+        .ranges
+        .into_iter()
+        .map(|range| (range, String::new()))
+        .collect();
+
+    Ok(apply_edits(file_content, edits))
+}
+
+struct DuplicateRuleMerger<'a> {
+    source: &'a str,
+    fm: &'a SourceFile,
+    edits: Vec<(Range<usize>, String)>,
+}
+
+/// A sibling of the rules being merged: a style rule, or another node (e.g. an at-rule)
+/// that rules are never merged across.
+#[derive(Clone, Copy)]
+enum Sibling<'r> {
+    Rule(&'r QualifiedRule),
+    Barrier,
+}
+
+/// A declaration or a comment of a rule block, in source order.
+enum BlockItem {
+    Declaration {
+        property: String,
+        text: String,
+        important: bool,
+    },
+    Comment(String),
+}
+
+impl BlockItem {
+    fn declares(&self, name: &str) -> bool {
+        matches!(self, BlockItem::Declaration { property, .. } if property == name)
+    }
+
+    fn declares_important(&self, name: &str) -> bool {
+        matches!(
+            self,
+            BlockItem::Declaration { property, important: true, .. } if property == name
+        )
+    }
+}
+
+/// Whether a rule block holds nothing else than declarations, e.g. no nested rules.
+fn only_declarations(rule: &QualifiedRule) -> bool {
+    rule.block
+        .value
+        .iter()
+        .all(|component| matches!(component, ComponentValue::Declaration(_)))
+}
+
+/// The `/* ... */` comments of the text between two declarations.
+fn block_comments(gap: &str) -> Vec<BlockItem> {
+    let mut comments = vec![];
+    let mut rest = gap;
+    while let Some(start) = rest.find("/*") {
+        let end = rest[start + 2..]
+            .find("*/")
+            .map_or(rest.len(), |end| start + end + 4);
+        comments.push(BlockItem::Comment(rest[start..end].to_string()));
+        rest = &rest[end..];
+    }
+    comments
+}
+
+impl DuplicateRuleMerger<'_> {
+    /// Merges the rules of one block (or of the top level) that share a selector list.
+    /// Rules holding anything else than declarations, e.g. nested rules, are left alone.
+    ///
+    /// A rule is only merged into an earlier one when the rules between them declare none
+    /// of its properties, so moving its declarations up doesn't change the cascade.
+    fn merge(&mut self, siblings: Vec<Sibling>) {
+        let mut groups: Vec<Vec<&QualifiedRule>> = vec![];
+        // The group a selector merges into, with the sibling index of its last rule
+        let mut open: Vec<(String, usize, usize)> = vec![];
+
+        for (index, sibling) in siblings.iter().enumerate() {
+            let Sibling::Rule(rule) = *sibling else {
+                continue;
+            };
+            if !only_declarations(rule) {
+                continue;
+            }
+
+            let selector = selector_text(self.source, self.fm, &rule.prelude);
+            let items = self.block_items(rule);
+
+            let open_group = open
+                .iter_mut()
+                .find(|(existing, _, _)| *existing == selector);
+            match open_group {
+                Some((_, group, last))
+                    if siblings[*last + 1..index]
+                        .iter()
+                        .all(|between| !self.overrides(between, &items)) =>
+                {
+                    groups[*group].push(rule);
+                    *last = index;
+                }
+                Some((_, group, last)) => {
+                    groups.push(vec![rule]);
+                    *group = groups.len() - 1;
+                    *last = index;
+                }
+                None => {
+                    groups.push(vec![rule]);
+                    open.push((selector, groups.len() - 1, index));
+                }
+            }
+        }
+
+        for group in groups.into_iter().filter(|group| group.len() > 1) {
+            let items = self.merged_items(&group);
+
+            let first = group[0];
+            let block_range = source_range(self.fm, first.block.span);
+            let block = &self.source[block_range.clone()];
+            self.edits
+                .push((block_range, self.format_block(first, block, &items)));
+
+            for rule in &group[1..] {
+                let range = removal_range(self.source, source_range(self.fm, rule.span));
+                self.edits.push((range, String::new()));
+            }
+        }
+    }
+
+    /// Whether a sibling placed between two duplicates stops the later one from moving up:
+    /// any node but a style rule of declarations, or a rule declaring one of its properties.
+    fn overrides(&self, sibling: &Sibling, items: &[BlockItem]) -> bool {
+        let Sibling::Rule(rule) = sibling else {
+            return true;
+        };
+        if !only_declarations(rule) {
+            return true;
+        }
+
+        self.block_items(rule).iter().any(|item| match item {
+            BlockItem::Declaration { property, .. } => {
+                items.iter().any(|other| other.declares(property))
+            }
+            BlockItem::Comment(_) => false,
+        })
+    }
+
+    /// The declarations and comments of a rule block, with lowercase property names.
+    fn block_items(&self, rule: &QualifiedRule) -> Vec<BlockItem> {
+        let block = source_range(self.fm, rule.block.span);
+        let mut position = block.start + 1;
+        let mut items = vec![];
+
+        for component in &rule.block.value {
+            if let ComponentValue::Declaration(declaration) = component {
+                let range = source_range(self.fm, declaration.span);
+                items.extend(block_comments(&self.source[position..range.start]));
+
+                let text = self.source[range.clone()]
+                    .trim()
+                    .trim_end_matches(';')
+                    .trim_end();
+                let property = text
+                    .split_once(':')
+                    .map_or(text, |(property, _)| property)
+                    .trim()
+                    .to_ascii_lowercase();
+
+                items.push(BlockItem::Declaration {
+                    property,
+                    text: text.to_string(),
+                    important: declaration.important.is_some(),
+                });
+                position = range.end;
+            }
+        }
+        items.extend(block_comments(&self.source[position..block.end - 1]));
+
+        items
+    }
+
+    /// The items of the rules of a group, in order. A property declared again by a later
+    /// rule replaces the earlier declarations, unless only the earlier one is `!important`,
+    /// in which case the later ones are dropped. Repeated declarations within one rule,
+    /// e.g. fallbacks such as `display: -webkit-box; display: flex`, are all kept.
+    fn merged_items(&self, group: &[&QualifiedRule]) -> Vec<BlockItem> {
+        let mut merged: Vec<BlockItem> = vec![];
+
+        for rule in group {
+            let mut items = self.block_items(rule);
+
+            let mut properties: Vec<String> = vec![];
+            for item in &items {
+                if let BlockItem::Declaration { property, .. } = item {
+                    if !properties.contains(property) {
+                        properties.push(property.clone());
+                    }
+                }
+            }
+
+            for property in properties {
+                let earlier_important =
+                    merged.iter().any(|item| item.declares_important(&property));
+                let later_important = items.iter().any(|item| item.declares_important(&property));
+
+                if earlier_important && !later_important {
+                    items.retain(|item| !item.declares(&property));
+                } else {
+                    merged.retain(|item| !item.declares(&property));
+                }
+            }
+
+            merged.extend(items);
+        }
+
+        merged
+    }
+
+    /// Writes the merged declarations and comments in the layout of the first rule: one per
+    /// line with its indentation, or all on one line when the rule was written on one line.
+    fn format_block(&self, rule: &QualifiedRule, block: &str, items: &[BlockItem]) -> String {
+        let lines: Vec<String> = items
+            .iter()
+            .map(|item| match item {
+                BlockItem::Declaration { text, .. } => format!("{};", text),
+                BlockItem::Comment(comment) => comment.clone(),
+            })
+            .collect();
+
+        if !block.contains('\n') {
+            return format!("{{ {} }}", lines.join(" "));
+        }
+
+        let rule_start = source_range(self.fm, rule.span).start;
+        let line_start = self.source[..rule_start].rfind('\n').map_or(0, |i| i + 1);
+        let rule_indent = &self.source[line_start..rule_start];
+
+        let indent = block
+            .lines()
+            .skip(1)
+            .find(|line| !line.trim().is_empty() && line.trim() != "}")
+            .map(|line| &line[..line.len() - line.trim_start().len()])
+            .unwrap_or("  ");
+
+        let mut output = String::from("{\n");
+        for line in lines {
+            output.push_str(&format!("{}{}\n", indent, line));
+        }
+        output.push_str(rule_indent);
+        output.push('}');
+        output
+    }
+}
+
+impl Visit for DuplicateRuleMerger<'_> {
+    fn visit_stylesheet(&mut self, node: &Stylesheet) {
+        self.merge(
+            node.rules
+                .iter()
+                .map(|rule| match rule {
+                    Rule::QualifiedRule(rule) => Sibling::Rule(&**rule),
+                    _ => Sibling::Barrier,
+                })
+                .collect(),
+        );
+
+        node.visit_children_with(self);
+    }
+
+    fn visit_simple_block(&mut self, node: &SimpleBlock) {
+        self.merge(
+            node.value
+                .iter()
+                .filter_map(|component| match component {
+                    ComponentValue::QualifiedRule(rule) => Some(Sibling::Rule(&**rule)),
+                    // The declarations of a nested block belong to its own rule
+                    ComponentValue::Declaration(_) => None,
+                    _ => Some(Sibling::Barrier),
+                })
+                .collect(),
+        );
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Merges style rules with the same selector list into the first of them, e.g. the two
+/// `.a` rules of `.a { color: red } .a { margin: 0 }` become `.a { color: red; margin: 0; }`.
+///
+/// Only rules of the same block are merged: top level rules with each other, and the rules
+/// of one `@media` (or other) block with each other. Rules holding nested rules are not
+/// merged, and a rule is not merged across another node, such as an at-rule or a rule that
+/// declares one of its properties, so the cascade stays the same.
+///
+/// When a property is declared again by a later rule, its declarations take the place of
+/// the earlier ones, except that an `!important` declaration is never replaced by a normal
+/// one. Repeated declarations within one rule, e.g. fallbacks, are kept.
+///
+/// The merged block is written in the layout of the first rule, with the comments of the
+/// merged blocks; every other comment is kept as well.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if parsing fails.
+pub fn merge_duplicate_rules_from_ast(file_content: &str) -> Result<String, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut merger = DuplicateRuleMerger {
+        source: file_content,
+        fm: &fm,
+        edits: Vec::new(),
+    };
+    stylesheet.visit_with(&mut merger);
+
+    Ok(apply_edits(file_content, merger.edits))
+}
+
+/// Appends a block of CSS at the end of the source, separated from the last rule by a
+/// blank line.
+fn append_block(file_content: &str, block: &str) -> String {
+    let separator = match file_content {
+        "" => "",
+        content if content.ends_with("\n\n") => "",
+        content if content.ends_with('\n') => "\n",
+        _ => "\n\n",
+    };
+    format!("{}{}{}\n", file_content, separator, block)
+}
+
+/// Where [`insert_rule_to_ast`] places a new rule.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum RulePosition<'a> {
+    /// Before the other rules, but after a leading `@charset` and the `@import` rules.
+    Top,
+    /// After the last rule.
+    Bottom,
+    /// Right after the first top-level rule with the selector, compared ignoring whitespace.
+    After(&'a str),
+}
+
+/// Inserts a full rule, e.g. `.new { color: red; }`, into CSS source code.
+///
+/// The rule source is parsed with the CSS parser first, and then inserted as written,
+/// separated from its neighbours by a blank line. Comments and the formatting of the rest
+/// of the source are kept.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `rule_source`: The rule (or rules) to insert.
+/// - `position`: Where to insert the rule, see [`RulePosition`].
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if either source fails
+/// to parse, `rule_source` has no rule, or no rule has the selector of `After`. `After` on
+/// an empty source fails with
+/// [`EMPTY_SOURCE_ERROR`](crate::helpers::EMPTY_SOURCE_ERROR).
+pub fn insert_rule_to_ast(
+    file_content: &str,
+    rule_source: &str,
+    position: RulePosition,
+) -> Result<String, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+    let (new_rules, _rules_fm, errors) = parse_with_recovered_errors(rule_source)?;
+    if let Some(error) = errors.into_iter().next() {
+        return Err(format!("Failed to parse the rule: {}", error));
+    }
+    if new_rules.rules.is_empty() {
+        return Err("No rule found in the given rule source".to_string());
+    }
+    let rule_text = rule_source.trim();
+
+    let anchor = match position {
+        RulePosition::Top => collect_imports(&stylesheet)
+            .last()
+            .map(|import| import.span)
+            .or_else(|| leading_charset_span(&stylesheet)),
+        RulePosition::Bottom => return Ok(append_block(file_content, rule_text)),
+        RulePosition::After(selector) => {
+            ensure_source_not_empty(file_content)?;
+            let selector = selector.split_whitespace().collect::<Vec<_>>().join(" ");
+            let rule = stylesheet.rules.iter().find_map(|rule| match rule {
+                Rule::QualifiedRule(rule)
+                    if selector_text(file_content, &fm, &rule.prelude) == selector =>
+                {
+                    Some(rule.span)
+                }
+                _ => None,
+            });
+
+            Some(rule.ok_or_else(|| format!("No rule with the selector `{}` found", selector))?)
+        }
+    };
+
+    match anchor {
+        Some(span) => {
+            let position = rule_end(file_content, source_range(&fm, span).end);
+            Ok(format!(
+                "{}\n\n{}{}",
+                &file_content[..position],
+                rule_text,
+                &file_content[position..]
+            ))
+        }
+        None if file_content.trim().is_empty() => Ok(format!("{}\n", rule_text)),
+        None => Ok(format!("{}\n\n{}", rule_text, file_content)),
+    }
+}
+
+// ###################################################################################
+// ################# (▰˘◡˘▰) Work with AST Custom Properties (▰˘◡˘▰) #################
+// ###################################################################################
+/// Whether one of the comma separated selectors of a rule is exactly `:root`.
+fn selector_is_root(source: &str, fm: &SourceFile, prelude: &QualifiedRulePrelude) -> bool {
+    selector_text(source, fm, prelude)
+        .split(',')
+        .any(|selector| selector.trim() == ":root")
+}
+
+fn is_custom_property(declaration: &Declaration, var_name: &str) -> bool {
+    matches!(&declaration.name, DeclarationName::DashedIdent(ident)
+        if ident.value.trim_start_matches("--") == var_name)
+}
+
+struct CustomPropertyFinder<'a> {
+    source: &'a str,
+    fm: &'a SourceFile,
+    var_name: &'a str,
+    root_only: bool,
+    found: bool,
+}
+
+impl Visit for CustomPropertyFinder<'_> {
+    fn visit_qualified_rule(&mut self, node: &QualifiedRule) {
+        if self.root_only && selector_is_root(self.source, self.fm, &node.prelude) {
+            let declared = node.block.value.iter().any(|component| {
+                matches!(component, ComponentValue::Declaration(declaration)
+                    if is_custom_property(declaration, self.var_name))
+            });
+            if declared {
+                self.found = true;
+            }
+        }
+
+        node.visit_children_with(self);
+    }
+
+    fn visit_declaration(&mut self, node: &Declaration) {
+        if !self.root_only && is_custom_property(node, self.var_name) {
+            self.found = true;
+        }
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Checks if a custom property (CSS variable) such as `--color-primary` is declared.
+/// Usages like `var(--color-primary)` do not count.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `var_name`: The property name, with or without the leading `--`.
+/// - `root_only`: Only look at the declarations of `:root` rules.
+///
+/// # Returns
+/// A `Result` containing `true` when the property is declared and `false` otherwise, or an
+/// error message if parsing fails.
+pub fn contains_custom_property_from_ast(
+    file_content: &str,
+    var_name: &str,
+    root_only: bool,
+) -> Result<bool, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut finder = CustomPropertyFinder {
+        source: file_content,
+        fm: &fm,
+        var_name: var_name.trim_start_matches("--"),
+        root_only,
+        found: false,
+    };
+    stylesheet.visit_with(&mut finder);
+
+    Ok(finder.found)
+}
+
+/// The source range of a declaration value without the `;`, and without the `!important`
+/// flag unless `with_important` is set.
+fn declaration_value_range(
+    source: &str,
+    fm: &SourceFile,
+    declaration: &Declaration,
+    with_important: bool,
+) -> Option<Range<usize>> {
+    let range = source_range(fm, declaration.span);
+    let end = match &declaration.important {
+        Some(important) if !with_important => source_range(fm, important.span).start,
+        _ => range.end,
+    };
+
+    let colon = range.start + source[range.start..end].find(':')?;
+    let value = &source[colon + 1..end];
+    let start = colon + 1 + (value.len() - value.trim_start().len());
+    let end = start + value.trim().trim_end_matches(';').trim_end().len();
+
+    Some(start..end)
+}
+
+/// The whitespace a line starts with, up to `position`.
+fn line_indent(source: &str, position: usize) -> &str {
+    let line_start = source[..position].rfind('\n').map_or(0, |index| index + 1);
+    let line = &source[line_start..position];
+    &line[..line.len() - line.trim_start_matches([' ', '\t']).len()]
+}
+
+/// Sets the value of a custom property (CSS variable) in the first top-level rule with the
+/// given selector, e.g. to change a design token.
+///
+/// An existing declaration only has its value replaced; otherwise the declaration is added
+/// after the last one of the rule. When no rule has the selector, a new rule is appended.
+/// Comments, other declarations and the formatting of the rest of the source are kept.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `selector`: The selector of the rule, compared ignoring whitespace; `:root` when `None`.
+/// - `var_name`: The property name, with or without the leading `--`.
+/// - `value`: The new value, e.g. `#fff`.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if parsing fails or
+/// the value does not fit in a single declaration.
+pub fn set_custom_property_from_ast(
+    file_content: &str,
+    selector: Option<&str>,
+    var_name: &str,
+    value: &str,
+) -> Result<String, String> {
+    let var_name = var_name.trim_start_matches("--");
+
+    upsert_declaration(
+        file_content,
+        selector.unwrap_or(":root"),
+        &format!("--{}", var_name),
+        value,
+        |declaration| is_custom_property(declaration, var_name),
+        false,
+    )
+}
+
+/// Sets the value of a declaration in the first top-level rule with the given selector,
+/// e.g. `color` of `.btn` for theming.
+///
+/// An existing declaration has its whole value replaced, so an `!important` flag is only
+/// kept when the new value includes it. Otherwise the declaration is added after the last
+/// one of the rule, and when no rule has the selector, a new rule is appended. Comments,
+/// other declarations and the formatting of the rest of the source are kept.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `selector`: The selector of the rule, compared ignoring whitespace, e.g. `.btn, .button`.
+/// - `property`: The property name, compared case-insensitively; custom properties
+///   (`--name`) are compared as written.
+/// - `value`: The new value, e.g. `blue` or `blue !important`.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if parsing fails or
+/// `property: value;` is not a single valid declaration.
+pub fn set_declaration_in_ast(
+    file_content: &str,
+    selector: &str,
+    property: &str,
+    value: &str,
+) -> Result<String, String> {
+    let property = property.trim();
+
+    upsert_declaration(
+        file_content,
+        selector,
+        property,
+        value,
+        |declaration| match property.strip_prefix("--") {
+            Some(var_name) => is_custom_property(declaration, var_name),
+            None => matches!(&declaration.name, DeclarationName::Ident(ident)
+                if ident.value.eq_ignore_ascii_case(property)),
+        },
+        true,
+    )
+}
+
+/// Checks that `declaration_text` parses as exactly one declaration, so a value such as
+/// `red; } body {` or an empty property can not break the rule it is written to.
+fn validate_declaration(declaration_text: &str) -> Result<(), String> {
+    let invalid = || format!("Invalid declaration `{}`", declaration_text);
+    let (stylesheet, _fm, errors) =
+        parse_with_recovered_errors(&format!("a {{ {} }}", declaration_text))
+            .map_err(|_| invalid())?;
+
+    match stylesheet.rules.as_slice() {
+        [Rule::QualifiedRule(rule)]
+            if errors.is_empty()
+                && matches!(
+                    rule.block.value.as_slice(),
+                    [ComponentValue::Declaration(_)]
+                ) =>
+        {
+            Ok(())
+        }
+        _ => Err(invalid()),
+    }
+}
+
+/// Replaces the value of the declaration `is_target` finds in the first top-level rule with
+/// the selector, or adds a `property: value;` declaration to the rule (appending the rule
+/// when it is missing). With `replace_important`, the `!important` flag is part of the
+/// replaced value. The `property: value;` declaration is validated first.
+fn upsert_declaration(
+    file_content: &str,
+    selector: &str,
+    property: &str,
+    value: &str,
+    is_target: impl Fn(&Declaration) -> bool,
+    replace_important: bool,
+) -> Result<String, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let selector = selector.split_whitespace().collect::<Vec<_>>().join(" ");
+    let declaration_text = format!("{}: {};", property, value.trim());
+    validate_declaration(&declaration_text)?;
+
+    let rule = stylesheet.rules.iter().find_map(|rule| match rule {
+        Rule::QualifiedRule(rule)
+            if selector_text(file_content, &fm, &rule.prelude) == selector =>
+        {
+            Some(rule)
+        }
+        _ => None,
+    });
+
+    let Some(rule) = rule else {
+        return Ok(append_block(
+            file_content,
+            &format!("{} {{\n  {}\n}}", selector, declaration_text),
+        ));
+    };
+
+    let declarations: Vec<&Declaration> = rule
+        .block
+        .value
+        .iter()
+        .filter_map(|component| match component {
+            ComponentValue::Declaration(declaration) => {
+                let declaration: &Declaration = declaration;
+                Some(declaration)
+            }
+            _ => None,
+        })
+        .collect();
+
+    if let Some(existing) = declarations
+        .iter()
+        .find(|declaration| is_target(declaration))
+    {
+        let range = declaration_value_range(file_content, &fm, existing, replace_important)
+            .ok_or_else(|| format!("Failed to read the value of `{}`", property))?;
+        return Ok(apply_edits(
+            file_content,
+            vec![(range, value.trim().to_string())],
+        ));
+    }
+
+    let edit = match declarations.last() {
+        Some(last) => {
+            let last_range = source_range(&fm, last.span);
+            let after = &file_content[last_range.end..];
+            let (position, prefix) = if file_content[last_range.clone()].ends_with(';') {
+                (last_range.end, "")
+            } else if after.trim_start().starts_with(';') {
+                (
+                    last_range.end + after.find(';').map_or(0, |index| index + 1),
+                    "",
+                )
+            } else {
+                (last_range.end, ";")
+            };
+
+            // A one-line rule such as `.a { color: red }` stays on one line
+            let block_start = source_range(&fm, rule.block.span).start;
+            let separator = if file_content[block_start..last_range.start].contains('\n') {
+                format!("\n{}", line_indent(file_content, last_range.start))
+            } else {
+                " ".to_string()
+            };
+
+            (
+                position..position,
+                format!("{}{}{}", prefix, separator, declaration_text),
+            )
+        }
+        None => {
+            let block = source_range(&fm, rule.block.span);
+            let inner_end = block.end - 1;
+            let content_end =
+                block.start + 1 + file_content[block.start + 1..inner_end].trim_end().len();
+            let indent = line_indent(file_content, source_range(&fm, rule.span).start);
+
+            (
+                content_end..inner_end,
+                format!("\n{}  {}\n{}", indent, declaration_text, indent),
+            )
+        }
+    };
+
+    Ok(apply_edits(file_content, vec![edit]))
+}
+
+/// Collects the spans of a custom property name, both where it is declared and where it is
+/// used, e.g. in `var()`.
+struct CustomPropertyCollector<'a> {
+    var_name: &'a str,
+    spans: Vec<Span>,
+}
+
+impl Visit for CustomPropertyCollector<'_> {
+    fn visit_dashed_ident(&mut self, node: &DashedIdent) {
+        if node.value.trim_start_matches("--") == self.var_name {
+            self.spans.push(node.span);
+        }
+    }
+
+    // The values of custom properties are kept as raw tokens, e.g. `--a: var(--b)`.
+    fn visit_token_and_span(&mut self, node: &TokenAndSpan) {
+        if let Token::Ident { value, .. } = &node.token {
+            if value.starts_with("--") && value.trim_start_matches("--") == self.var_name {
+                self.spans.push(node.span);
+            }
+        }
+    }
+}
+
+/// Renames a custom property (CSS variable), e.g. a design token, in its declarations and
+/// in every `var()` that uses it, including those in the values of other custom properties.
+///
+/// Only the names are replaced in the source text, so the fallback of `var(--old, 4px)`,
+/// comments and formatting are kept.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+/// - `old_name`: The property to rename, with or without the leading `--`.
+/// - `new_name`: The new property name, with or without the leading `--`.
+///
+/// # Returns
+/// A `Result` containing the updated CSS code, or an error message if the source is empty,
+/// parsing fails or `old_name` is neither declared nor used.
+pub fn rename_custom_property_in_ast(
+    file_content: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut collector = CustomPropertyCollector {
+        var_name: old_name.trim_start_matches("--"),
+        spans: Vec::new(),
+    };
+    stylesheet.visit_with(&mut collector);
+
+    if collector.spans.is_empty() {
+        return Err(format!(
+            "The custom property `--{}` is neither declared nor used",
+            collector.var_name
+        ));
+    }
+
+    let new_name = format!("--{}", new_name.trim_start_matches("--"));
+    let edits = collector
+        .spans
+        .iter()
+        .map(|span| (source_range(&fm, *span), new_name.clone()))
+        .collect();
+
+    Ok(apply_edits(file_content, edits))
+}
+
+// ###################################################################################
+// #################### (▰˘◡˘▰) Work with AST Media Queries (▰˘◡˘▰) ##################
+// ###################################################################################
+
+struct MediaQueryCollector<'a> {
+    source: &'a str,
+    fm: &'a SourceFile,
+    queries: Vec<String>,
+}
+
+impl Visit for MediaQueryCollector<'_> {
+    fn visit_at_rule(&mut self, node: &AtRule) {
+        if let Some(prelude) = &node.prelude {
+            if let AtRulePrelude::MediaPrelude(media) = &**prelude {
+                for query in &media.queries {
+                    let text = &self.source[source_range(self.fm, query.span)];
+                    let query = text.split_whitespace().collect::<Vec<_>>().join(" ");
+
+                    if !self.queries.contains(&query) {
+                        self.queries.push(query);
+                    }
+                }
+            }
+        }
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Lists the conditions of every `@media` rule, e.g. to inventory the breakpoints of a
+/// stylesheet. A rule like `@media screen, (min-width: 768px)` gives one entry per query.
+///
+/// Queries are returned as their source text with whitespace collapsed, in the order they
+/// first appear; identical queries are listed once. Nested `@media` rules are included.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the media queries, or an error message if parsing fails.
+pub fn list_media_queries_from_ast(file_content: &str) -> Result<Vec<String>, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut collector = MediaQueryCollector {
+        source: file_content,
+        fm: &fm,
+        queries: Vec::new(),
+    };
+    stylesheet.visit_with(&mut collector);
+
+    Ok(collector.queries)
+}
+
+// ###################################################################################
+// ##################### (▰˘◡˘▰) Work with AST Validation (▰˘◡˘▰) ####################
+// ###################################################################################
+
+/// Checks that the CSS source code parses without errors, without modifying it.
+///
+/// Unlike the editing functions, which only reject an unexpected end of file (see
+/// [`parse`]) and work on whatever else the parser could recover, any error the parser
+/// recovered from is reported here as well.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// `Ok(())` when the stylesheet is valid, otherwise the message and position of the first
+/// parser error.
+pub fn validate_css_from_ast(file_content: &str) -> Result<(), String> {
+    let (_stylesheet, _fm, errors) = parse_with_recovered_errors(file_content)?;
+
+    match errors.into_iter().next() {
+        Some(error) => Err(format!("Failed to parse stylesheet: {}", error)),
+        None => Ok(()),
+    }
+}
+
+// ###################################################################################
+// #################### (▰˘◡˘▰) Work with AST Statistics (▰˘◡˘▰) #####################
+// ###################################################################################
+
+/// Statistics of a stylesheet, see [`statistics_from_ast`].
+#[derive(Debug, Default, PartialEq)]
+pub struct CSSStatistics {
+    /// Style rules, nested ones included.
+    pub rules: usize,
+    /// At-rules such as `@import`, `@media` or `@font-face`.
+    pub at_rules: usize,
+    /// Declarations of all style rules.
+    pub declarations: usize,
+    /// The most declarations found in a single style rule.
+    pub max_declarations: usize,
+    /// The selector of every style rule with its number of declarations, in source order.
+    pub rule_declarations: Vec<(String, usize)>,
+}
+
+struct StatisticsCollector<'a> {
+    source: &'a str,
+    fm: &'a SourceFile,
+    statistics: CSSStatistics,
+}
+
+impl Visit for StatisticsCollector<'_> {
+    fn visit_qualified_rule(&mut self, node: &QualifiedRule) {
+        let selector = selector_text(self.source, self.fm, &node.prelude);
+        let declarations = node
+            .block
+            .value
+            .iter()
+            .filter(|component| matches!(component, ComponentValue::Declaration(_)))
+            .count();
+
+        self.statistics.rules += 1;
+        self.statistics.declarations += declarations;
+        self.statistics.max_declarations = self.statistics.max_declarations.max(declarations);
+        self.statistics
+            .rule_declarations
+            .push((selector, declarations));
+
+        node.visit_children_with(self);
+    }
+
+    fn visit_at_rule(&mut self, node: &AtRule) {
+        self.statistics.at_rules += 1;
+
+        node.visit_children_with(self);
+    }
+}
+
+/// Counts the rules and declarations of a stylesheet, with the number of declarations of
+/// every style rule to spot bloated selectors.
+///
+/// # Arguments
+/// - `file_content`: The CSS source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the [`CSSStatistics`], or an error message if parsing fails.
+pub fn statistics_from_ast(file_content: &str) -> Result<CSSStatistics, String> {
+    let (stylesheet, fm) = parse(file_content)?;
+
+    let mut collector = StatisticsCollector {
+        source: file_content,
+        fm: &fm,
+        statistics: CSSStatistics::default(),
+    };
+    stylesheet.visit_with(&mut collector);
+
+    Ok(collector.statistics)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_insert_import_to_ast() {
+        let code = r#"@import "base.css";
+
+body {
+  margin: 0;
+}
+"#;
+
+        let imports = r#"
+            @import "base.css";
+            @import "theme.css";
+        "#;
+
+        let result = insert_import_to_ast(code, imports).unwrap();
+        assert_eq!(
+            result,
+            "@import \"base.css\";\n@import \"theme.css\";\n\nbody {\n  margin: 0;\n}\n"
+        );
+
+        let result = insert_import_to_ast(code, "@import \"base.css\";").unwrap();
+        assert_eq!(result, code);
+
+        let error = insert_import_to_ast("body { color: red", "@import \"x.css\";").unwrap_err();
+        assert!(
+            error.contains("end of file"),
+            "Unexpected message: {}",
+            error
+        );
+    }
+
+    #[test]
+    fn test_insert_import_keeps_layer_and_conditions() {
+        let code = "body {\n  margin: 0;\n}\n";
+        let imports = r#"
+            @import "x.css" layer(base);
+            @import "print.css" print and (orientation: landscape);
+        "#;
+
+        let result = insert_import_to_ast(code, imports).unwrap();
+        assert!(result.starts_with("@import \"x.css\" layer(base);\n"));
+        assert!(result.contains("@import \"print.css\" print and (orientation: landscape);\n"));
+
+        let (stylesheet, _) = parse(&result).unwrap();
+        let imports = collect_imports(&stylesheet);
+        assert_eq!(imports.len(), 2);
+    }
+
+    #[test]
+    fn test_insert_import_url_form() {
+        let code = "@import url(\"theme.css\");\n\nbody {\n  margin: 0;\n}\n";
+
+        let result = insert_import_to_ast(code, "@import \"theme.css\";").unwrap();
+        assert_eq!(result, code);
+
+        let result = insert_import_to_ast(code, "@import url(theme.css);").unwrap();
+        assert_eq!(result, code);
+
+        let result = insert_import_to_ast(code, "@import url(\"print.css\") print;").unwrap();
+        assert!(
+            result.starts_with("@import url(\"theme.css\");\n@import url(\"print.css\") print;\n")
+        );
+
+        let code = "@import \"base.css\";\n";
+        let result = insert_import_to_ast(code, "@import url(base.css);").unwrap();
+        assert_eq!(result, code);
+
+        let result = insert_import_to_ast(code, "@import url(reset.css);").unwrap();
+        assert_eq!(result, "@import \"base.css\";\n@import url(reset.css);\n");
+    }
+
+    #[test]
+    fn test_insert_import_after_existing_imports() {
+        let code = "body {}\n@import \"a.css\";\n.card {\n  padding: 0;\n}\n";
+
+        let result = insert_import_to_ast(code, "@import \"b.css\";").unwrap();
+        assert_eq!(
+            result,
+            "body {}\n@import \"a.css\";\n@import \"b.css\";\n.card {\n  padding: 0;\n}\n"
+        );
+
+        let code = "@charset \"utf-8\";\nbody {}\n";
+        let result = insert_import_to_ast(code, "@import \"b.css\";").unwrap();
+        assert_eq!(result, "@charset \"utf-8\";\n@import \"b.css\";\nbody {}\n");
+    }
+
+    #[test]
+    fn test_class_has_declaration_from_ast() {
+        let code = r#"
+            .btn, .link {
+                display:   flex;
+                font-family: Inter,  sans-serif !important;
+            }
+
+            .card .btn-icon { display: block; }
+        "#;
+
+        assert_eq!(
+            class_has_declaration_from_ast(code, "btn", "display", "flex"),
+            Ok(true)
+        );
+        assert_eq!(
+            class_has_declaration_from_ast(code, "link", "DISPLAY", " flex "),
+            Ok(true)
+        );
+        assert_eq!(
+            class_has_declaration_from_ast(code, "btn", "font-family", "Inter, sans-serif"),
+            Ok(true)
+        );
+        assert_eq!(
+            class_has_declaration_from_ast(code, "btn", "display", "block"),
+            Ok(false)
+        );
+        assert_eq!(
+            class_has_declaration_from_ast(code, "btn-icon", "display", "block"),
+            Ok(true)
+        );
+        assert_eq!(
+            class_has_declaration_from_ast(code, "none", "display", "flex"),
+            Ok(false)
+        );
+        assert!(class_has_declaration_from_ast("body {", "btn", "display", "flex").is_err());
+
+        // Only the subject of a selector counts, not its ancestors or a negated class
+        assert_eq!(
+            class_has_declaration_from_ast(code, "card", "display", "block"),
+            Ok(false)
+        );
+        let code = ".item:not(.card) { display: grid; }";
+        assert_eq!(
+            class_has_declaration_from_ast(code, "card", "display", "grid"),
+            Ok(false)
+        );
+        assert_eq!(
+            class_has_declaration_from_ast(code, "item", "display", "grid"),
+            Ok(true)
+        );
+    }
+
+    #[test]
+    fn test_nested_rules() {
+        let code = r#".card {
+  padding: 1rem;
+
+  & .card-title {
+    font-weight: bold;
+  }
+
+  &:hover .card-link {
+    color: blue;
+  }
+
+  @media (min-width: 768px) {
+    & .card-body {
+      display: flex;
+    }
+  }
+}
+"#;
+
+        assert!(validate_css_from_ast(code).is_ok());
+        assert_eq!(contains_class_from_ast(code, "card-title"), Ok(true));
+        assert_eq!(contains_class_from_ast(code, ".card-link"), Ok(true));
+        assert_eq!(contains_class_from_ast(code, "card-body"), Ok(true));
+        assert_eq!(contains_class_from_ast(code, "card-footer"), Ok(false));
+
+        assert_eq!(
+            class_has_declaration_from_ast(code, "card-title", "font-weight", "bold"),
+            Ok(true)
+        );
+        assert_eq!(count_selectors_matching_from_ast(code, "card-"), Ok(3));
+        assert_eq!(
+            list_media_queries_from_ast(code),
+            Ok(vec!["(min-width: 768px)".to_string()])
+        );
+
+        let result = rename_class_in_ast(code, "card-title", "card-heading").unwrap();
+        assert!(result.contains("& .card-heading {"));
+
+        assert!(contains_class_from_ast(".card { color: red", "card").is_err());
+    }
+
+    #[test]
+    fn test_count_selectors_matching_from_ast() {
+        let code = r#"
+.tw-flex { display: flex; }
+.tw-p-4, .card .tw-m-2 { padding: 1rem; }
+.card { color: red; }
+.button:hover { color: blue; }
+
+@media (min-width: 768px) {
+  .tw-hidden { display: none; }
+}
+"#;
+
+        assert_eq!(count_selectors_matching_from_ast(code, "tw-"), Ok(4));
+        assert_eq!(count_selectors_matching_from_ast(code, ".tw-"), Ok(4));
+        assert_eq!(count_selectors_matching_from_ast(code, "card"), Ok(2));
+        assert_eq!(count_selectors_matching_from_ast(code, "btn-"), Ok(0));
+        assert!(count_selectors_matching_from_ast(".a { color: red", "a").is_err());
+    }
+
+    #[test]
+    fn test_remove_empty_rules_from_ast() {
+        let code = r#"/* Buttons */
+.btn {
+  color: red;
+}
+.empty {}
+.spaced {
+
+}
+.commented { /* todo */ }
+
+@media (min-width: 768px) {
+  .card {}
+}
+"#;
+
+        let result = remove_empty_rules_from_ast(code).unwrap();
+        assert_eq!(
+            result,
+            "/* Buttons */\n.btn {\n  color: red;\n}\n.commented { /* todo */ }\n\n@media (min-width: 768px) {\n}\n"
+        );
+
+        let code = ".btn { color: red; }\n";
+        assert_eq!(remove_empty_rules_from_ast(code), Ok(code.to_string()));
+
+        assert!(remove_empty_rules_from_ast(".btn { color: red").is_err());
+    }
+
+    #[test]
+    fn test_merge_duplicate_rules_from_ast() {
+        let code = r#"/* Alerts */
+.a {
+  color: red;
+  padding: 1rem;
+}
+
+.b { font-weight: bold; }
+
+/* Override */
+.a {
+  color: green !important;
+  margin: 0;
+}
+
+@media (min-width: 768px) {
+  .b { color: red; }
+  .b { margin: 0 }
+}
+"#;
+
+        let result = merge_duplicate_rules_from_ast(code).unwrap();
+        assert_eq!(
+            result,
+            r#"/* Alerts */
+.a {
+  padding: 1rem;
+  color: green !important;
+  margin: 0;
+}
+
+.b { font-weight: bold; }
+
+/* Override */
+
+@media (min-width: 768px) {
+  .b { color: red; margin: 0; }
+}
+"#
+        );
+
+        let code = ".a {color:red}\n.a {background:blue}\n";
+        assert_eq!(
+            merge_duplicate_rules_from_ast(code),
+            Ok(".a { color:red; background:blue; }\n".to_string())
+        );
+
+        let code = ".a { color: red; }\n.b { color: red; }\n";
+        assert_eq!(merge_duplicate_rules_from_ast(code), Ok(code.to_string()));
+
+        assert!(merge_duplicate_rules_from_ast(".a { color: red").is_err());
+    }
+
+    #[test]
+    fn test_merge_duplicate_rules_keeps_the_cascade() {
+        // A normal declaration never replaces an `!important` one
+        let code = ".a { color: red !important; }\n.a { color: blue; margin: 0; }\n";
+        assert_eq!(
+            merge_duplicate_rules_from_ast(code),
+            Ok(".a { color: red !important; margin: 0; }\n".to_string())
+        );
+
+        // Fallbacks within one rule are kept
+        let code = ".a { display: -webkit-box; display: flex; }\n.a { color: red; }\n";
+        assert_eq!(
+            merge_duplicate_rules_from_ast(code),
+            Ok(".a { display: -webkit-box; display: flex; color: red; }\n".to_string())
+        );
+        let code = ".a { display: block; }\n.a { display: -webkit-box; display: flex; }\n";
+        assert_eq!(
+            merge_duplicate_rules_from_ast(code),
+            Ok(".a { display: -webkit-box; display: flex; }\n".to_string())
+        );
+
+        // Rules are not merged across a rule declaring the same property, or an at-rule
+        let code = ".a { color: red; }\n.b { color: blue; }\n.a { color: green; }\n";
+        assert_eq!(merge_duplicate_rules_from_ast(code), Ok(code.to_string()));
+        let code = ".a { color: red; }\n@media print { .b { margin: 0; } }\n.a { margin: 0; }\n";
+        assert_eq!(merge_duplicate_rules_from_ast(code), Ok(code.to_string()));
+
+        let code = ".a { color: red; }\n.b { margin: 0; }\n.a { color: green; }\n";
+        assert_eq!(
+            merge_duplicate_rules_from_ast(code),
+            Ok(".a { color: green; }\n.b { margin: 0; }\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_merge_duplicate_rules_keeps_comments() {
+        let code = ".a {\n  /* Brand */\n  color: red;\n}\n.a {\n  margin: 0; /* reset */\n}\n";
+        assert_eq!(
+            merge_duplicate_rules_from_ast(code),
+            Ok(".a {\n  /* Brand */\n  color: red;\n  margin: 0;\n  /* reset */\n}\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_list_media_queries_from_ast() {
+        let code = r#"
+.card { padding: 1rem; }
+
+@media (min-width: 768px) {
+  .card { padding: 2rem; }
+}
+
+@media   (min-width:   768px) {
+  .title { font-size: 2rem; }
+}
+
+@media print and (orientation: landscape) {
+  .card { display: none; }
+}
+"#;
+
+        assert_eq!(
+            list_media_queries_from_ast(code),
+            Ok(vec![
+                "(min-width: 768px)".to_string(),
+                "print and (orientation: landscape)".to_string(),
+            ])
+        );
+        assert_eq!(
+            list_media_queries_from_ast(".a { color: red; }"),
+            Ok(vec![])
+        );
+    }
+
+    #[test]
+    fn test_rename_class_in_ast() {
+        let code = r#"/* Hide the scrollbar of .hide-scrollbar elements */
+.hide-scrollbar::-webkit-scrollbar {
+  display: none;
+}
+
+.hide-scrollbar, .card.hide-scrollbar:not(.hide-scrollbar-x) {
+  --label: ".hide-scrollbar";
+  scrollbar-width: none;
+}
+"#;
+
+        let result = rename_class_in_ast(code, "hide-scrollbar", ".no-scrollbar");
+        assert!(result.is_ok(), "Renaming failed: {:?}", result.err());
+
+        assert_eq!(
+            result.unwrap(),
+            r#"/* Hide the scrollbar of .hide-scrollbar elements */
+.no-scrollbar::-webkit-scrollbar {
+  display: none;
+}
+
+.no-scrollbar, .card.no-scrollbar:not(.hide-scrollbar-x) {
+  --label: ".hide-scrollbar";
+  scrollbar-width: none;
+}
+"#
+        );
+
+        assert!(rename_class_in_ast(code, "missing", "other").is_err());
+    }
+
+    #[test]
+    fn test_validate_css_from_ast() {
+        let code = r#"
+@import "base.css";
+
+/* Layout */
+.card { padding: 1rem; }
+
+@media (min-width: 768px) {
+  .card { padding: 2rem; }
+}
+"#;
+        assert_eq!(validate_css_from_ast(code), Ok(()));
+
+        let result = validate_css_from_ast(".card {\n  padding: 1rem;\n");
+        let error = result.err().expect("Expected a validation error");
+        assert!(!error.is_empty());
+        assert!(error.contains("line"), "Unexpected message: {}", error);
+    }
+
+    #[test]
+    fn test_statistics_from_ast() {
+        let code = r#"@import "base.css";
+
+.btn,
+.button {
+  display: flex;
+  color: red;
+  padding: 1rem;
+}
+
+@media (min-width: 768px) {
+  .card {
+    margin: 0;
+  }
+}
+
+.empty {}
+"#;
+
+        let statistics = statistics_from_ast(code).unwrap();
+        assert_eq!(statistics.rules, 3);
+        assert_eq!(statistics.at_rules, 2);
+        assert_eq!(statistics.declarations, 4);
+        assert_eq!(statistics.max_declarations, 3);
+        assert_eq!(
+            statistics.rule_declarations,
+            vec![
+                (".btn, .button".to_string(), 3),
+                (".card".to_string(), 1),
+                (".empty".to_string(), 0),
+            ]
+        );
+
+        assert!(statistics_from_ast(".btn { color: red").is_err());
+    }
+
+    #[test]
+    fn test_contains_custom_property_from_ast() {
+        let code = include_str!("../../../../../test/assets/themeVariables.css");
+
+        assert!(contains_custom_property_from_ast(code, "--color-primary", false).unwrap());
+        assert!(contains_custom_property_from_ast(code, "color-primary", true).unwrap());
+        assert!(contains_custom_property_from_ast(code, "--color-secondary", false).unwrap());
+        assert!(!contains_custom_property_from_ast(code, "--color-secondary", true).unwrap());
+        assert!(!contains_custom_property_from_ast(code, "--color", false).unwrap());
+
+        let code = ".btn { color: var(--color-accent); }";
+        assert!(!contains_custom_property_from_ast(code, "--color-accent", false).unwrap());
+        assert!(contains_custom_property_from_ast("a { --x: 1", "--x", false).is_err());
+    }
+
+    #[test]
+    fn test_set_custom_property_from_ast() {
+        let code = include_str!("../../../../../test/assets/themeVariables.css");
+
+        let result = set_custom_property_from_ast(code, None, "--color-primary", "#000").unwrap();
+        assert_eq!(
+            result,
+            code.replace("--color-primary: #fff;", "--color-primary: #000;")
+        );
+
+        let result = set_custom_property_from_ast(code, None, "radius", "8px").unwrap();
+        assert_eq!(
+            result,
+            code.replace(
+                "  --spacing: 4px;\n",
+                "  --spacing: 4px;\n  --radius: 8px;\n"
+            )
+        );
+        assert!(result.starts_with("/* Design tokens */\n"));
+
+        let result =
+            set_custom_property_from_ast(code, Some(".dark"), "--color-primary", "#111").unwrap();
+        assert!(result.contains(".dark {\n  --color-secondary: #333;\n  --color-primary: #111;\n}"));
+
+        let result =
+            set_custom_property_from_ast(".a { --x: 1 !important }", Some(".a"), "--x", "2")
+                .unwrap();
+        assert_eq!(result, ".a { --x: 2 !important }");
+
+        let result =
+            set_custom_property_from_ast(".a { color: red }", Some(".a"), "--x", "2").unwrap();
+        assert_eq!(result, ".a { color: red; --x: 2; }");
+
+        let result = set_custom_property_from_ast(".a {}\n", None, "--x", "2").unwrap();
+        assert_eq!(result, ".a {}\n\n:root {\n  --x: 2;\n}\n");
+
+        let result = set_custom_property_from_ast(":root {}", None, "--x", "2").unwrap();
+        assert_eq!(result, ":root {\n  --x: 2;\n}");
+
+        assert!(set_custom_property_from_ast(":root {", None, "--x", "2").is_err());
+
+        assert_eq!(
+            set_custom_property_from_ast(":root { --x: 1; }", None, "--x", "red; } body {"),
+            Err("Invalid declaration `--x: red; } body {;`".to_string())
+        );
+        assert!(set_custom_property_from_ast(":root {}", None, "--x", "1; } a { b: c").is_err());
+    }
+
+    #[test]
+    fn test_list_selectors_from_ast() {
+        let code = include_str!("../../../../../test/assets/scrollbarStyles.css");
+
+        let summary = list_selectors_from_ast(code).unwrap();
+        assert_eq!(summary.classes, vec!["hide-scrollbar", "scroll-area"]);
+        assert_eq!(summary.ids, vec!["sidebar", "main"]);
+
+        let code = include_str!("../../../../../test/assets/validStyles.css");
+        let summary = list_selectors_from_ast(code).unwrap();
+        assert_eq!(summary.classes, vec!["btn", "button", "card"]);
+        assert!(summary.ids.is_empty());
+
+        let summary = list_selectors_from_ast("@media print { .card { color: #000; } }").unwrap();
+        assert_eq!(summary.classes, vec!["card"]);
+        assert!(summary.ids.is_empty());
+
+        assert!(list_selectors_from_ast(".btn { color: red").is_err());
+    }
+
+    #[test]
+    fn test_is_imported_from_ast() {
+        let code = r#"@import url("base.css");
+@import "print.css" print;
+@import "theme.css" layer(base) supports(display: grid) screen;
+"#;
+
+        assert!(is_imported_from_ast(code, "@import \"base.css\";", false).unwrap());
+        assert!(is_imported_from_ast(code, "@import \"base.css\";", true).unwrap());
+        assert!(!is_imported_from_ast(code, "@import \"missing.css\";", false).unwrap());
+
+        // The default mode only compares hrefs
+        assert!(is_imported_from_ast(code, "@import \"print.css\";", false).unwrap());
+        assert!(!is_imported_from_ast(code, "@import \"print.css\";", true).unwrap());
+        assert!(is_imported_from_ast(code, "@import url(print.css) print;", true).unwrap());
+
+        let theme = "@import \"theme.css\"  layer(base)\n  supports(display: grid)  screen;";
+        assert!(is_imported_from_ast(code, theme, true).unwrap());
+        assert!(!is_imported_from_ast(code, "@import \"theme.css\" layer(base);", true).unwrap());
+        assert!(!is_imported_from_ast(code, "@import \"theme.css\" screen;", true).unwrap());
+
+        assert!(is_imported_from_ast(code, "body { margin: 0; }", false).is_err());
+        assert!(is_imported_from_ast("body { color: red", "@import \"x.css\";", false).is_err());
+    }
+
+    #[test]
+    fn test_insert_rule_to_ast() {
+        let code = r#"@charset "utf-8";
+@import "base.css";
+
+/* Buttons */
+.btn {
+  color: red;
+}
+
+.card {
+  padding: 0;
+}
+"#;
+        let rule = ".new { color: red; }\n";
+
+        let result = insert_rule_to_ast(code, rule, RulePosition::Top).unwrap();
+        assert_eq!(
+            result,
+            "@charset \"utf-8\";\n@import \"base.css\";\n\n.new { color: red; }\n\n/* Buttons */\n.btn {\n  color: red;\n}\n\n.card {\n  padding: 0;\n}\n"
+        );
+
+        let result = insert_rule_to_ast(code, rule, RulePosition::Bottom).unwrap();
+        assert_eq!(result, format!("{}\n.new {{ color: red; }}\n", code));
+
+        let result = insert_rule_to_ast(code, rule, RulePosition::After(".btn")).unwrap();
+        assert_eq!(
+            result,
+            "@charset \"utf-8\";\n@import \"base.css\";\n\n/* Buttons */\n.btn {\n  color: red;\n}\n\n.new { color: red; }\n\n.card {\n  padding: 0;\n}\n"
+        );
+
+        let result = insert_rule_to_ast(".a {}", rule, RulePosition::Top).unwrap();
+        assert_eq!(result, ".new { color: red; }\n\n.a {}");
+        let result = insert_rule_to_ast("", rule, RulePosition::Top).unwrap();
+        assert_eq!(result, ".new { color: red; }\n");
+
+        assert_eq!(
+            insert_rule_to_ast(code, rule, RulePosition::After(".missing")),
+            Err("No rule with the selector `.missing` found".to_string())
+        );
+        assert!(insert_rule_to_ast(code, ".new { color: red;", RulePosition::Bottom).is_err());
+        assert!(insert_rule_to_ast(code, "/* nothing */", RulePosition::Bottom).is_err());
+    }
+
+    #[test]
+    fn test_set_declaration_in_ast() {
+        let code = include_str!("../../../../../test/assets/validStyles.css");
+
+        let result = set_declaration_in_ast(code, ".btn, .button", "color", "blue").unwrap();
+        assert_eq!(result, code.replace("color: red;", "color: blue;"));
+
+        let result = set_declaration_in_ast(code, "body", "line-height", "1.5").unwrap();
+        assert_eq!(
+            result,
+            code.replace(
+                "  font-family: sans-serif;\n",
+                "  font-family: sans-serif;\n  line-height: 1.5;\n"
+            )
+        );
+
+        // Only the top-level `.card` rule is edited, not the one inside `@media`
+        let result = set_declaration_in_ast(code, ".card", "MARGIN", "2rem").unwrap();
+        assert!(result.contains(".card {\n  margin: 2rem;\n}"));
+        assert!(result.contains("    margin: 1rem;"));
+
+        let result = set_declaration_in_ast(code, ".new", "color", "red").unwrap();
+        assert!(result.ends_with("}\n\n.new {\n  color: red;\n}\n"));
+        assert!(result.contains("/* Layout */"));
+
+        let code = ".a { color: red !important; }";
+        let result = set_declaration_in_ast(code, ".a", "color", "blue").unwrap();
+        assert_eq!(result, ".a { color: blue; }");
+        let result = set_declaration_in_ast(code, ".a", "color", "blue !important").unwrap();
+        assert_eq!(result, ".a { color: blue !important; }");
+
+        assert!(set_declaration_in_ast(".a { color: red", ".a", "color", "blue").is_err());
+
+        let code = ".a { color: red; }";
+        assert_eq!(
+            set_declaration_in_ast(code, ".a", "color", "red; } body {"),
+            Err("Invalid declaration `color: red; } body {;`".to_string())
+        );
+        assert!(set_declaration_in_ast(code, ".b", "color", "red; } body {").is_err());
+        assert!(set_declaration_in_ast(code, ".a", "", "blue").is_err());
+        assert!(set_declaration_in_ast(code, ".a", "color", "blue; margin: 0").is_err());
+    }
+
+    #[test]
+    fn test_selector_specificity_from_ast() {
+        let code = r#"
+#main .btn a { color: red; }
+.btn, #main { color: red; }
+a:hover::before, li:first-child { color: red; }
+input[type="text"]:not(#id, .a) { color: red; }
+:where(#main .btn) a { color: red; }
+* { margin: 0; }
+
+@media (min-width: 768px) {
+  .card { margin: 0; }
+}
+"#;
+
+        let specificities = selector_specificity_from_ast(code).unwrap();
+        let triples: Vec<(&str, Specificity)> = specificities
+            .iter()
+            .map(|rule| (rule.selector.as_str(), rule.specificity))
+            .collect();
+
+        assert_eq!(
+            triples,
+            vec![
+                ("#main .btn a", (1, 1, 1)),
+                (".btn, #main", (1, 0, 0)),
+                ("a:hover::before, li:first-child", (0, 1, 2)),
+                ("input[type=\"text\"]:not(#id, .a)", (1, 1, 1)),
+                (":where(#main .btn) a", (0, 0, 1)),
+                ("*", (0, 0, 0)),
+            ]
+        );
+
+        assert!(selector_specificity_from_ast(".btn { color: red").is_err());
+    }
+
+    #[test]
+    fn test_rename_custom_property_in_ast() {
+        let code = include_str!("../../../../../test/assets/themeVariables.css");
+
+        let result = rename_custom_property_in_ast(code, "--color-primary", "brand").unwrap();
+        assert_eq!(result, code.replace("--color-primary", "--brand"));
+
+        let code = r#":root {
+  --spacing: 4px;
+  --gap: var(--spacing);
+}
+
+.card {
+  /* Keep the fallback */
+  margin: var(--spacing, 8px) var(--spacing-large);
+}
+"#;
+        let result = rename_custom_property_in_ast(code, "spacing", "--space").unwrap();
+        assert_eq!(
+            result,
+            r#":root {
+  --space: 4px;
+  --gap: var(--space);
+}
+
+.card {
+  /* Keep the fallback */
+  margin: var(--space, 8px) var(--spacing-large);
+}
+"#
+        );
+
+        assert!(rename_custom_property_in_ast(code, "--missing", "--other").is_err());
+        assert!(rename_custom_property_in_ast(".a { color: red", "--a", "--b").is_err());
+    }
+
+    #[test]
+    fn test_editors_on_empty_input() {
+        use crate::helpers::EMPTY_SOURCE_ERROR;
+
+        // Editors with nothing to clean up are a no-op
+        assert_eq!(remove_empty_rules_from_ast(""), Ok("".to_string()));
+        assert_eq!(merge_duplicate_rules_from_ast(""), Ok("".to_string()));
+
+        // Editors that add code return only the added code
+        assert_eq!(
+            insert_import_to_ast("", r#"@import "base.css";"#),
+            Ok("@import \"base.css\";\n".to_string())
+        );
+        let rule = ".a { color: red; }";
+        assert_eq!(
+            insert_rule_to_ast("", rule, RulePosition::Top),
+            Ok(".a { color: red; }\n".to_string())
+        );
+        assert_eq!(
+            insert_rule_to_ast("", rule, RulePosition::Bottom),
+            Ok(".a { color: red; }\n".to_string())
+        );
+        assert_eq!(
+            set_declaration_in_ast("", ".btn", "color", "blue"),
+            Ok(".btn {\n  color: blue;\n}\n".to_string())
+        );
+        assert_eq!(
+            set_custom_property_from_ast("", None, "radius", "8px"),
+            Ok(":root {\n  --radius: 8px;\n}\n".to_string())
+        );
+
+        // Editors that need an edit target fail with a clear message
+        let empty = Err(EMPTY_SOURCE_ERROR.to_string());
+        assert_eq!(
+            insert_rule_to_ast("", rule, RulePosition::After(".b")),
+            empty
+        );
+        assert_eq!(rename_class_in_ast("", "btn", "button"), empty);
+        assert_eq!(rename_custom_property_in_ast("", "--a", "--b"), empty);
+    }
+}
@@ -0,0 +1,310 @@
+use crate::atoms;
+use crate::helpers::encode_response;
+use crate::parsers::css::ast::*;
+
+use rustler::{Env, NifResult, NifStruct, NifTaggedEnum, Term};
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_css_import_to_ast_nif(
+    env: Env,
+    file_content: String,
+    import_lines: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::insert_css_import_to_ast_nif();
+    let (status, result) = match insert_import_to_ast(&file_content, &import_lines) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum RulePositionType {
+    Top,
+    Bottom,
+    After(String),
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_insert_rule_to_ast_nif(
+    env: Env,
+    file_content: String,
+    rule_source: String,
+    position: RulePositionType,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_insert_rule_to_ast_nif();
+    let position = match &position {
+        RulePositionType::Top => RulePosition::Top,
+        RulePositionType::Bottom => RulePosition::Bottom,
+        RulePositionType::After(selector) => RulePosition::After(selector),
+    };
+    let (status, result) = match insert_rule_to_ast(&file_content, &rule_source, position) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_set_declaration_in_ast_nif(
+    env: Env,
+    file_content: String,
+    selector: String,
+    property: String,
+    value: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_set_declaration_in_ast_nif();
+    let (status, result) = match set_declaration_in_ast(&file_content, &selector, &property, &value)
+    {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_is_imported_from_ast_nif(
+    env: Env,
+    file_content: String,
+    import_line: String,
+    strict: bool,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_is_imported_from_ast_nif();
+    let (status, result) = match is_imported_from_ast(&file_content, &import_line, strict) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_class_has_declaration_from_ast_nif(
+    env: Env,
+    file_content: String,
+    class_name: String,
+    property: String,
+    value: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_class_has_declaration_from_ast_nif();
+
+    match class_has_declaration_from_ast(&file_content, &class_name, &property, &value) {
+        Ok(true) => encode_response(env, atoms::ok(), fn_atom, true),
+        Ok(false) => encode_response(env, atoms::error(), fn_atom, false),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_contains_class_from_ast_nif(
+    env: Env,
+    file_content: String,
+    class_name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_contains_class_from_ast_nif();
+    let (status, result) = match contains_class_from_ast(&file_content, &class_name) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_contains_custom_property_from_ast_nif(
+    env: Env,
+    file_content: String,
+    var_name: String,
+    root_only: bool,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_contains_custom_property_from_ast_nif();
+    let (status, result) =
+        match contains_custom_property_from_ast(&file_content, &var_name, root_only) {
+            Ok(true) => (atoms::ok(), true),
+            _ => (atoms::error(), false),
+        };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_set_custom_property_from_ast_nif(
+    env: Env,
+    file_content: String,
+    selector: Option<String>,
+    var_name: String,
+    value: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_set_custom_property_from_ast_nif();
+    let (status, result) =
+        match set_custom_property_from_ast(&file_content, selector.as_deref(), &var_name, &value) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.CSS.SelectorSummary"]
+pub struct SelectorSummaryResult {
+    pub classes: Vec<String>,
+    pub ids: Vec<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_list_selectors_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_list_selectors_from_ast_nif();
+    match list_selectors_from_ast(&file_content) {
+        Ok(summary) => {
+            let result = SelectorSummaryResult {
+                classes: summary.classes,
+                ids: summary.ids,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.CSS.SelectorSpecificity"]
+pub struct SelectorSpecificityResult {
+    pub selector: String,
+    pub specificity: (usize, usize, usize),
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_selector_specificity_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_selector_specificity_from_ast_nif();
+    match selector_specificity_from_ast(&file_content) {
+        Ok(specificities) => {
+            let result: Vec<SelectorSpecificityResult> = specificities
+                .into_iter()
+                .map(|rule| SelectorSpecificityResult {
+                    selector: rule.selector,
+                    specificity: rule.specificity,
+                })
+                .collect();
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_count_selectors_matching_from_ast_nif(
+    env: Env,
+    file_content: String,
+    prefix: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_count_selectors_matching_from_ast_nif();
+    match count_selectors_matching_from_ast(&file_content, &prefix) {
+        Ok(count) => encode_response(env, atoms::ok(), fn_atom, count),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_list_media_queries_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_list_media_queries_from_ast_nif();
+    match list_media_queries_from_ast(&file_content) {
+        Ok(queries) => encode_response(env, atoms::ok(), fn_atom, queries),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_rename_class_in_ast_nif(
+    env: Env,
+    file_content: String,
+    old_name: String,
+    new_name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_rename_class_in_ast_nif();
+    let (status, result) = match rename_class_in_ast(&file_content, &old_name, &new_name) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_rename_custom_property_in_ast_nif(
+    env: Env,
+    file_content: String,
+    old_name: String,
+    new_name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::css_rename_custom_property_in_ast_nif();
+    let (status, result) = match rename_custom_property_in_ast(&file_content, &old_name, &new_name)
+    {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_remove_empty_rules_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_remove_empty_rules_from_ast_nif();
+    let (status, result) = match remove_empty_rules_from_ast(&file_content) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_merge_duplicate_rules_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_merge_duplicate_rules_from_ast_nif();
+    let (status, result) = match merge_duplicate_rules_from_ast(&file_content) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_validate_css_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_validate_css_from_ast_nif();
+    match validate_css_from_ast(&file_content) {
+        Ok(()) => encode_response(env, atoms::ok(), fn_atom, true),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.CSS.CSSStatisticsResult"]
+pub struct CSSStatisticsResult {
+    pub rules: usize,
+    pub at_rules: usize,
+    pub declarations: usize,
+    pub max_declarations: usize,
+    pub rule_declarations: Vec<(String, usize)>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn css_statistics_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::css_statistics_from_ast_nif();
+    match statistics_from_ast(&file_content) {
+        Ok(statistics) => {
+            let result = CSSStatisticsResult {
+                rules: statistics.rules,
+                at_rules: statistics.at_rules,
+                declarations: statistics.declarations,
+                max_declarations: statistics.max_declarations,
+                rule_declarations: statistics.rule_declarations,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
@@ -27,6 +27,15 @@ pub fn is_formatted(source_code: &str) -> Result<bool, String> {
     Ok(formatted_code.trim() == source_code.trim())
 }
 
+/// Reads the file at `path` on the Rust side and formats its content, so big sources don't
+/// have to be copied across the NIF boundary.
+pub fn format_file(path: &str) -> Result<String, String> {
+    let source_code =
+        std::fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))?;
+
+    format(&source_code)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -78,4 +87,21 @@ h1 {
         let formatted = format(css_formatted).unwrap();
         assert_eq!(is_formatted(&formatted).unwrap(), true);
     }
+
+    #[test]
+    fn test_format_file_css() {
+        let path = std::env::temp_dir().join("igniter_js_format_file_test.css");
+        std::fs::write(&path, "body{color:red}").unwrap();
+        assert_eq!(
+            format_file(path.to_str().unwrap()),
+            Ok("body {\n  color: red;\n}\n".to_string())
+        );
+
+        std::fs::write(&path, "body { color: red;").unwrap();
+        assert!(format_file(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = format_file("/none/existing/path/file.css");
+        assert!(missing.unwrap_err().starts_with("Failed to read"));
+    }
 }
@@ -4,7 +4,7 @@ use crate::parsers::css::formatter::*;
 
 use rustler::{Env, NifResult, Term};
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn format_css_nif(env: Env, file_content: String) -> NifResult<Term> {
     let fn_atom = atoms::format_css_nif();
     let (status, result) = match format(&file_content) {
@@ -15,7 +15,7 @@ pub fn format_css_nif(env: Env, file_content: String) -> NifResult<Term> {
     encode_response(env, status, fn_atom, result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn is_css_formatted_nif(env: Env, file_content: String) -> NifResult<Term> {
     let fn_atom = atoms::is_css_formatted_nif();
     let (status, result) = match is_formatted(&file_content) {
@@ -25,3 +25,14 @@ pub fn is_css_formatted_nif(env: Env, file_content: String) -> NifResult<Term> {
 
     encode_response(env, status, fn_atom, result)
 }
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn format_css_file_nif(env: Env, path: String) -> NifResult<Term> {
+    let fn_atom = atoms::format_css_file_nif();
+    let (status, result) = match format_file(&path) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
@@ -1,2 +1,4 @@
+pub mod ast;
+pub mod ast_ex;
 pub mod formatter;
 pub mod formatter_ex;
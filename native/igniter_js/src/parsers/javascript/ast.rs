@@ -7,10 +7,13 @@
 //!
 //! The module leverages a Rust-based parser and integrates seamlessly with Elixir through NIFs.
 
+use crate::helpers::ensure_source_not_empty;
 use crate::parsers::javascript::helpers::*;
-use swc_common::{SyntaxContext, DUMMY_SP};
+use std::collections::HashMap;
+use swc_common::comments::{Comment, CommentKind, Comments, SingleThreadedComments};
+use swc_common::{Span, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
-use swc_ecma_visit::{VisitMut, VisitMutWith};
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
 #[derive(Debug, PartialEq, Eq)]
 pub enum Operation {
@@ -54,35 +57,41 @@ impl FindCondition {
 // ###################################################################################
 
 #[derive(Debug)]
-struct ASTVisitImport<'a> {
-    code: &'a str,
+struct ASTVisitImport {
+    imports: Vec<ModuleItem>,
     duplicate_imports: Vec<String>,
     none_duplicate_imports: Vec<String>,
+    inserted_imports: Vec<ModuleItem>,
+    before_source: Option<String>,
     operation: Operation,
 }
 
-impl Default for ASTVisitImport<'_> {
-    fn default() -> Self {
-        Self {
-            code: "",
+impl ASTVisitImport {
+    /// Parses the requested import lines up front, so a malformed input is reported
+    /// as an error before the visitor touches the target module.
+    fn new(code: &str, operation: Operation) -> Result<Self, String> {
+        let (imports, _comments, _cm) = parse(code)?;
+
+        Ok(Self {
+            imports: imports.body,
             duplicate_imports: Vec::new(),
             none_duplicate_imports: Vec::new(),
-            operation: Operation::Edit,
-        }
+            inserted_imports: Vec::new(),
+            before_source: None,
+            operation,
+        })
     }
 }
 
-impl VisitMut for ASTVisitImport<'_> {
+impl VisitMut for ASTVisitImport {
     fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
         // We are using it to delete imports
-        let (imports, _comments, _cm) = parse(self.code).expect("Failed to parse imports");
-
         if matches!(self.operation, Operation::Delete) {
             let mut indices_to_remove = vec![];
 
             for (index, item) in items.iter().enumerate() {
                 if let ModuleItem::ModuleDecl(ModuleDecl::Import(existing_import)) = item {
-                    if imports.body.iter().any(|import| {
+                    if self.imports.iter().any(|import| {
                         matches!(import, ModuleItem::ModuleDecl(ModuleDecl::Import(new_import))
                             if new_import.src.value == existing_import.src.value)
                     }) {
@@ -101,9 +110,7 @@ impl VisitMut for ASTVisitImport<'_> {
 
     fn visit_mut_module(&mut self, module: &mut Module) {
         // We are using it to add imports and know it is duplicated or not
-        let (imports, _comments, _cm) = parse(self.code).expect("Failed to parse imports");
-
-        for import in imports.body {
+        for import in self.imports.clone() {
             if !is_duplicate_import(&import, &module.body) {
                 if matches!(self.operation, Operation::Add | Operation::Read) {
                     let mut last_import_index = None;
@@ -122,7 +129,26 @@ impl VisitMut for ASTVisitImport<'_> {
                         }
                     }
 
-                    if let Some(index) = last_import_index {
+                    if matches!(self.operation, Operation::Add)
+                        && merge_default_into_existing_import(&import, &mut module.body)
+                    {
+                        continue;
+                    }
+
+                    if matches!(self.operation, Operation::Add) {
+                        self.inserted_imports.push(import.clone());
+                    }
+
+                    let before_index = self.before_source.as_ref().and_then(|source| {
+                        module.body.iter().position(|item| {
+                            matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(existing))
+                                if existing.src.value == *source)
+                        })
+                    });
+
+                    if let Some(index) = before_index {
+                        module.body.insert(index, import);
+                    } else if let Some(index) = last_import_index {
                         module.body.insert(index + 1, import);
                     } else {
                         module.body.insert(0, import);
@@ -140,6 +166,62 @@ impl VisitMut for ASTVisitImport<'_> {
     }
 }
 
+/// Merges a default-plus-named import into an existing import of the same source that
+/// has no default yet, e.g. `import React, { useState } from "react"` into
+/// `import { useState } from "react"`, instead of adding a second declaration that would
+/// bind `useState` twice. The default is placed first and missing named specifiers are
+/// appended. Returns `false` when the import has no default or no import can take it.
+fn merge_default_into_existing_import(import: &ModuleItem, body: &mut [ModuleItem]) -> bool {
+    let ModuleItem::ModuleDecl(ModuleDecl::Import(new_import)) = import else {
+        return false;
+    };
+    let has_default = new_import
+        .specifiers
+        .iter()
+        .any(|specifier| matches!(specifier, ImportSpecifier::Default(_)));
+    if !has_default || !is_mergeable_import(new_import) {
+        return false;
+    }
+
+    let existing = body.iter_mut().find_map(|item| match item {
+        ModuleItem::ModuleDecl(ModuleDecl::Import(existing))
+            if existing.src.value == new_import.src.value
+                && is_mergeable_import(existing)
+                && !existing
+                    .specifiers
+                    .iter()
+                    .any(|specifier| matches!(specifier, ImportSpecifier::Default(_))) =>
+        {
+            Some(existing)
+        }
+        _ => None,
+    });
+    let Some(existing) = existing else {
+        return false;
+    };
+
+    for specifier in &new_import.specifiers {
+        let key = import_specifier_key(specifier);
+        if existing
+            .specifiers
+            .iter()
+            .any(|existing_specifier| import_specifier_key(existing_specifier) == key)
+        {
+            continue;
+        }
+
+        let mut specifier = specifier.clone();
+        specifier.visit_mut_with(&mut SpanRemover);
+        if matches!(specifier, ImportSpecifier::Default(_)) {
+            existing.specifiers.insert(0, specifier);
+        } else {
+            existing.specifiers.push(specifier);
+        }
+    }
+
+    true
+}
+
 /// Checks if a specific module is imported in the JavaScript source code.
 ///
 /// This function parses the given JavaScript source code into an AST
@@ -153,11 +235,8 @@ impl VisitMut for ASTVisitImport<'_> {
 /// A `Result` containing `true` if the module is imported, `false` otherwise,
 /// or an error message if parsing fails.
 pub fn is_module_imported_from_ast(file_content: &str, module_name: &str) -> Result<bool, bool> {
-    let mut import_visitor = ASTVisitImport {
-        code: module_name,
-        operation: Operation::Read,
-        ..Default::default()
-    };
+    let mut import_visitor =
+        ASTVisitImport::new(module_name, Operation::Read).map_err(|_| false)?;
 
     let _output = code_gen_from_ast_vist(file_content, &mut import_visitor);
 
@@ -172,6 +251,185 @@ pub fn is_module_imported_from_ast(file_content: &str, module_name: &str) -> Res
     }
 }
 
+/// How a module is imported, see [`module_import_presence_from_ast`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportPresence {
+    /// An `import ... from "module"` declaration.
+    Static,
+    /// Only an `import("module")` call, e.g. in a code-split app.
+    Dynamic,
+}
+
+struct DynamicImportFinder<'a> {
+    module_source: &'a str,
+    found: bool,
+}
+
+impl Visit for DynamicImportFinder<'_> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if let Callee::Import(_) = call_expr.callee {
+            if let Some(Expr::Lit(Lit::Str(source))) = call_expr.args.first().map(|arg| &*arg.expr)
+            {
+                if source.value == self.module_source {
+                    self.found = true;
+                }
+            }
+        }
+
+        call_expr.visit_children_with(self)
+    }
+}
+
+/// Checks if a module is imported by its source, e.g. `phoenix`, and tells how.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `module_source`: The module source as written in the import, without quotes.
+/// - `include_dynamic`: Whether an `import("module")` call with that string literal counts
+///   as an import when there is no static import.
+///
+/// # Returns
+/// A `Result` containing the [`ImportPresence`] (a static import wins over a dynamic one),
+/// or `false` when the module is not imported or the code can not be parsed.
+pub fn module_import_presence_from_ast(
+    file_content: &str,
+    module_source: &str,
+    include_dynamic: bool,
+) -> Result<ImportPresence, bool> {
+    let (module, _comments, _cm) = parse(file_content).map_err(|_| false)?;
+
+    let static_import = module.body.iter().any(|item| {
+        matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))
+            if import_decl.src.value == module_source)
+    });
+    if static_import {
+        return Ok(ImportPresence::Static);
+    }
+
+    if include_dynamic {
+        let mut finder = DynamicImportFinder {
+            module_source,
+            found: false,
+        };
+        module.visit_with(&mut finder);
+
+        if finder.found {
+            return Ok(ImportPresence::Dynamic);
+        }
+    }
+
+    Err(false)
+}
+
+/// How an import binds its local name, see [`ImportInfo`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ImportBindingKind {
+    /// `import topbar from "..."`.
+    Default,
+    /// `import { Socket } from "..."` or `import { Socket as PhxSocket } from "..."`.
+    Named,
+    /// `import * as Hooks from "..."`.
+    Namespace,
+}
+
+/// The import that introduces a local binding, see [`find_import_by_local_ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportInfo {
+    pub source: String,
+    pub kind: ImportBindingKind,
+    /// The exported name of a named import, e.g. `Socket` for `{ Socket as PhxSocket }`;
+    /// `None` for default and namespace imports.
+    pub imported: Option<String>,
+}
+
+/// Finds the import that introduces `local_name`, e.g. to learn where `topbar` comes from.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `local_name`: The local binding, e.g. `PhxSocket` for `{ Socket as PhxSocket }`.
+///
+/// # Returns
+/// A `Result` containing the [`ImportInfo`] of the binding, or an error message if parsing
+/// fails or no import binds `local_name`.
+pub fn find_import_by_local_ast(
+    file_content: &str,
+    local_name: &str,
+) -> Result<ImportInfo, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => Some(import_decl),
+            _ => None,
+        })
+        .find_map(|import_decl| {
+            import_decl.specifiers.iter().find_map(|specifier| {
+                let (local, kind, imported) = match specifier {
+                    ImportSpecifier::Default(default) => {
+                        (&default.local, ImportBindingKind::Default, None)
+                    }
+                    ImportSpecifier::Named(named) => {
+                        let imported = named
+                            .imported
+                            .as_ref()
+                            .map(module_export_name)
+                            .unwrap_or_else(|| named.local.sym.to_string());
+                        (&named.local, ImportBindingKind::Named, Some(imported))
+                    }
+                    ImportSpecifier::Namespace(namespace) => {
+                        (&namespace.local, ImportBindingKind::Namespace, None)
+                    }
+                };
+
+                (local.sym == local_name).then(|| ImportInfo {
+                    source: import_decl.src.value.to_string(),
+                    kind,
+                    imported,
+                })
+            })
+        })
+        .ok_or_else(|| format!("No import binds `{}`", local_name))
+}
+
+/// A dependency of a file, see [`import_edges_from_ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportEdge {
+    pub from: String,
+    pub to: String,
+}
+
+/// Lists one `from_label -> source` edge per static import, in source order, to build a
+/// dependency graph. Dynamic `import()` calls are not included.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `from_label`: The label of the file in the graph, e.g. its path.
+///
+/// # Returns
+/// A `Result` containing the edges, or an error message if parsing fails.
+pub fn import_edges_from_ast(
+    file_content: &str,
+    from_label: &str,
+) -> Result<Vec<ImportEdge>, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    let edges = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => Some(ImportEdge {
+                from: from_label.to_string(),
+                to: import_decl.src.value.to_string(),
+            }),
+            _ => None,
+        })
+        .collect();
+
+    Ok(edges)
+}
+
 /// Inserts new import statements into JavaScript source code.
 ///
 /// Parses the provided JavaScript source code into an AST, adds the specified
@@ -188,13 +446,197 @@ pub fn is_module_imported_from_ast(file_content: &str, module_name: &str) -> Res
 ///
 /// # Behavior
 /// - Ensures duplicate imports are skipped.
+/// - Adds the default of a default-plus-named import to an existing import of the same
+///   source that only has named specifiers, instead of inserting a second declaration.
 /// - Inserts new import statements after existing ones or at the top if none exist.
 pub fn insert_import_to_ast(file_content: &str, import_lines: &str) -> Result<String, String> {
-    let mut import_visitor = ASTVisitImport {
-        code: import_lines,
-        operation: Operation::Add,
-        ..Default::default()
-    };
+    insert_import_to_ast_with_options(file_content, import_lines, &EmitOptions::default())
+}
+
+/// Same as [`insert_import_to_ast`], emitting the result with the given [`EmitOptions`].
+pub fn insert_import_to_ast_with_options(
+    file_content: &str,
+    import_lines: &str,
+    options: &EmitOptions,
+) -> Result<String, String> {
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+
+    code_gen_from_ast_vist_with_options(file_content, &mut import_visitor, options)
+}
+
+/// Inserts `import_lines` into an already parsed module like [`insert_import_to_ast`],
+/// so the insertion can share one parse and emit with other edits.
+pub(crate) fn insert_import_to_module(
+    module: &mut Module,
+    import_lines: &str,
+) -> Result<(), String> {
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+    module.visit_mut_with(&mut import_visitor);
+
+    Ok(())
+}
+
+/// Quote style of the source string of an inserted import, see
+/// [`insert_import_to_ast_with_quote_style`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum QuoteStyle {
+    /// Keeps the quotes the import line was written with.
+    #[default]
+    AsWritten,
+    Single,
+    Double,
+}
+
+impl QuoteStyle {
+    /// Re-quotes `value` in this style, or returns `None` for [`QuoteStyle::AsWritten`].
+    fn quote(self, value: &str) -> Option<String> {
+        let quote = match self {
+            QuoteStyle::AsWritten => return None,
+            QuoteStyle::Single => '\'',
+            QuoteStyle::Double => '"',
+        };
+        let escaped = value
+            .replace('\\', "\\\\")
+            .replace(quote, &format!("\\{}", quote));
+
+        Some(format!("{}{}{}", quote, escaped, quote))
+    }
+}
+
+/// Inserts new import statements like [`insert_import_to_ast`], writing the source string of
+/// every inserted import with `quote_style`. Imports already in the file keep their quotes
+/// either way, the codegen re-emits the raw source string of untouched literals.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `import_lines`: The new import lines to be added, separated by newlines.
+/// - `quote_style`: The quotes of the inserted import sources.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code or an error message if parsing fails.
+pub fn insert_import_to_ast_with_quote_style(
+    file_content: &str,
+    import_lines: &str,
+    quote_style: QuoteStyle,
+) -> Result<String, String> {
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+
+    for import in import_visitor.imports.iter_mut() {
+        if let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = import {
+            if let Some(raw) = quote_style.quote(&import_decl.src.value) {
+                import_decl.src.raw = Some(raw.into());
+            }
+        }
+    }
+
+    code_gen_from_ast_vist(file_content, &mut import_visitor)
+}
+
+/// Inserts `import_lines` like [`insert_import_to_ast`] only when the module is not imported
+/// yet (what [`is_module_imported_from_ast`] reports as absent), in a single parse.
+///
+/// # Returns
+/// A `Result` containing the code and whether an import was inserted. When every import is
+/// already present the original `file_content` is returned untouched with `false`, or an
+/// error message if parsing fails.
+pub fn insert_import_if_missing_ast(
+    file_content: &str,
+    import_lines: &str,
+) -> Result<(String, bool), String> {
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    module.visit_mut_with(&mut import_visitor);
+    if import_visitor.none_duplicate_imports.is_empty() {
+        return Ok((file_content.to_string(), false));
+    }
+
+    let code = code_gen_from_ast_module_with_options(
+        &mut module,
+        comments,
+        cm,
+        file_content,
+        &EmitOptions::default(),
+    )?;
+
+    Ok((code, true))
+}
+
+/// Dry run of [`insert_import_to_ast`]: reports whether inserting `import_lines` would
+/// change the file, e.g. `false` when every import is a duplicate.
+pub fn insert_import_to_ast_would_change(
+    file_content: &str,
+    import_lines: &str,
+) -> Result<bool, String> {
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+
+    changes_from_ast_vist(file_content, &mut import_visitor)
+}
+
+/// Inserts new import statements like [`insert_import_to_ast`] and also reports where they
+/// landed in the generated code.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `import_lines`: The new import lines to be added, separated by newlines.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code and the `(start, end)` byte range of
+/// every inserted import statement (end exclusive, trailing newline not included), sorted
+/// by position. Imports skipped as duplicates have no range.
+pub fn insert_import_to_ast_with_spans(
+    file_content: &str,
+    import_lines: &str,
+) -> Result<(String, Vec<(usize, usize)>), String> {
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+    let output = code_gen_from_ast_vist(file_content, &mut import_visitor)?;
+
+    let mut spans: Vec<(usize, usize)> = Vec::new();
+    for import in import_visitor.inserted_imports {
+        let mut standalone = Module {
+            span: DUMMY_SP,
+            body: vec![import],
+            shebang: None,
+        };
+        let emitted =
+            code_gen_from_ast_module(&mut standalone, Default::default(), Default::default())?;
+        let emitted = emitted.trim_end();
+
+        let start = output.match_indices(emitted).map(|(i, _)| i).find(|i| {
+            (*i == 0 || output[..*i].ends_with('\n')) && !spans.iter().any(|(start, _)| start == i)
+        });
+
+        if let Some(start) = start {
+            spans.push((start, start + emitted.len()));
+        }
+    }
+    spans.sort();
+
+    Ok((output, spans))
+}
+
+/// Inserts new import statements immediately before the import whose source equals
+/// `before_source`, e.g. to keep vendor imports grouped.
+///
+/// Falls back to the behavior of [`insert_import_to_ast`] when no import from
+/// `before_source` exists. Duplicate imports are skipped.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `import_lines`: The new import lines to be added, separated by newlines.
+/// - `before_source`: The module source of the import used as the marker.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code as a `String` on success,
+/// or an error message if parsing fails.
+pub fn insert_import_before_ast(
+    file_content: &str,
+    import_lines: &str,
+    before_source: &str,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut import_visitor = ASTVisitImport::new(import_lines, Operation::Add)?;
+    import_visitor.before_source = Some(before_source.to_string());
 
     code_gen_from_ast_vist(file_content, &mut import_visitor)
 }
@@ -217,466 +659,4770 @@ pub fn insert_import_to_ast(file_content: &str, import_lines: &str) -> Result<St
 /// - Retains all other import statements and code structure.
 /// - Removes only the specified modules from the import declarations.
 pub fn remove_import_from_ast(file_content: &str, modules: &str) -> Result<String, String> {
-    let mut import_visitor = ASTVisitImport {
-        code: modules,
-        operation: Operation::Delete,
-        ..Default::default()
-    };
+    remove_import_from_ast_with_options(file_content, modules, &EmitOptions::default())
+}
 
-    code_gen_from_ast_vist(file_content, &mut import_visitor)
+/// Same as [`remove_import_from_ast`], emitting the result with the given [`EmitOptions`].
+pub fn remove_import_from_ast_with_options(
+    file_content: &str,
+    modules: &str,
+    options: &EmitOptions,
+) -> Result<String, String> {
+    let mut import_visitor = ASTVisitImport::new(modules, Operation::Delete)?;
+
+    code_gen_from_ast_vist_with_options(file_content, &mut import_visitor, options)
 }
 
-// ###################################################################################
-// ##################### (▰˘◡˘▰) Work with AST Statistics (▰˘◡˘▰) ####################
-// ###################################################################################
-pub struct ASTStatistics {
-    pub functions: usize,
-    pub classes: usize,
-    pub debuggers: usize,
-    pub imports: usize,
-    pub trys: usize,
-    pub throws: usize,
-    pub operation: Operation,
+/// Dry run of [`remove_import_from_ast`]: reports whether removing `modules` would change
+/// the file, e.g. `false` when none of them is imported.
+pub fn remove_import_from_ast_would_change(
+    file_content: &str,
+    modules: &str,
+) -> Result<bool, String> {
+    let mut import_visitor = ASTVisitImport::new(modules, Operation::Delete)?;
+
+    changes_from_ast_vist(file_content, &mut import_visitor)
 }
 
-impl Default for ASTStatistics {
-    fn default() -> Self {
-        Self {
-            functions: 0,
-            classes: 0,
-            debuggers: 0,
-            imports: 0,
-            trys: 0,
-            throws: 0,
-            operation: Operation::Read,
+/// Sort key of an import specifier: the default import first, then the named imports by
+/// imported and local name. Namespace specifiers are never merged, so they go last.
+fn import_specifier_key(specifier: &ImportSpecifier) -> (u8, String, String) {
+    match specifier {
+        ImportSpecifier::Default(default) => (0, String::new(), default.local.sym.to_string()),
+        ImportSpecifier::Named(named) => {
+            let imported = named
+                .imported
+                .as_ref()
+                .map(module_export_name)
+                .unwrap_or_else(|| named.local.sym.to_string());
+            (1, imported, named.local.sym.to_string())
+        }
+        ImportSpecifier::Namespace(namespace) => {
+            (2, String::new(), namespace.local.sym.to_string())
         }
     }
 }
 
-impl VisitMut for ASTStatistics {
-    fn visit_mut_function(&mut self, node: &mut Function) {
-        if matches!(self.operation, Operation::Read) {
-            self.functions += 1;
-        }
-        node.visit_mut_children_with(self)
-    }
+fn normalize_import_specifiers(specifiers: &mut Vec<ImportSpecifier>) {
+    specifiers.sort_by_key(import_specifier_key);
+    specifiers.dedup_by(|a, b| import_specifier_key(a) == import_specifier_key(b));
+}
 
-    fn visit_mut_class(&mut self, node: &mut Class) {
-        if matches!(self.operation, Operation::Read) {
-            self.classes += 1;
-        }
-        node.visit_mut_children_with(self)
-    }
+/// Only plain default/named imports are merged; namespace, side effect, type-only and
+/// attributed imports keep their own declaration.
+fn is_mergeable_import(import_decl: &ImportDecl) -> bool {
+    !import_decl.type_only
+        && import_decl.with.is_none()
+        && import_decl.phase == ImportPhase::Evaluation
+        && !import_decl.specifiers.is_empty()
+        && !import_decl
+            .specifiers
+            .iter()
+            .any(|specifier| matches!(specifier, ImportSpecifier::Namespace(_)))
+}
 
-    fn visit_mut_debugger_stmt(&mut self, node: &mut DebuggerStmt) {
-        if matches!(self.operation, Operation::Read) {
-            self.debuggers += 1;
-        }
-        node.visit_mut_children_with(self)
-    }
+/// Two imports can be merged when they bind at most one default name between them.
+fn defaults_compatible(first: &ImportDecl, second: &ImportDecl) -> bool {
+    let mut defaults: Vec<&str> = first
+        .specifiers
+        .iter()
+        .chain(second.specifiers.iter())
+        .filter_map(|specifier| match specifier {
+            ImportSpecifier::Default(default) => Some(&*default.local.sym),
+            _ => None,
+        })
+        .collect();
+    defaults.sort();
+    defaults.dedup();
+    defaults.len() <= 1
+}
 
-    fn visit_mut_import_decl(&mut self, node: &mut ImportDecl) {
-        if matches!(self.operation, Operation::Read) {
-            self.imports += 1;
-        }
-        node.visit_mut_children_with(self)
-    }
+struct ImportNormalizer;
 
-    fn visit_mut_try_stmt(&mut self, node: &mut TryStmt) {
-        if matches!(self.operation, Operation::Read) {
-            self.trys += 1;
-        }
-        node.visit_mut_children_with(self)
-    }
+impl VisitMut for ImportNormalizer {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let mut body: Vec<ModuleItem> = Vec::with_capacity(module.body.len());
 
-    fn visit_mut_throw_stmt(&mut self, node: &mut ThrowStmt) {
-        if matches!(self.operation, Operation::Read) {
-            self.throws += 1;
+        for item in std::mem::take(&mut module.body) {
+            let ModuleItem::ModuleDecl(ModuleDecl::Import(mut import_decl)) = item else {
+                body.push(item);
+                continue;
+            };
+
+            normalize_import_specifiers(&mut import_decl.specifiers);
+
+            if is_mergeable_import(&import_decl) {
+                let target = body.iter_mut().find_map(|existing| match existing {
+                    ModuleItem::ModuleDecl(ModuleDecl::Import(existing))
+                        if existing.src.value == import_decl.src.value
+                            && is_mergeable_import(existing)
+                            && defaults_compatible(existing, &import_decl) =>
+                    {
+                        Some(existing)
+                    }
+                    _ => None,
+                });
+
+                if let Some(existing) = target {
+                    existing.specifiers.append(&mut import_decl.specifiers);
+                    normalize_import_specifiers(&mut existing.specifiers);
+                    continue;
+                }
+            } else if import_decl.specifiers.is_empty() {
+                // A repeated side effect import adds nothing
+                let repeated = body.iter().any(|existing| {
+                    matches!(existing, ModuleItem::ModuleDecl(ModuleDecl::Import(existing))
+                        if existing.specifiers.is_empty()
+                            && existing.src.value == import_decl.src.value
+                            && existing.with == import_decl.with)
+                });
+
+                if repeated {
+                    continue;
+                }
+            }
+
+            body.push(ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)));
         }
-        node.visit_mut_children_with(self)
+
+        module.body = body;
     }
 }
 
-/// Parses the given JavaScript source code and collects statistics about the AST nodes.
+/// Canonicalizes the import statements of the JavaScript source code, so the output is the
+/// same regardless of the edit history of the file.
+///
+/// - Specifiers of every import are sorted alphabetically and deduplicated.
+/// - Default and named imports from the same source are collapsed into the first one.
+/// - Repeated side effect imports (`import "m"`) are dropped.
+///
+/// Namespace imports are never merged with named imports, see [`is_duplicate_import`].
 ///
 /// # Arguments
-/// - `file_content`: A string slice containing the JavaScript source code.
+/// - `file_content`: The JavaScript source code as a string slice.
 ///
 /// # Returns
-/// A result containing `ASTStatistics` with statistics about the parsed source code or an
-/// error message if parsing fails.
-///
-/// # Example
-/// ```rust
-/// let result = statistics_from_ast(file_content);
-/// assert!(result.is_ok());
-/// ```
-pub fn statistics_from_ast(file_content: &str) -> Result<ASTStatistics, String> {
-    let mut import_visitor = ASTStatistics {
-        operation: Operation::Read,
-        ..Default::default()
-    };
+/// A `Result` containing the updated JavaScript code as a `String` on success,
+/// or an error message if parsing fails.
+pub fn normalize_imports_ast(file_content: &str) -> Result<String, String> {
+    code_gen_from_ast_vist(file_content, ImportNormalizer)
+}
 
-    let _ = code_gen_from_ast_vist(file_content, &mut import_visitor);
+/// Moves every import declaration above the other statements of the module.
+struct ImportHoister;
 
-    Ok(import_visitor)
+impl VisitMut for ImportHoister {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let items = std::mem::take(&mut module.body);
+        let (mut body, rest): (Vec<ModuleItem>, Vec<ModuleItem>) = items
+            .into_iter()
+            .partition(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))));
+
+        body.extend(rest);
+        module.body = body;
+    }
+}
+
+/// Moves the imports of a hand-edited file that are scattered after other statements to
+/// the top of the module. Imports keep their relative order, and so do the other
+/// statements.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code as a `String` on success,
+/// or an error message if parsing fails.
+pub fn hoist_imports_ast(file_content: &str) -> Result<String, String> {
+    code_gen_from_ast_vist(file_content, ImportHoister)
+}
+
+/// Lists the sources imported by more than one import declaration, e.g. after several edits
+/// added `import { useState } from "react"` next to an existing `import React from "react"`.
+/// It is the detection step for [`normalize_imports_ast`].
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing every duplicated source once, in the order of its first import,
+/// or an error message if parsing fails.
+pub fn find_duplicate_import_sources_from_ast(file_content: &str) -> Result<Vec<String>, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    let mut seen: Vec<String> = Vec::new();
+    let mut duplicates: Vec<String> = Vec::new();
+    for item in &module.body {
+        let ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) = item else {
+            continue;
+        };
+
+        let source = import_decl.src.value.to_string();
+        if !seen.contains(&source) {
+            seen.push(source);
+        } else if !duplicates.contains(&source) {
+            duplicates.push(source);
+        }
+    }
+
+    // Report in the order of the first import of each source
+    duplicates.sort_by_key(|source| seen.iter().position(|first| first == source));
+
+    Ok(duplicates)
+}
+
+/// Looks for a reference to one of `names` outside of the import declarations.
+struct IdentUsageFinder<'a> {
+    names: &'a [String],
+    found: bool,
+}
+
+impl Visit for IdentUsageFinder<'_> {
+    fn visit_import_decl(&mut self, _import_decl: &ImportDecl) {}
+
+    // `export { name } from "module"` does not reference the local `name`.
+    fn visit_named_export(&mut self, named_export: &NamedExport) {
+        if named_export.src.is_none() {
+            named_export.visit_children_with(self)
+        }
+    }
+
+    // Labels share the identifier type but are not references.
+    fn visit_labeled_stmt(&mut self, labeled_stmt: &LabeledStmt) {
+        labeled_stmt.body.visit_with(self)
+    }
+
+    fn visit_break_stmt(&mut self, _break_stmt: &BreakStmt) {}
+
+    fn visit_continue_stmt(&mut self, _continue_stmt: &ContinueStmt) {}
+
+    fn visit_ident(&mut self, ident: &Ident) {
+        if self.names.iter().any(|name| ident.sym == **name) {
+            self.found = true;
+        }
+    }
+}
+
+/// Checks whether a binding introduced by an import from `source` is used by the module,
+/// e.g. whether `import topbar from "../vendor/topbar"` can be removed. Default, namespace
+/// and named bindings are considered; property names such as `obj.topbar` are not
+/// references.
+///
+/// Scopes are not tracked, so a local variable with the same name as the binding counts as
+/// a use, which errs on the side of keeping the import. A side effect import
+/// (`import "module"`) is always reported as used.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `source`: The module source of the import, e.g. `../vendor/topbar`.
+///
+/// # Returns
+/// A `Result` containing whether a binding of the import is referenced, or an error
+/// message if parsing fails or nothing is imported from `source`.
+pub fn is_import_used_ast(file_content: &str, source: &str) -> Result<bool, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    let imports: Vec<&ImportDecl> = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl))
+                if import_decl.src.value == source =>
+            {
+                Some(import_decl)
+            }
+            _ => None,
+        })
+        .collect();
+
+    if imports.is_empty() {
+        return Err(format!("No import from `{}` found", source));
+    }
+    if imports
+        .iter()
+        .any(|import_decl| import_decl.specifiers.is_empty())
+    {
+        return Ok(true);
+    }
+
+    let names: Vec<String> = imports
+        .iter()
+        .flat_map(|import_decl| import_decl.specifiers.iter())
+        .map(|specifier| match specifier {
+            ImportSpecifier::Named(named) => named.local.sym.to_string(),
+            ImportSpecifier::Default(default) => default.local.sym.to_string(),
+            ImportSpecifier::Namespace(namespace) => namespace.local.sym.to_string(),
+        })
+        .collect();
+
+    let mut finder = IdentUsageFinder {
+        names: &names,
+        found: false,
+    };
+    module.visit_with(&mut finder);
+
+    Ok(finder.found)
 }
 
 // ###################################################################################
-// ################### (▰˘◡˘▰) Work with AST Var and Object (▰˘◡˘▰) ##################
+// ##################### (▰˘◡˘▰) Work with AST Statistics (▰˘◡˘▰) ####################
 // ###################################################################################
-struct ObjectExtender {
-    target_var_name: String,
-    new_properties: Vec<Prop>,
-    operation: Operation,
-    find: FindCondition,
+
+/// Node counters collected by [`statistics_from_ast`].
+///
+/// This SWC visitor is the only statistics backend of the crate; `statistics_from_ast_nif`
+/// copies its fields into `IgniterJs.Native.Parsers.Javascript.ASTStatisticsResult`, so
+/// adding a counter here means adding it to that `NifStruct` as well.
+pub struct ASTStatistics {
+    pub functions: usize,
+    pub classes: usize,
+    pub debuggers: usize,
+    pub imports: usize,
+    pub trys: usize,
+    pub throws: usize,
+    pub exports: usize,
+    pub consts: usize,
+    pub lets: usize,
+    pub vars: usize,
+    pub line_comments: usize,
+    pub block_comments: usize,
+    pub operation: Operation,
 }
 
-impl Default for ObjectExtender {
+impl Default for ASTStatistics {
     fn default() -> Self {
         Self {
-            target_var_name: "".to_string(),
-            new_properties: Vec::new(),
-            operation: Operation::Edit,
-            find: FindCondition::NotFound("".to_string()),
+            functions: 0,
+            classes: 0,
+            debuggers: 0,
+            imports: 0,
+            trys: 0,
+            throws: 0,
+            exports: 0,
+            consts: 0,
+            lets: 0,
+            vars: 0,
+            line_comments: 0,
+            block_comments: 0,
+            operation: Operation::Read,
         }
     }
 }
 
-impl VisitMut for ObjectExtender {
-    fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
-        if matches!(self.operation, Operation::Edit) {
-            for decl in &mut var_decl.decls {
-                if let Some(ident) = decl.name.as_ident() {
-                    if ident.sym == self.target_var_name {
-                        if let Some(init) = &mut decl.init {
-                            self.find = FindCondition::FoundError("".to_string());
-                            if let Expr::Object(obj_expr) = init.as_mut() {
-                                if matches!(self.operation, Operation::Edit) {
-                                    self.find = FindCondition::Found;
-                                    let existing_keys: Vec<String> = obj_expr
-                                        .props
-                                        .iter()
-                                        .filter_map(|prop| match prop {
-                                            PropOrSpread::Prop(prop) => match &**prop {
-                                                Prop::Shorthand(ident) => {
-                                                    Some(ident.sym.to_string())
-                                                }
-                                                Prop::KeyValue(key_value) => match &key_value.key {
-                                                    PropName::Ident(ident) => {
-                                                        Some(ident.sym.to_string())
-                                                    }
-                                                    _ => None,
-                                                },
-                                                _ => None,
-                                            },
-                                            PropOrSpread::Spread(spread) => match &*spread.expr {
-                                                Expr::Ident(ident) => {
-                                                    Some(format!("...{}", ident.sym))
-                                                }
-                                                _ => None,
-                                            },
-                                        })
-                                        .collect();
-
-                                    let new_props: Vec<PropOrSpread> = self
-                                        .new_properties
-                                        .clone()
-                                        .into_iter()
-                                        .filter(|prop| {
-                                            if let Prop::Shorthand(ident) = prop {
-                                                !existing_keys.contains(&ident.sym.to_string())
-                                            } else {
-                                                true
-                                            }
-                                        })
-                                        .map(|prop| PropOrSpread::Prop(Box::new(prop)))
-                                        .collect();
+impl ASTStatistics {
+    /// Counts the `//` and `/* */` comments captured by [`parse`]. Every comment is stored
+    /// once, either as leading or as trailing comment of a node.
+    fn count_comments(&mut self, comments: &SingleThreadedComments) {
+        let (leading, trailing) = comments.borrow_all();
 
-                                    obj_expr.props.extend(new_props);
-                                }
-                            }
-                        }
-                    }
-                }
+        for comment in leading.values().chain(trailing.values()).flatten() {
+            match comment.kind {
+                CommentKind::Line => self.line_comments += 1,
+                CommentKind::Block => self.block_comments += 1,
             }
         }
-        var_decl.visit_mut_children_with(self)
     }
 }
 
-pub fn extend_var_object_property_by_names_to_ast<'a>(
-    file_content: &str,
-    var_name: &str,
-    object_names: impl IntoIterator<Item = &'a str> + Clone,
-) -> Result<String, String> {
-    let new_properties: Vec<Prop> = object_names
-        .into_iter()
-        .map(|name| Prop::Shorthand(Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty())))
-        .collect();
+impl VisitMut for ASTStatistics {
+    fn visit_mut_function(&mut self, node: &mut Function) {
+        if matches!(self.operation, Operation::Read) {
+            self.functions += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
 
-    let mut object_extender = ObjectExtender {
-        target_var_name: var_name.to_string(),
-        new_properties,
-        operation: Operation::Edit,
-        ..Default::default()
-    };
+    fn visit_mut_class(&mut self, node: &mut Class) {
+        if matches!(self.operation, Operation::Read) {
+            self.classes += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
 
-    let result = code_gen_from_ast_vist(file_content, &mut object_extender);
-    if object_extender.find == FindCondition::Found {
-        result
-    } else {
-        Err(object_extender.find.message().to_string())
+    fn visit_mut_debugger_stmt(&mut self, node: &mut DebuggerStmt) {
+        if matches!(self.operation, Operation::Read) {
+            self.debuggers += 1;
+        }
+        node.visit_mut_children_with(self)
     }
-}
 
-pub fn contains_variable_from_ast(file_content: &str, variable_name: &str) -> Result<bool, bool> {
-    let (module, _, _) = parse(file_content).expect("Failed to parse imports");
+    fn visit_mut_import_decl(&mut self, node: &mut ImportDecl) {
+        if matches!(self.operation, Operation::Read) {
+            self.imports += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
 
-    for item in &module.body {
-        if let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = item {
-            if var_decl.kind == VarDeclKind::Let {
-                for decl in &var_decl.decls {
-                    if let Pat::Ident(BindingIdent { id, .. }) = &decl.name {
-                        if id.sym == variable_name {
-                            return Ok(true);
-                        }
-                    }
-                }
+    fn visit_mut_try_stmt(&mut self, node: &mut TryStmt) {
+        if matches!(self.operation, Operation::Read) {
+            self.trys += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_throw_stmt(&mut self, node: &mut ThrowStmt) {
+        if matches!(self.operation, Operation::Read) {
+            self.throws += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_export_decl(&mut self, node: &mut ExportDecl) {
+        if matches!(self.operation, Operation::Read) {
+            self.exports += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_export_default_decl(&mut self, node: &mut ExportDefaultDecl) {
+        if matches!(self.operation, Operation::Read) {
+            self.exports += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_export_default_expr(&mut self, node: &mut ExportDefaultExpr) {
+        if matches!(self.operation, Operation::Read) {
+            self.exports += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_named_export(&mut self, node: &mut NamedExport) {
+        if matches!(self.operation, Operation::Read) {
+            self.exports += 1;
+        }
+        node.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_var_decl(&mut self, node: &mut VarDecl) {
+        if matches!(self.operation, Operation::Read) {
+            // Each binding of `let a = 1, b = 2;` is counted on its own
+            let bindings = node.decls.len();
+            match node.kind {
+                VarDeclKind::Const => self.consts += bindings,
+                VarDeclKind::Let => self.lets += bindings,
+                VarDeclKind::Var => self.vars += bindings,
             }
         }
+        node.visit_mut_children_with(self)
     }
-    Err(false)
 }
 
-#[cfg(test)]
-mod tests {
-    use std::collections::HashSet;
+/// Parses the given JavaScript source code and collects statistics about the AST nodes.
+///
+/// # Arguments
+/// - `file_content`: A string slice containing the JavaScript source code.
+///
+/// # Returns
+/// A result containing `ASTStatistics` with statistics about the parsed source code or an
+/// error message if parsing fails.
+///
+/// # Example
+/// ```rust
+/// let result = statistics_from_ast(file_content);
+/// assert!(result.is_ok());
+/// ```
+pub fn statistics_from_ast(file_content: &str) -> Result<ASTStatistics, String> {
+    let (mut module, comments, _cm) = parse(file_content)?;
+    let mut import_visitor = ASTStatistics {
+        operation: Operation::Read,
+        ..Default::default()
+    };
+
+    module.visit_mut_with(&mut import_visitor);
+    import_visitor.count_comments(&comments);
+
+    Ok(import_visitor)
+}
+
+/// Source location of a module level import; `line` and `column` are 1-based.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ImportLocation {
+    pub source: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+/// The counters of [`statistics_from_ast`] together with the location of every import.
+pub struct ASTDetailedStatistics {
+    pub statistics: ASTStatistics,
+    pub imports: Vec<ImportLocation>,
+}
+
+/// Collects the same counters as [`statistics_from_ast`] and, for editor integrations, the
+/// source and position of each import so a caller can jump to it.
+pub fn statistics_detailed_from_ast(file_content: &str) -> Result<ASTDetailedStatistics, String> {
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    let mut statistics = ASTStatistics {
+        operation: Operation::Read,
+        ..Default::default()
+    };
+    module.visit_mut_with(&mut statistics);
+    statistics.count_comments(&comments);
+
+    let imports = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => {
+                let loc = cm.lookup_char_pos(import_decl.span.lo);
+                Some(ImportLocation {
+                    source: import_decl.src.value.to_string(),
+                    line: loc.line,
+                    column: loc.col.0 + 1,
+                })
+            }
+            _ => None,
+        })
+        .collect();
+
+    Ok(ASTDetailedStatistics {
+        statistics,
+        imports,
+    })
+}
+
+/// The keywords [`list_todo_comments_from_ast`] is usually called with.
+pub const DEFAULT_TODO_KEYWORDS: [&str; 2] = ["TODO", "FIXME"];
+
+/// A comment marked with one of the keywords of [`list_todo_comments_from_ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TodoComment {
+    /// The keyword found first in the comment, e.g. `TODO`.
+    pub keyword: String,
+    /// The comment text without its `//` or `/* */` delimiters, trimmed.
+    pub text: String,
+    /// The 1-based line the comment starts on.
+    pub line: usize,
+}
+
+/// Lists the comments that contain one of `keywords`, e.g. to build a tech-debt report.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `keywords`: The case-sensitive markers to look for, e.g. [`DEFAULT_TODO_KEYWORDS`].
+///
+/// # Returns
+/// A `Result` containing the matching comments in source order, or an error message if
+/// parsing fails.
+pub fn list_todo_comments_from_ast(
+    file_content: &str,
+    keywords: &[&str],
+) -> Result<Vec<TodoComment>, String> {
+    let (_module, comments, cm) = parse(file_content)?;
+    let (leading, trailing) = comments.borrow_all();
+
+    let mut all_comments: Vec<&Comment> = leading
+        .values()
+        .chain(trailing.values())
+        .flatten()
+        .collect();
+    all_comments.sort_by_key(|comment| comment.span.lo);
+
+    let todos = all_comments
+        .into_iter()
+        .filter_map(|comment| {
+            let keyword = keywords
+                .iter()
+                .filter_map(|keyword| comment.text.find(keyword).map(|index| (index, keyword)))
+                .min()?
+                .1;
+
+            Some(TodoComment {
+                keyword: keyword.to_string(),
+                text: comment.text.trim().to_string(),
+                line: cm.lookup_char_pos(comment.span.lo).line,
+            })
+        })
+        .collect();
+
+    Ok(todos)
+}
+
+/// A string literal found by [`extract_string_literals_from_ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct StringLiteral {
+    /// The value of the literal, without quotes and with escapes resolved.
+    pub value: String,
+    /// The 1-based line the literal starts on.
+    pub line: usize,
+}
+
+/// Collects the string literals that are not module sources or property names.
+struct StringLiteralCollector<'a> {
+    cm: &'a swc_common::SourceMap,
+    literals: Vec<StringLiteral>,
+}
+
+impl Visit for StringLiteralCollector<'_> {
+    fn visit_import_decl(&mut self, _import_decl: &ImportDecl) {}
+
+    fn visit_export_all(&mut self, _export_all: &ExportAll) {}
+
+    fn visit_named_export(&mut self, _named_export: &NamedExport) {}
+
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        // The source of a dynamic `import("module")`
+        if !matches!(call_expr.callee, Callee::Import(_)) {
+            call_expr.visit_children_with(self)
+        }
+    }
+
+    fn visit_prop_name(&mut self, prop_name: &PropName) {
+        if let PropName::Computed(computed) = prop_name {
+            computed.visit_with(self)
+        }
+    }
+
+    fn visit_str(&mut self, str: &Str) {
+        self.literals.push(StringLiteral {
+            value: str.value.to_string(),
+            line: self.cm.lookup_char_pos(str.span.lo).line,
+        });
+    }
+}
+
+/// Lists the string literals of the module, e.g. to feed a translation-key extractor.
+///
+/// Module sources (of static imports, re-exports and dynamic `import()` calls) and object
+/// or class keys such as `{ "label": ... }` are left out, a computed key like
+/// `{ ["label"]: ... }` is kept. Template literals are not string literals and are not
+/// included.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the literals in source order, or an error message if parsing
+/// fails.
+pub fn extract_string_literals_from_ast(file_content: &str) -> Result<Vec<StringLiteral>, String> {
+    let (module, _comments, cm) = parse(file_content)?;
+
+    let mut collector = StringLiteralCollector {
+        cm: &cm,
+        literals: vec![],
+    };
+    module.visit_with(&mut collector);
+
+    Ok(collector.literals)
+}
+
+/// Counts the source lines of code: every line minus the blank lines and the lines that only
+/// hold comments. A line with code next to a comment counts.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` with the number of lines of code, or an error message if parsing fails.
+pub fn count_sloc_from_ast(file_content: &str) -> Result<usize, String> {
+    let (_module, comments, cm) = parse(file_content)?;
+    let (leading, trailing) = comments.borrow_all();
+
+    let mut in_comment = vec![false; file_content.len()];
+    for comment in leading.values().chain(trailing.values()).flatten() {
+        let start = cm.lookup_byte_offset(comment.span.lo).pos.0 as usize;
+        let end = (cm.lookup_byte_offset(comment.span.hi).pos.0 as usize).min(in_comment.len());
+        if start < end {
+            in_comment[start..end].fill(true);
+        }
+    }
+
+    let mut sloc = 0;
+    let mut line_start = 0;
+    for line in file_content.split_inclusive('\n') {
+        let has_code = line
+            .char_indices()
+            .any(|(index, char)| !char.is_whitespace() && !in_comment[line_start + index]);
+        if has_code {
+            sloc += 1;
+        }
+        line_start += line.len();
+    }
+
+    Ok(sloc)
+}
+
+/// The newer syntax features used by a source, see [`detect_js_features_from_ast`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct JSFeatures {
+    /// `a?.b`, `a?.[b]` or `a?.()`.
+    pub optional_chaining: bool,
+    /// `a ?? b` or `a ??= b`.
+    pub nullish_coalescing: bool,
+    /// `await` or `for await` outside of any function.
+    pub top_level_await: bool,
+    /// `import("module")`.
+    pub dynamic_import: bool,
+}
+
+#[derive(Default)]
+struct JSFeatureDetector {
+    features: JSFeatures,
+    function_depth: usize,
+}
+
+impl Visit for JSFeatureDetector {
+    fn visit_function(&mut self, function: &Function) {
+        self.function_depth += 1;
+        function.visit_children_with(self);
+        self.function_depth -= 1;
+    }
+
+    fn visit_arrow_expr(&mut self, arrow_expr: &ArrowExpr) {
+        self.function_depth += 1;
+        arrow_expr.visit_children_with(self);
+        self.function_depth -= 1;
+    }
+
+    fn visit_opt_chain_expr(&mut self, opt_chain_expr: &OptChainExpr) {
+        self.features.optional_chaining = true;
+        opt_chain_expr.visit_children_with(self);
+    }
+
+    fn visit_bin_expr(&mut self, bin_expr: &BinExpr) {
+        if bin_expr.op == BinaryOp::NullishCoalescing {
+            self.features.nullish_coalescing = true;
+        }
+        bin_expr.visit_children_with(self);
+    }
+
+    fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+        if assign_expr.op == AssignOp::NullishAssign {
+            self.features.nullish_coalescing = true;
+        }
+        assign_expr.visit_children_with(self);
+    }
+
+    fn visit_await_expr(&mut self, await_expr: &AwaitExpr) {
+        if self.function_depth == 0 {
+            self.features.top_level_await = true;
+        }
+        await_expr.visit_children_with(self);
+    }
+
+    fn visit_for_of_stmt(&mut self, for_of_stmt: &ForOfStmt) {
+        if for_of_stmt.is_await && self.function_depth == 0 {
+            self.features.top_level_await = true;
+        }
+        for_of_stmt.visit_children_with(self);
+    }
+
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if let Callee::Import(_) = call_expr.callee {
+            self.features.dynamic_import = true;
+        }
+        call_expr.visit_children_with(self);
+    }
+}
+
+/// Detects which newer syntax features a source uses, e.g. to warn when it needs a newer
+/// build target.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the detected [`JSFeatures`], or an error message if parsing fails.
+pub fn detect_js_features_from_ast(file_content: &str) -> Result<JSFeatures, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    let mut detector = JSFeatureDetector::default();
+    module.visit_with(&mut detector);
+
+    Ok(detector.features)
+}
+
+/// Counts the requested node kinds in a single pass; see [`count_node_kinds_from_ast`].
+struct NodeKindCounter {
+    counts: HashMap<String, usize>,
+}
+
+impl NodeKindCounter {
+    fn bump(&mut self, kind: &str) {
+        if let Some(count) = self.counts.get_mut(kind) {
+            *count += 1;
+        }
+    }
+}
+
+/// Implements one `VisitMut` method per supported node kind and lists the kind names,
+/// which are the SWC type names of the nodes.
+macro_rules! node_kind_counter {
+    ($($method:ident($node:ty) => $kind:literal),* $(,)?) => {
+        pub const COUNTABLE_NODE_KINDS: &[&str] = &[$($kind),*];
+
+        impl VisitMut for NodeKindCounter {
+            $(
+                fn $method(&mut self, node: &mut $node) {
+                    self.bump($kind);
+                    node.visit_mut_children_with(self)
+                }
+            )*
+        }
+    };
+}
+
+node_kind_counter! {
+    visit_mut_arrow_expr(ArrowExpr) => "ArrowExpr",
+    visit_mut_await_expr(AwaitExpr) => "AwaitExpr",
+    visit_mut_yield_expr(YieldExpr) => "YieldExpr",
+    visit_mut_call_expr(CallExpr) => "CallExpr",
+    visit_mut_new_expr(NewExpr) => "NewExpr",
+    visit_mut_member_expr(MemberExpr) => "MemberExpr",
+    visit_mut_cond_expr(CondExpr) => "CondExpr",
+    visit_mut_assign_expr(AssignExpr) => "AssignExpr",
+    visit_mut_object_lit(ObjectLit) => "ObjectLit",
+    visit_mut_array_lit(ArrayLit) => "ArrayLit",
+    visit_mut_tpl(Tpl) => "Tpl",
+    visit_mut_fn_decl(FnDecl) => "FnDecl",
+    visit_mut_fn_expr(FnExpr) => "FnExpr",
+    visit_mut_class_decl(ClassDecl) => "ClassDecl",
+    visit_mut_class_expr(ClassExpr) => "ClassExpr",
+    visit_mut_class_method(ClassMethod) => "ClassMethod",
+    visit_mut_var_decl(VarDecl) => "VarDecl",
+    visit_mut_if_stmt(IfStmt) => "IfStmt",
+    visit_mut_for_stmt(ForStmt) => "ForStmt",
+    visit_mut_for_in_stmt(ForInStmt) => "ForInStmt",
+    visit_mut_for_of_stmt(ForOfStmt) => "ForOfStmt",
+    visit_mut_while_stmt(WhileStmt) => "WhileStmt",
+    visit_mut_do_while_stmt(DoWhileStmt) => "DoWhileStmt",
+    visit_mut_switch_stmt(SwitchStmt) => "SwitchStmt",
+    visit_mut_try_stmt(TryStmt) => "TryStmt",
+    visit_mut_throw_stmt(ThrowStmt) => "ThrowStmt",
+    visit_mut_return_stmt(ReturnStmt) => "ReturnStmt",
+    visit_mut_debugger_stmt(DebuggerStmt) => "DebuggerStmt",
+    visit_mut_import_decl(ImportDecl) => "ImportDecl",
+    visit_mut_export_decl(ExportDecl) => "ExportDecl",
+    visit_mut_named_export(NamedExport) => "NamedExport",
+    visit_mut_export_default_decl(ExportDefaultDecl) => "ExportDefaultDecl",
+    visit_mut_export_default_expr(ExportDefaultExpr) => "ExportDefaultExpr",
+}
+
+/// Counts arbitrary node kinds of the JavaScript source code, so a new metric does not
+/// need a new field on [`ASTStatistics`].
+///
+/// Kinds are named after the SWC node types, e.g. `"ArrowExpr"` or `"AwaitExpr"`; see
+/// [`COUNTABLE_NODE_KINDS`] for the supported names.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `kinds`: The node kind names to count.
+///
+/// # Returns
+/// A `Result` with a map of every requested kind to its count, or an error message if
+/// parsing fails or a kind is not supported.
+pub fn count_node_kinds_from_ast(
+    file_content: &str,
+    kinds: Vec<String>,
+) -> Result<HashMap<String, usize>, String> {
+    let unsupported: Vec<&str> = kinds
+        .iter()
+        .map(|kind| kind.as_str())
+        .filter(|kind| !COUNTABLE_NODE_KINDS.contains(kind))
+        .collect();
+
+    if !unsupported.is_empty() {
+        return Err(format!(
+            "Unsupported node kinds: {}. Supported kinds are: {}",
+            unsupported.join(", "),
+            COUNTABLE_NODE_KINDS.join(", ")
+        ));
+    }
+
+    let (mut module, _comments, _cm) = parse(file_content)?;
+
+    let mut counter = NodeKindCounter {
+        counts: kinds.into_iter().map(|kind| (kind, 0)).collect(),
+    };
+    module.visit_mut_with(&mut counter);
+
+    Ok(counter.counts)
+}
+
+// ###################################################################################
+// ################### (▰˘◡˘▰) Work with AST Var and Object (▰˘◡˘▰) ##################
+// ###################################################################################
+struct ObjectExtender {
+    target_var_name: String,
+    /// Keys of the nested object to extend, the variable's own object when empty.
+    path: Vec<String>,
+    new_properties: Vec<PropOrSpread>,
+    operation: Operation,
+    find: FindCondition,
+}
+
+impl Default for ObjectExtender {
+    fn default() -> Self {
+        Self {
+            target_var_name: "".to_string(),
+            path: Vec::new(),
+            new_properties: Vec::new(),
+            operation: Operation::Edit,
+            find: FindCondition::NotFound("".to_string()),
+        }
+    }
+}
+
+/// Skips the parentheses around an expression, e.g. `const Components = ({});`.
+fn unwrap_parens_mut(expr: &mut Expr) -> &mut Expr {
+    match expr {
+        Expr::Paren(paren) => unwrap_parens_mut(&mut paren.expr),
+        expr => expr,
+    }
+}
+
+/// Builds the property added for an object name: `...Name` becomes a spread element,
+/// anything else a shorthand property.
+fn object_property(name: &str) -> PropOrSpread {
+    match name.strip_prefix("...") {
+        Some(spread_name) => PropOrSpread::Spread(SpreadElement {
+            dot3_token: DUMMY_SP,
+            expr: Box::new(Expr::Ident(Ident::new(
+                spread_name.into(),
+                DUMMY_SP,
+                SyntaxContext::empty(),
+            ))),
+        }),
+        None => PropOrSpread::Prop(Box::new(Prop::Shorthand(Ident::new(
+            name.into(),
+            DUMMY_SP,
+            SyntaxContext::empty(),
+        )))),
+    }
+}
+
+/// Whether `key` can be written as a bare identifier, e.g. `timeout` but not `data-id` or
+/// `2x`.
+fn is_identifier_key(key: &str) -> bool {
+    let mut chars = key.chars();
+    chars
+        .next()
+        .is_some_and(|first| first.is_alphabetic() || first == '_' || first == '$')
+        && chars.all(|c| c.is_alphanumeric() || c == '_' || c == '$')
+}
+
+/// Builds the key of a `key: value` property, quoting keys that are not identifiers.
+fn property_name(key: &str) -> PropName {
+    if is_identifier_key(key) {
+        PropName::Ident(IdentName::new(key.into(), DUMMY_SP))
+    } else {
+        PropName::Str(Str {
+            span: DUMMY_SP,
+            value: key.into(),
+            raw: None,
+        })
+    }
+}
+
+/// The key used to detect duplicated properties: the name of shorthand, identifier and
+/// string keyed properties, and `...Name` for spread identifiers.
+fn object_property_key(prop: &PropOrSpread) -> Option<String> {
+    match prop {
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::Shorthand(ident) => Some(ident.sym.to_string()),
+            Prop::KeyValue(KeyValueProp { key, .. }) => match key {
+                PropName::Ident(ident) => Some(ident.sym.to_string()),
+                PropName::Str(string) => Some(string.value.to_string()),
+                _ => None,
+            },
+            _ => None,
+        },
+        PropOrSpread::Spread(spread) => match &*spread.expr {
+            Expr::Ident(ident) => Some(format!("...{}", ident.sym)),
+            _ => None,
+        },
+    }
+}
+
+/// Walks `path` through nested object properties, e.g. `["params", "user"]` for
+/// `{ params: { user: {} } }`, creating the missing objects. Returns `None` when a key on the
+/// path holds something other than an object literal.
+fn object_at_path_mut<'o>(object: &'o mut ObjectLit, path: &[String]) -> Option<&'o mut ObjectLit> {
+    let Some((key, rest)) = path.split_first() else {
+        return Some(object);
+    };
+
+    let position = object
+        .props
+        .iter()
+        .position(|prop| object_property_key(prop).as_deref() == Some(key.as_str()));
+
+    let position = match position {
+        Some(position) => position,
+        None => {
+            object
+                .props
+                .push(PropOrSpread::Prop(Box::new(Prop::KeyValue(KeyValueProp {
+                    key: property_name(key),
+                    value: Box::new(Expr::Object(ObjectLit {
+                        span: DUMMY_SP,
+                        props: Vec::new(),
+                    })),
+                }))));
+            object.props.len() - 1
+        }
+    };
+
+    match &mut object.props[position] {
+        PropOrSpread::Prop(prop) => match &mut **prop {
+            Prop::KeyValue(KeyValueProp { value, .. }) => match unwrap_parens_mut(value) {
+                Expr::Object(nested) => object_at_path_mut(nested, rest),
+                _ => None,
+            },
+            _ => None,
+        },
+        PropOrSpread::Spread(_) => None,
+    }
+}
+
+impl VisitMut for ObjectExtender {
+    fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
+        if matches!(self.operation, Operation::Edit) {
+            for decl in &mut var_decl.decls {
+                if let Some(ident) = decl.name.as_ident() {
+                    if ident.sym == self.target_var_name {
+                        if let Some(init) = &mut decl.init {
+                            // A shadowing declaration later in the file must not turn an
+                            // object that was already extended into an error.
+                            if self.find != FindCondition::Found {
+                                self.find = FindCondition::FoundError("".to_string());
+                            }
+                            let obj_expr = match unwrap_parens_mut(init) {
+                                Expr::Object(obj_expr) => object_at_path_mut(obj_expr, &self.path),
+                                _ => None,
+                            };
+                            if let Some(obj_expr) = obj_expr {
+                                if matches!(self.operation, Operation::Edit) {
+                                    self.find = FindCondition::Found;
+                                    let existing_keys: Vec<String> = obj_expr
+                                        .props
+                                        .iter()
+                                        .filter_map(object_property_key)
+                                        .collect();
+
+                                    let new_props: Vec<PropOrSpread> = self
+                                        .new_properties
+                                        .clone()
+                                        .into_iter()
+                                        .filter(|prop| match object_property_key(prop) {
+                                            Some(key) => !existing_keys.contains(&key),
+                                            None => true,
+                                        })
+                                        .collect();
+
+                                    obj_expr.props.extend(new_props);
+                                }
+                            }
+                        }
+                    }
+                }
+            }
+        }
+        var_decl.visit_mut_children_with(self)
+    }
+}
+
+pub fn extend_var_object_property_by_names_to_ast<'a>(
+    file_content: &str,
+    var_name: &str,
+    object_names: impl IntoIterator<Item = &'a str> + Clone,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let new_properties: Vec<PropOrSpread> = object_names.into_iter().map(object_property).collect();
+
+    let mut object_extender = ObjectExtender {
+        target_var_name: var_name.to_string(),
+        new_properties,
+        operation: Operation::Edit,
+        ..Default::default()
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut object_extender);
+    if object_extender.find == FindCondition::Found {
+        result
+    } else {
+        Err(object_extender.find.message().to_string())
+    }
+}
+
+/// Extends an object variable like [`extend_var_object_property_by_names_to_ast`] but also
+/// hands back code when the variable is not found, e.g. for a preview that shows the file
+/// either way.
+///
+/// # Returns
+/// A `Result` containing the [`FindCondition`] of the variable with the updated code when
+/// it is [`FindCondition::Found`], or the original `file_content` untouched otherwise. Only
+/// a parse failure is an error.
+pub fn extend_var_object_property_by_names_to_ast_with_status<'a>(
+    file_content: &str,
+    var_name: &str,
+    object_names: impl IntoIterator<Item = &'a str>,
+) -> Result<(FindCondition, String), String> {
+    ensure_source_not_empty(file_content)?;
+    let mut object_extender = ObjectExtender {
+        target_var_name: var_name.to_string(),
+        new_properties: object_names.into_iter().map(object_property).collect(),
+        operation: Operation::Edit,
+        ..Default::default()
+    };
+
+    let code = code_gen_from_ast_vist(file_content, &mut object_extender)?;
+    if object_extender.find == FindCondition::Found {
+        Ok((FindCondition::Found, code))
+    } else {
+        Ok((object_extender.find, file_content.to_string()))
+    }
+}
+
+/// Extends a nested object of a variable like [`extend_var_object_property_by_names_to_ast`],
+/// e.g. `params` of `const config = { params: {} }` for the path `["params"]`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `var_name`: The name of the variable holding the object.
+/// - `path`: The keys leading from the variable's object to the object to extend. Missing
+///   objects on the path are created.
+/// - `object_names`: The property names to add, `...Name` adds a spread.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if the variable is not an
+/// object or a key on the path holds something other than an object literal.
+pub fn extend_object_at_path_ast<'a>(
+    file_content: &str,
+    var_name: &str,
+    path: Vec<String>,
+    object_names: impl IntoIterator<Item = &'a str>,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut object_extender = ObjectExtender {
+        target_var_name: var_name.to_string(),
+        path,
+        new_properties: object_names.into_iter().map(object_property).collect(),
+        operation: Operation::Edit,
+        ..Default::default()
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut object_extender);
+    if object_extender.find == FindCondition::Found {
+        result
+    } else {
+        Err(object_extender.find.message().to_string())
+    }
+}
+
+/// Extends an object variable like [`extend_var_object_property_by_names_to_ast`], also
+/// accepting `key: value` properties.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `var_name`: The name of the variable holding the object.
+/// - `properties`: Pairs of a property key and the source of its value expression, e.g.
+///   `("timeout", Some("3000"))`. A `None` value adds the key as a shorthand property. Keys
+///   that are not identifiers, like `data-id`, are quoted.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if a value expression does
+/// not parse, a shorthand key is not an identifier or the variable is not an object. Keys
+/// that already exist in the object, quoted or not, are skipped.
+pub fn extend_var_object_properties_to_ast(
+    file_content: &str,
+    var_name: &str,
+    properties: Vec<(String, Option<String>)>,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut new_properties = vec![];
+    for (key, value) in properties {
+        let prop = match value {
+            Some(value) => Prop::KeyValue(KeyValueProp {
+                key: property_name(&key),
+                value: parse_expression(&value)?,
+            }),
+            None if is_identifier_key(&key) => {
+                Prop::Shorthand(Ident::new(key.into(), DUMMY_SP, SyntaxContext::empty()))
+            }
+            None => return Err(format!("`{key}` cannot be added as a shorthand property")),
+        };
+        new_properties.push(PropOrSpread::Prop(Box::new(prop)));
+    }
+
+    let mut object_extender = ObjectExtender {
+        target_var_name: var_name.to_string(),
+        new_properties,
+        operation: Operation::Edit,
+        ..Default::default()
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut object_extender);
+    if object_extender.find == FindCondition::Found {
+        result
+    } else {
+        Err(object_extender.find.message().to_string())
+    }
+}
+
+/// Outcome of [`extend_var_objects_batch_ast`].
+#[derive(Debug)]
+pub struct ExtendVarObjectsBatch {
+    pub code: String,
+    /// Variables that were found as object literals and extended.
+    pub extended: Vec<String>,
+    /// Variables that were not found, or are not initialized with an object literal.
+    pub failed: Vec<String>,
+}
+
+/// Applies several object extensions of [`extend_var_object_property_by_names_to_ast`] in a
+/// single parse and emit cycle.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `extensions`: Pairs of a variable name and the property names to add to it.
+///
+/// # Returns
+/// A `Result` containing the updated code and which variables were extended or not, or an
+/// error message if parsing fails. Variables that failed are left untouched.
+pub fn extend_var_objects_batch_ast(
+    file_content: &str,
+    extensions: Vec<(String, Vec<String>)>,
+) -> Result<ExtendVarObjectsBatch, String> {
+    ensure_source_not_empty(file_content)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    let mut extended = vec![];
+    let mut failed = vec![];
+    for (var_name, object_names) in extensions {
+        let mut object_extender = ObjectExtender {
+            target_var_name: var_name.clone(),
+            new_properties: object_names
+                .iter()
+                .map(|name| object_property(name))
+                .collect(),
+            operation: Operation::Edit,
+            ..Default::default()
+        };
+
+        module.visit_mut_with(&mut object_extender);
+
+        if object_extender.find == FindCondition::Found {
+            extended.push(var_name);
+        } else {
+            failed.push(var_name);
+        }
+    }
+
+    let code = code_gen_from_ast_module(&mut module, comments, cm)?;
+
+    Ok(ExtendVarObjectsBatch {
+        code,
+        extended,
+        failed,
+    })
+}
+
+pub fn contains_variable_from_ast(file_content: &str, variable_name: &str) -> Result<bool, bool> {
+    let (module, _, _) = parse(file_content).map_err(|_| false)?;
+
+    for item in &module.body {
+        if let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = item {
+            for decl in &var_decl.decls {
+                if let Pat::Ident(BindingIdent { id, .. }) = &decl.name {
+                    if id.sym == variable_name {
+                        return Ok(true);
+                    }
+                }
+            }
+        }
+    }
+    Err(false)
+}
+
+// ###################################################################################
+// ####################### (▰˘◡˘▰) Work with AST Codegen (▰˘◡˘▰) #####################
+// ###################################################################################
+
+/// Re-emits JavaScript source code in its minified form.
+///
+/// The source is parsed and emitted again with the codegen `minify` option, which drops
+/// every formatting whitespace and newline. Comments are not emitted.
+///
+/// # Arguments
+/// - `source_code`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the minified code, or an error message if parsing fails.
+pub fn minify_js(source_code: &str) -> Result<String, String> {
+    let (mut module, _comments, cm) = parse(source_code)?;
+
+    let options = EmitOptions {
+        minify: true,
+        ..Default::default()
+    };
+
+    code_gen_from_ast_module_with_options(
+        &mut module,
+        Default::default(),
+        cm,
+        source_code,
+        &options,
+    )
+}
+
+/// Re-emits JavaScript source code without any of its comments.
+///
+/// The module is emitted with an empty comment store instead of the comments captured
+/// while parsing, so leading, trailing and inline comments are all dropped. The formatting
+/// of the code is the same as the other editing functions.
+///
+/// # Arguments
+/// - `source_code`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the code without comments, or an error message if parsing fails.
+pub fn strip_comments_js(source_code: &str) -> Result<String, String> {
+    let (mut module, _comments, cm) = parse(source_code)?;
+
+    code_gen_from_ast_module(&mut module, Default::default(), cm)
+}
+
+/// 64-bit FNV-1a, used instead of `DefaultHasher` because its output is stable across
+/// Rust releases, so a stored fingerprint stays comparable after an upgrade.
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    bytes.iter().fold(0xcbf29ce484222325, |hash, byte| {
+        (hash ^ u64::from(*byte)).wrapping_mul(0x100000001b3)
+    })
+}
+
+/// Computes a fingerprint of the code of a JavaScript file, to detect real changes
+/// independently of its formatting.
+///
+/// The module is re-emitted in its canonical form (minified, see [`minify_js`], which also
+/// drops the comments) and that form is hashed, so two files that only differ in
+/// whitespace, newlines or comments get the same fingerprint.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the fingerprint as 16 lowercase hex digits, or an error message
+/// if parsing fails.
+pub fn ast_fingerprint(file_content: &str) -> Result<String, String> {
+    let canonical = minify_js(file_content)?;
+
+    Ok(format!("{:016x}", fnv1a_64(canonical.as_bytes())))
+}
+
+// ###################################################################################
+// ####################### (▰˘◡˘▰) Work with AST Exports (▰˘◡˘▰) #####################
+// ###################################################################################
+
+fn module_export_name(name: &ModuleExportName) -> String {
+    match name {
+        ModuleExportName::Ident(ident) => ident.sym.to_string(),
+        ModuleExportName::Str(str) => str.value.to_string(),
+    }
+}
+
+/// Collects every name a module exports; default exports are reported as `"default"`.
+pub fn exported_names(module: &Module) -> Vec<String> {
+    let mut names = vec![];
+
+    for item in &module.body {
+        if let ModuleItem::ModuleDecl(module_decl) = item {
+            match module_decl {
+                ModuleDecl::ExportDecl(export_decl) => match &export_decl.decl {
+                    Decl::Fn(fn_decl) => names.push(fn_decl.ident.sym.to_string()),
+                    Decl::Class(class_decl) => names.push(class_decl.ident.sym.to_string()),
+                    Decl::Var(var_decl) => {
+                        for decl in &var_decl.decls {
+                            if let Pat::Ident(BindingIdent { id, .. }) = &decl.name {
+                                names.push(id.sym.to_string());
+                            }
+                        }
+                    }
+                    _ => {}
+                },
+                ModuleDecl::ExportNamed(named_export) => {
+                    for specifier in &named_export.specifiers {
+                        match specifier {
+                            ExportSpecifier::Named(named) => names.push(module_export_name(
+                                named.exported.as_ref().unwrap_or(&named.orig),
+                            )),
+                            ExportSpecifier::Default(default) => {
+                                names.push(default.exported.sym.to_string())
+                            }
+                            ExportSpecifier::Namespace(namespace) => {
+                                names.push(module_export_name(&namespace.name))
+                            }
+                        }
+                    }
+                }
+                ModuleDecl::ExportDefaultDecl(_) | ModuleDecl::ExportDefaultExpr(_) => {
+                    names.push("default".to_string())
+                }
+                _ => {}
+            }
+        }
+    }
+
+    names
+}
+
+/// Checks if the JavaScript source code exports the given name.
+///
+/// Inline declarations (`export const name`, `export function name`, `export class name`),
+/// export lists (`export { name }`, `export { other as name }`) and re-exports are
+/// considered. Pass `"default"` to check for a default export.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `name`: The exported name to look for.
+///
+/// # Returns
+/// `Ok(true)` if the name is exported, otherwise `Err(false)`, also when parsing fails.
+pub fn contains_export_from_ast(file_content: &str, name: &str) -> Result<bool, bool> {
+    let (module, _, _) = parse(file_content).map_err(|_| false)?;
+
+    if exported_names(&module)
+        .iter()
+        .any(|exported| exported == name)
+    {
+        Ok(true)
+    } else {
+        Err(false)
+    }
+}
+
+/// The documentation of an exported function or class, see [`extract_jsdoc_from_ast`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct JsDoc {
+    /// The exported name, `"default"` for an anonymous default export.
+    pub name: String,
+    /// The text of the `/** ... */` comment right before the export, without its
+    /// delimiters and the leading ` * ` of every line, or `None` when there is none.
+    pub doc: Option<String>,
+}
+
+/// The text of a `/** ... */` comment, `None` for other comments.
+fn jsdoc_text(comment: &Comment) -> Option<String> {
+    if comment.kind != CommentKind::Block {
+        return None;
+    }
+    let text = comment.text.strip_prefix('*')?;
+
+    let lines: Vec<&str> = text
+        .lines()
+        .map(|line| {
+            let line = line.trim_start();
+            let line = line.strip_prefix('*').unwrap_or(line);
+            line.strip_prefix(' ').unwrap_or(line).trim_end()
+        })
+        .collect();
+
+    Some(lines.join("\n").trim_matches('\n').to_string())
+}
+
+/// Lists the exported functions and classes with their JSDoc, e.g. to generate API docs.
+///
+/// A JSDoc is the `/** ... */` block comment placed right before the `export` keyword; a
+/// line comment or a plain `/* */` comment in between means the export is undocumented.
+/// Exported variables and export lists are not included.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing every exported function and class in source order, or an error
+/// message if parsing fails.
+pub fn extract_jsdoc_from_ast(file_content: &str) -> Result<Vec<JsDoc>, String> {
+    let (module, comments, _cm) = parse(file_content)?;
+
+    let docs = module
+        .body
+        .iter()
+        .filter_map(|item| {
+            let (span, name) = match item {
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => {
+                    match &export_decl.decl {
+                        Decl::Fn(fn_decl) => (export_decl.span, fn_decl.ident.sym.to_string()),
+                        Decl::Class(class_decl) => {
+                            (export_decl.span, class_decl.ident.sym.to_string())
+                        }
+                        _ => return None,
+                    }
+                }
+                ModuleItem::ModuleDecl(ModuleDecl::ExportDefaultDecl(export_default)) => {
+                    let ident = match &export_default.decl {
+                        DefaultDecl::Fn(fn_expr) => fn_expr.ident.as_ref(),
+                        DefaultDecl::Class(class_expr) => class_expr.ident.as_ref(),
+                        _ => return None,
+                    };
+                    let name = ident.map_or("default".to_string(), |ident| ident.sym.to_string());
+                    (export_default.span, name)
+                }
+                _ => return None,
+            };
+
+            let doc = comments
+                .get_leading(span.lo)
+                .and_then(|leading| leading.last().and_then(jsdoc_text));
+
+            Some(JsDoc { name, doc })
+        })
+        .collect();
+
+    Ok(docs)
+}
+
+/// Name given to an anonymous default export when the caller does not pick one.
+const DEFAULT_EXPORT_NAME: &str = "defaultExport";
+
+/// `export { name };`
+fn export_named(span: Span, ident: Ident) -> ModuleDecl {
+    ModuleDecl::ExportNamed(NamedExport {
+        span,
+        specifiers: vec![ExportSpecifier::Named(ExportNamedSpecifier {
+            span: DUMMY_SP,
+            orig: ModuleExportName::Ident(ident),
+            exported: None,
+            is_type_only: false,
+        })],
+        src: None,
+        type_only: false,
+        with: None,
+    })
+}
+
+/// Turns a default export into a named export, e.g. `export default Components;` into
+/// `export { Components };`, `export default function Foo() {}` into
+/// `export function Foo() {}` and `export { Foo as default };` into `export { Foo };`.
+///
+/// An anonymous default export is given `name`: `export default function () {}` becomes
+/// `export function name() {}`, and an expression such as `export default { a: 1 };`
+/// becomes `const name = { a: 1 };` followed by `export { name };`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `name`: The name of an anonymous default export, `defaultExport` when `None`.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if parsing fails or there
+/// is no default export.
+pub fn convert_default_export_to_named_ast(
+    file_content: &str,
+    name: Option<&str>,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+    let name = ident(name.unwrap_or(DEFAULT_EXPORT_NAME));
+
+    let mut converted = false;
+    let mut body = Vec::with_capacity(module.body.len() + 1);
+    for mut item in module.body.drain(..) {
+        let ModuleItem::ModuleDecl(module_decl) = &mut item else {
+            body.push(item);
+            continue;
+        };
+
+        match module_decl {
+            ModuleDecl::ExportDefaultExpr(export) => match &*export.expr {
+                Expr::Ident(ident) => {
+                    body.push(ModuleItem::ModuleDecl(export_named(
+                        export.span,
+                        ident.clone(),
+                    )));
+                }
+                _ => {
+                    body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(Box::new(VarDecl {
+                        span: export.span,
+                        ctxt: SyntaxContext::empty(),
+                        kind: VarDeclKind::Const,
+                        declare: false,
+                        decls: vec![VarDeclarator {
+                            span: DUMMY_SP,
+                            name: Pat::Ident(BindingIdent {
+                                id: name.clone(),
+                                type_ann: None,
+                            }),
+                            init: Some(export.expr.clone()),
+                            definite: false,
+                        }],
+                    })))));
+                    body.push(ModuleItem::ModuleDecl(export_named(DUMMY_SP, name.clone())));
+                }
+            },
+            ModuleDecl::ExportDefaultDecl(export) => {
+                let decl = match &export.decl {
+                    DefaultDecl::Fn(FnExpr { ident, function }) => Decl::Fn(FnDecl {
+                        ident: ident.clone().unwrap_or_else(|| name.clone()),
+                        declare: false,
+                        function: function.clone(),
+                    }),
+                    DefaultDecl::Class(ClassExpr { ident, class }) => Decl::Class(ClassDecl {
+                        ident: ident.clone().unwrap_or_else(|| name.clone()),
+                        declare: false,
+                        class: class.clone(),
+                    }),
+                    DefaultDecl::TsInterfaceDecl(_) => {
+                        body.push(item);
+                        continue;
+                    }
+                };
+
+                body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span: export.span,
+                    decl,
+                })));
+            }
+            ModuleDecl::ExportNamed(named_export) if named_export.src.is_none() => {
+                for specifier in &mut named_export.specifiers {
+                    if let ExportSpecifier::Named(named) = specifier {
+                        if named.exported.as_ref().map(module_export_name).as_deref()
+                            == Some("default")
+                        {
+                            named.exported = None;
+                            converted = true;
+                        }
+                    }
+                }
+                body.push(item);
+                continue;
+            }
+            _ => {
+                body.push(item);
+                continue;
+            }
+        }
+
+        converted = true;
+    }
+    module.body = body;
+
+    if !converted {
+        return Err("No default export found".to_string());
+    }
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+/// Stops exporting `name`: it is removed from `export { ... }` lists (dropping a list that
+/// ends up empty) and the `export` keyword is removed from an inline declaration such as
+/// `export const name = 1;` or `export function name() {}`, which stays in place. A name
+/// declared next to others, e.g. `export const a = 1, name = 2, b = 3;`, gets its own
+/// declaration between the exported ones, in source order. When `name` is bound by a
+/// destructuring pattern, e.g. `export const { a, name } = obj;`, the declaration loses its
+/// `export` and the other names of the pattern are exported with `export { a };`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `name`: The exported name to remove.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if parsing fails or the
+/// name is not exported.
+pub fn remove_export_from_ast(file_content: &str, name: &str) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    let mut removed = false;
+    let mut body = Vec::with_capacity(module.body.len());
+    for item in module.body.drain(..) {
+        match item {
+            ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(mut named_export)) => {
+                let before = named_export.specifiers.len();
+                named_export.specifiers.retain(|specifier| match specifier {
+                    ExportSpecifier::Named(named) => {
+                        module_export_name(named.exported.as_ref().unwrap_or(&named.orig)) != name
+                    }
+                    ExportSpecifier::Default(default) => default.exported.sym != name,
+                    ExportSpecifier::Namespace(namespace) => {
+                        module_export_name(&namespace.name) != name
+                    }
+                });
+
+                if named_export.specifiers.len() == before {
+                    body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        named_export,
+                    )));
+                    continue;
+                }
+
+                removed = true;
+                if !named_export.specifiers.is_empty() {
+                    body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                        named_export,
+                    )));
+                }
+            }
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export_decl)) => match export_decl.decl {
+                Decl::Fn(fn_decl) if fn_decl.ident.sym == name => {
+                    removed = true;
+                    body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))));
+                }
+                Decl::Class(class_decl) if class_decl.ident.sym == name => {
+                    removed = true;
+                    body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Class(class_decl))));
+                }
+                Decl::Var(mut var_decl) if var_decl_binds(&var_decl, name) => {
+                    removed = true;
+                    // Split in source order, so a declarator still sees the ones before it
+                    let mut before = std::mem::take(&mut var_decl.decls);
+                    let index = before
+                        .iter()
+                        .position(|decl| pat_binds(&decl.name, name))
+                        .unwrap_or_default();
+                    let after = before.split_off(index + 1);
+                    let local = before.pop();
+                    let with_decls = |decls: Vec<VarDeclarator>| {
+                        Box::new(VarDecl {
+                            decls,
+                            ..(*var_decl).clone()
+                        })
+                    };
+
+                    if !before.is_empty() {
+                        body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span: export_decl.span,
+                            decl: Decl::Var(with_decls(before)),
+                        })));
+                    }
+
+                    if let Some(local) = local {
+                        // The other names of a destructuring pattern stay exported
+                        let mut others = vec![];
+                        pat_bound_names(&local.name, &mut others);
+                        others.retain(|ident| ident.sym != name);
+
+                        body.push(ModuleItem::Stmt(Stmt::Decl(Decl::Var(with_decls(vec![
+                            local,
+                        ])))));
+
+                        if !others.is_empty() {
+                            body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportNamed(
+                                NamedExport {
+                                    span: DUMMY_SP,
+                                    specifiers: others
+                                        .into_iter()
+                                        .map(|ident| {
+                                            ExportSpecifier::Named(ExportNamedSpecifier {
+                                                span: DUMMY_SP,
+                                                orig: ModuleExportName::Ident(ident),
+                                                exported: None,
+                                                is_type_only: false,
+                                            })
+                                        })
+                                        .collect(),
+                                    src: None,
+                                    type_only: false,
+                                    with: None,
+                                },
+                            )));
+                        }
+                    }
+
+                    if !after.is_empty() {
+                        body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                            span: DUMMY_SP,
+                            decl: Decl::Var(with_decls(after)),
+                        })));
+                    }
+                }
+                decl => body.push(ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                    span: export_decl.span,
+                    decl,
+                }))),
+            },
+            item => body.push(item),
+        }
+    }
+    module.body = body;
+
+    if !removed {
+        return Err(format!("No export named `{}` found", name));
+    }
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+// ###################################################################################
+// ###################### (▰˘◡˘▰) Work with AST Functions (▰˘◡˘▰) ####################
+// ###################################################################################
+
+fn ident(name: &str) -> Ident {
+    Ident::new(name.into(), DUMMY_SP, SyntaxContext::empty())
+}
+
+/// Builds `try { <stmts> } catch (e) { console.error(e) }`.
+fn try_catch_stmt(stmts: Vec<Stmt>) -> Stmt {
+    let console_error = Stmt::Expr(ExprStmt {
+        span: DUMMY_SP,
+        expr: Box::new(Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(ident("console"))),
+                prop: MemberProp::Ident(IdentName::new("error".into(), DUMMY_SP)),
+            }))),
+            args: vec![ExprOrSpread {
+                spread: None,
+                expr: Box::new(Expr::Ident(ident("e"))),
+            }],
+            type_args: None,
+        })),
+    });
+
+    Stmt::Try(Box::new(TryStmt {
+        span: DUMMY_SP,
+        block: BlockStmt {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            stmts,
+        },
+        handler: Some(CatchClause {
+            span: DUMMY_SP,
+            param: Some(Pat::Ident(BindingIdent {
+                id: ident("e"),
+                type_ann: None,
+            })),
+            body: BlockStmt {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                stmts: vec![console_error],
+            },
+        }),
+        finalizer: None,
+    }))
+}
+
+struct FunctionTryWrapper {
+    target_fn_name: String,
+    find: FindCondition,
+}
+
+impl FunctionTryWrapper {
+    fn wrap(&mut self, fn_decl: &mut FnDecl) {
+        if fn_decl.ident.sym != self.target_fn_name {
+            return;
+        }
+
+        match &mut fn_decl.function.body {
+            Some(body) => {
+                let stmts = std::mem::take(&mut body.stmts);
+                body.stmts = vec![try_catch_stmt(stmts)];
+                self.find = FindCondition::Found;
+            }
+            None => self.find = FindCondition::FoundError("".to_string()),
+        }
+    }
+}
+
+impl VisitMut for FunctionTryWrapper {
+    // Only module level declarations are targeted, so nested functions are not visited.
+    fn visit_mut_module_item(&mut self, item: &mut ModuleItem) {
+        match item {
+            ModuleItem::Stmt(Stmt::Decl(Decl::Fn(fn_decl))) => self.wrap(fn_decl),
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl {
+                decl: Decl::Fn(fn_decl),
+                ..
+            })) => self.wrap(fn_decl),
+            _ => {}
+        }
+    }
+}
+
+/// Wraps the body of a top-level function in `try { ... } catch (e) { console.error(e) }`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `target_fn_name`: The name of the function declaration to wrap.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if the function is not
+/// declared at module level.
+pub fn wrap_in_try_catch_ast(file_content: &str, target_fn_name: &str) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut wrapper = FunctionTryWrapper {
+        target_fn_name: target_fn_name.to_string(),
+        find: FindCondition::NotFound("".to_string()),
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut wrapper);
+    if wrapper.find == FindCondition::Found {
+        result
+    } else {
+        Err(wrapper.find.message().to_string())
+    }
+}
+
+/// Finds the top-level declaration of `fn_name`: a function declaration, or a variable
+/// declarator initialized with an arrow or function expression, alone in its declaration.
+fn find_function_declaration(module: &Module, fn_name: &str) -> Option<Decl> {
+    module.body.iter().find_map(|item| {
+        let decl = match item {
+            ModuleItem::Stmt(Stmt::Decl(decl)) => decl,
+            ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(export)) => &export.decl,
+            _ => return None,
+        };
+
+        match decl {
+            Decl::Fn(fn_decl) if fn_decl.ident.sym == fn_name => Some(decl.clone()),
+            Decl::Var(var_decl) => var_decl
+                .decls
+                .iter()
+                .find(|declarator| {
+                    declarator
+                        .name
+                        .as_ident()
+                        .is_some_and(|ident| ident.sym == fn_name)
+                        && matches!(
+                            declarator.init.as_deref(),
+                            Some(Expr::Arrow(_) | Expr::Fn(_))
+                        )
+                })
+                .map(|declarator| {
+                    Decl::Var(Box::new(VarDecl {
+                        decls: vec![declarator.clone()],
+                        ..*var_decl.clone()
+                    }))
+                }),
+            _ => None,
+        }
+    })
+}
+
+/// Returns the source of a top-level function, e.g. for documentation tooling.
+///
+/// The declaration is emitted on its own with the codegen, so it has the formatting of the
+/// other editing functions and keeps its leading comments.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `fn_name`: The function name. A `const` (or `let`/`var`) initialized with an arrow or
+///   function expression is returned as its variable declaration, e.g. `const f = () => {};`.
+///
+/// # Returns
+/// A `Result` containing the declaration source, or an error message if parsing fails or
+/// no top-level function has that name.
+pub fn get_function_source_from_ast(file_content: &str, fn_name: &str) -> Result<String, String> {
+    let (module, comments, cm) = parse(file_content)?;
+
+    let decl = find_function_declaration(&module, fn_name)
+        .ok_or_else(|| format!("No top-level function named `{}` was found", fn_name))?;
+
+    let mut isolated = Module {
+        span: DUMMY_SP,
+        body: vec![ModuleItem::Stmt(Stmt::Decl(decl))],
+        shebang: None,
+    };
+    code_gen_from_ast_module(&mut isolated, comments, cm).map(|code| code.trim_end().to_string())
+}
+
+// ###################################################################################
+// ###################### (▰˘◡˘▰) Work with AST Debugger (▰˘◡˘▰) #####################
+// ###################################################################################
+
+#[derive(Default)]
+struct DebuggerRemover {
+    removed: usize,
+}
+
+impl VisitMut for DebuggerRemover {
+    fn visit_mut_module_items(&mut self, items: &mut Vec<ModuleItem>) {
+        let before = items.len();
+        items.retain(|item| !matches!(item, ModuleItem::Stmt(Stmt::Debugger(_))));
+        self.removed += before - items.len();
+
+        items.visit_mut_children_with(self);
+    }
+
+    fn visit_mut_stmts(&mut self, stmts: &mut Vec<Stmt>) {
+        let before = stmts.len();
+        stmts.retain(|stmt| !matches!(stmt, Stmt::Debugger(_)));
+        self.removed += before - stmts.len();
+
+        stmts.visit_mut_children_with(self);
+    }
+
+    // A debugger used as a single statement body (`if (x) debugger;`) can not be dropped
+    // from its parent, so it is replaced with an empty statement.
+    fn visit_mut_stmt(&mut self, stmt: &mut Stmt) {
+        if let Stmt::Debugger(debugger) = stmt {
+            *stmt = Stmt::Empty(EmptyStmt {
+                span: debugger.span,
+            });
+            self.removed += 1;
+        }
+
+        stmt.visit_mut_children_with(self);
+    }
+}
+
+/// Removes every `debugger;` statement from the JavaScript source code, including the ones
+/// nested inside functions, classes and blocks.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if parsing fails.
+pub fn remove_debugger_statements_ast(file_content: &str) -> Result<String, String> {
+    remove_debugger_statements_ast_with_count(file_content).map(|(code, _removed)| code)
+}
+
+/// Same as [`remove_debugger_statements_ast`], but also returns how many statements were
+/// removed.
+pub fn remove_debugger_statements_ast_with_count(
+    file_content: &str,
+) -> Result<(String, usize), String> {
+    let mut remover = DebuggerRemover::default();
+    let code = code_gen_from_ast_vist(file_content, &mut remover)?;
+
+    Ok((code, remover.removed))
+}
+
+// ###################################################################################
+// ###################### (▰˘◡˘▰) Work with AST Rename (▰˘◡˘▰) #######################
+// ###################################################################################
+
+/// Checks if a binding pattern (including destructuring) declares the given name.
+fn pat_binds(pat: &Pat, name: &str) -> bool {
+    match pat {
+        Pat::Ident(binding) => binding.id.sym == name,
+        Pat::Array(array) => array
+            .elems
+            .iter()
+            .flatten()
+            .any(|elem| pat_binds(elem, name)),
+        Pat::Object(object) => object.props.iter().any(|prop| match prop {
+            ObjectPatProp::KeyValue(key_value) => pat_binds(&key_value.value, name),
+            ObjectPatProp::Assign(assign) => assign.key.id.sym == name,
+            ObjectPatProp::Rest(rest) => pat_binds(&rest.arg, name),
+        }),
+        Pat::Rest(rest) => pat_binds(&rest.arg, name),
+        Pat::Assign(assign) => pat_binds(&assign.left, name),
+        _ => false,
+    }
+}
+
+/// Collects the names a binding pattern declares, in source order.
+fn pat_bound_names(pat: &Pat, names: &mut Vec<Ident>) {
+    match pat {
+        Pat::Ident(binding) => names.push(binding.id.clone()),
+        Pat::Array(array) => {
+            for elem in array.elems.iter().flatten() {
+                pat_bound_names(elem, names);
+            }
+        }
+        Pat::Object(object) => {
+            for prop in &object.props {
+                match prop {
+                    ObjectPatProp::KeyValue(key_value) => pat_bound_names(&key_value.value, names),
+                    ObjectPatProp::Assign(assign) => names.push(assign.key.id.clone()),
+                    ObjectPatProp::Rest(rest) => pat_bound_names(&rest.arg, names),
+                }
+            }
+        }
+        Pat::Rest(rest) => pat_bound_names(&rest.arg, names),
+        Pat::Assign(assign) => pat_bound_names(&assign.left, names),
+        _ => {}
+    }
+}
+
+/// Finds `var` declarations that are hoisted to the enclosing function scope.
+struct HoistedVarFinder<'a> {
+    name: &'a str,
+    found: bool,
+}
+
+impl Visit for HoistedVarFinder<'_> {
+    fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+        if var_decl.kind == VarDeclKind::Var
+            && var_decl
+                .decls
+                .iter()
+                .any(|decl| pat_binds(&decl.name, self.name))
+        {
+            self.found = true;
+        }
+        var_decl.visit_children_with(self)
+    }
+
+    fn visit_function(&mut self, _function: &Function) {}
+
+    fn visit_arrow_expr(&mut self, _arrow_expr: &ArrowExpr) {}
+
+    fn visit_class(&mut self, _class: &Class) {}
+}
+
+/// Checks if the statements of a block or function body declare the given name.
+fn scope_declares(stmts: &[Stmt], name: &str) -> bool {
+    let declared = stmts.iter().any(|stmt| match stmt {
+        Stmt::Decl(Decl::Fn(fn_decl)) => fn_decl.ident.sym == name,
+        Stmt::Decl(Decl::Class(class_decl)) => class_decl.ident.sym == name,
+        Stmt::Decl(Decl::Var(var_decl)) => var_decl
+            .decls
+            .iter()
+            .any(|decl| pat_binds(&decl.name, name)),
+        _ => false,
+    });
+
+    if declared {
+        return true;
+    }
+
+    let mut finder = HoistedVarFinder { name, found: false };
+    for stmt in stmts {
+        stmt.visit_with(&mut finder);
+    }
+    finder.found
+}
+
+fn var_decl_binds(var_decl: &VarDecl, name: &str) -> bool {
+    var_decl
+        .decls
+        .iter()
+        .any(|decl| pat_binds(&decl.name, name))
+}
+
+/// Renames a module level binding and its references.
+///
+/// Every nested scope that declares a binding with the old name (parameters, variables,
+/// functions, classes, catch parameters and loop heads) shadows the binding, so the
+/// visitor does not descend into it.
+struct BindingRenamer {
+    old_name: String,
+    new_name: String,
+    /// Only a function declaration counts as the module level binding.
+    functions_only: bool,
+    find: FindCondition,
+}
+
+impl VisitMut for BindingRenamer {
+    fn visit_mut_module(&mut self, module: &mut Module) {
+        let declared = module.body.iter().any(|item| {
+            let decl = match item {
+                ModuleItem::Stmt(Stmt::Decl(decl))
+                | ModuleItem::ModuleDecl(ModuleDecl::ExportDecl(ExportDecl { decl, .. })) => decl,
+                _ => return false,
+            };
+
+            match decl {
+                Decl::Fn(fn_decl) => fn_decl.ident.sym == self.old_name,
+                Decl::Class(class_decl) if !self.functions_only => {
+                    class_decl.ident.sym == self.old_name
+                }
+                Decl::Var(var_decl) if !self.functions_only => {
+                    var_decl_binds(var_decl, &self.old_name)
+                }
+                _ => false,
+            }
+        });
+
+        if declared {
+            self.find = FindCondition::Found;
+            module.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_function(&mut self, function: &mut Function) {
+        let shadowed = function
+            .params
+            .iter()
+            .any(|param| pat_binds(&param.pat, &self.old_name))
+            || function
+                .body
+                .as_ref()
+                .is_some_and(|body| scope_declares(&body.stmts, &self.old_name));
+
+        if !shadowed {
+            function.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_constructor(&mut self, constructor: &mut Constructor) {
+        let shadowed = constructor.params.iter().any(|param| match param {
+            ParamOrTsParamProp::Param(param) => pat_binds(&param.pat, &self.old_name),
+            ParamOrTsParamProp::TsParamProp(_) => false,
+        }) || constructor
+            .body
+            .as_ref()
+            .is_some_and(|body| scope_declares(&body.stmts, &self.old_name));
+
+        if !shadowed {
+            constructor.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_arrow_expr(&mut self, arrow_expr: &mut ArrowExpr) {
+        let shadowed = arrow_expr
+            .params
+            .iter()
+            .any(|pat| pat_binds(pat, &self.old_name))
+            || match &*arrow_expr.body {
+                BlockStmtOrExpr::BlockStmt(body) => scope_declares(&body.stmts, &self.old_name),
+                BlockStmtOrExpr::Expr(_) => false,
+            };
+
+        if !shadowed {
+            arrow_expr.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_fn_expr(&mut self, fn_expr: &mut FnExpr) {
+        if !fn_expr
+            .ident
+            .as_ref()
+            .is_some_and(|ident| ident.sym == self.old_name)
+        {
+            fn_expr.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_class_expr(&mut self, class_expr: &mut ClassExpr) {
+        if !class_expr
+            .ident
+            .as_ref()
+            .is_some_and(|ident| ident.sym == self.old_name)
+        {
+            class_expr.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_block_stmt(&mut self, block: &mut BlockStmt) {
+        if !scope_declares(&block.stmts, &self.old_name) {
+            block.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_catch_clause(&mut self, catch_clause: &mut CatchClause) {
+        if !catch_clause
+            .param
+            .as_ref()
+            .is_some_and(|param| pat_binds(param, &self.old_name))
+        {
+            catch_clause.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_for_stmt(&mut self, for_stmt: &mut ForStmt) {
+        let shadowed = match &for_stmt.init {
+            Some(VarDeclOrExpr::VarDecl(var_decl)) => var_decl_binds(var_decl, &self.old_name),
+            _ => false,
+        };
+
+        if !shadowed {
+            for_stmt.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_for_in_stmt(&mut self, for_in_stmt: &mut ForInStmt) {
+        let shadowed = match &for_in_stmt.left {
+            ForHead::VarDecl(var_decl) => var_decl_binds(var_decl, &self.old_name),
+            _ => false,
+        };
+
+        if !shadowed {
+            for_in_stmt.visit_mut_children_with(self)
+        }
+    }
+
+    fn visit_mut_for_of_stmt(&mut self, for_of_stmt: &mut ForOfStmt) {
+        let shadowed = match &for_of_stmt.left {
+            ForHead::VarDecl(var_decl) => var_decl_binds(var_decl, &self.old_name),
+            _ => false,
+        };
+
+        if !shadowed {
+            for_of_stmt.visit_mut_children_with(self)
+        }
+    }
+
+    // Labels share the identifier type but are not references.
+    fn visit_mut_labeled_stmt(&mut self, labeled_stmt: &mut LabeledStmt) {
+        labeled_stmt.body.visit_mut_with(self)
+    }
+
+    fn visit_mut_break_stmt(&mut self, _break_stmt: &mut BreakStmt) {}
+
+    fn visit_mut_continue_stmt(&mut self, _continue_stmt: &mut ContinueStmt) {}
+
+    // `{ oldName }` keeps its key: `{ oldName: newName }`.
+    fn visit_mut_prop(&mut self, prop: &mut Prop) {
+        if let Prop::Shorthand(ident) = prop {
+            if ident.sym == self.old_name {
+                let key_value = Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(IdentName::new(ident.sym.clone(), ident.span)),
+                    value: Box::new(Expr::Ident(Ident::new(
+                        self.new_name.as_str().into(),
+                        ident.span,
+                        ident.ctxt,
+                    ))),
+                });
+                *prop = key_value;
+                return;
+            }
+        }
+        prop.visit_mut_children_with(self)
+    }
+
+    // `const { oldName } = object` keeps the key: `const { oldName: newName } = object`.
+    fn visit_mut_object_pat_prop(&mut self, prop: &mut ObjectPatProp) {
+        if let ObjectPatProp::Assign(assign) = prop {
+            if assign.key.id.sym == self.old_name {
+                let binding = Pat::Ident(BindingIdent {
+                    id: Ident::new(
+                        self.new_name.as_str().into(),
+                        assign.key.id.span,
+                        assign.key.id.ctxt,
+                    ),
+                    type_ann: None,
+                });
+                let value = match assign.value.take() {
+                    Some(default) => Pat::Assign(AssignPat {
+                        span: assign.span,
+                        left: Box::new(binding),
+                        right: default,
+                    }),
+                    None => binding,
+                };
+                *prop = ObjectPatProp::KeyValue(KeyValuePatProp {
+                    key: PropName::Ident(IdentName::new(
+                        assign.key.id.sym.clone(),
+                        assign.key.id.span,
+                    )),
+                    value: Box::new(value),
+                });
+                return;
+            }
+        }
+        prop.visit_mut_children_with(self)
+    }
+
+    // `export { oldName }` keeps the public name: `export { newName as oldName }`.
+    // Re-exports from another module do not reference the local function.
+    fn visit_mut_named_export(&mut self, named_export: &mut NamedExport) {
+        if named_export.src.is_some() {
+            return;
+        }
+
+        for specifier in &mut named_export.specifiers {
+            if let ExportSpecifier::Named(named) = specifier {
+                if let ModuleExportName::Ident(orig) = &named.orig {
+                    if orig.sym == self.old_name && named.exported.is_none() {
+                        named.exported = Some(ModuleExportName::Ident(orig.clone()));
+                    }
+                }
+            }
+        }
+        named_export.visit_mut_children_with(self)
+    }
+
+    fn visit_mut_ident(&mut self, ident: &mut Ident) {
+        if ident.sym == self.old_name {
+            ident.sym = self.new_name.as_str().into();
+        }
+    }
+}
+
+/// Renames a top-level function declaration and updates its references in the module.
+///
+/// References inside nested scopes that redeclare `old_name` point to that local binding
+/// and are left untouched.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `old_name`: The current name of the function.
+/// - `new_name`: The new name of the function.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if no function named
+/// `old_name` is declared at module level.
+pub fn rename_function_ast(
+    file_content: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut renamer = BindingRenamer {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        functions_only: true,
+        find: FindCondition::NotFound("".to_string()),
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut renamer);
+    if renamer.find == FindCondition::Found {
+        result
+    } else {
+        Err(renamer.find.message().to_string())
+    }
+}
+
+/// Renames a top-level binding declared with `const`, `let`, `var`, `function` or `class`
+/// and updates its references in the module, like [`rename_function_ast`] does for
+/// functions.
+///
+/// References inside nested scopes that redeclare `old_name` point to that local binding
+/// and are left untouched.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `old_name`: The current name of the binding.
+/// - `new_name`: The new name of the binding.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if no binding named
+/// `old_name` is declared at module level.
+pub fn rename_identifier_ast(
+    file_content: &str,
+    old_name: &str,
+    new_name: &str,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut renamer = BindingRenamer {
+        old_name: old_name.to_string(),
+        new_name: new_name.to_string(),
+        functions_only: false,
+        find: FindCondition::NotFound("".to_string()),
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut renamer);
+    if renamer.find == FindCondition::Found {
+        result
+    } else {
+        Err(renamer.find.message().to_string())
+    }
+}
+
+// ###################################################################################
+// #################### (▰˘◡˘▰) Work with AST Module Kind (▰˘◡˘▰) ####################
+// ###################################################################################
+
+/// The module system a JavaScript file is written for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ModuleKind {
+    Esm,
+    CommonJs,
+    Script,
+}
+
+#[derive(Default)]
+struct ModuleKindDetector {
+    esm: usize,
+    common_js: usize,
+}
+
+impl VisitMut for ModuleKindDetector {
+    fn visit_mut_module_decl(&mut self, module_decl: &mut ModuleDecl) {
+        self.esm += 1;
+        module_decl.visit_mut_children_with(self)
+    }
+
+    // `require("module")`
+    fn visit_mut_call_expr(&mut self, call_expr: &mut CallExpr) {
+        if let Callee::Expr(callee) = &call_expr.callee {
+            if matches!(&**callee, Expr::Ident(ident) if ident.sym == "require") {
+                self.common_js += 1;
+            }
+        }
+        call_expr.visit_mut_children_with(self)
+    }
+
+    // `module.exports` and `exports.name`
+    fn visit_mut_member_expr(&mut self, member_expr: &mut MemberExpr) {
+        if let Expr::Ident(obj) = &*member_expr.obj {
+            let is_module_exports = obj.sym == "module"
+                && matches!(&member_expr.prop, MemberProp::Ident(prop) if prop.sym == "exports");
+
+            if is_module_exports || obj.sym == "exports" {
+                self.common_js += 1;
+            }
+        }
+        member_expr.visit_mut_children_with(self)
+    }
+}
+
+/// Detects the module system of the JavaScript source code.
+///
+/// Any `import` or `export` declaration makes the file `Esm`, also when it mixes in
+/// CommonJS code. Otherwise `require(...)`, `module.exports` or `exports.name` make it
+/// `CommonJs`, and everything else is a plain `Script`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the detected [`ModuleKind`], or an error message if parsing fails.
+pub fn detect_module_kind_from_ast(file_content: &str) -> Result<ModuleKind, String> {
+    let (mut module, _comments, _cm) = parse(file_content)?;
+
+    let mut detector = ModuleKindDetector::default();
+    module.visit_mut_with(&mut detector);
+
+    if detector.esm > 0 {
+        Ok(ModuleKind::Esm)
+    } else if detector.common_js > 0 {
+        Ok(ModuleKind::CommonJs)
+    } else {
+        Ok(ModuleKind::Script)
+    }
+}
+
+// ###################################################################################
+// ##################### (▰˘◡˘▰) Work with AST Validation (▰˘◡˘▰) ####################
+// ###################################################################################
+
+/// Checks that the JavaScript source code parses, without visiting or emitting it.
+///
+/// This is a cheap gate before running heavier codemods. Errors the parser recovers from,
+/// e.g. a `with` statement in a module, make the code invalid too.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// `Ok(())` when the code parses without any error, otherwise an error message with the
+/// position of every syntax error.
+pub fn validate_js_from_ast(file_content: &str) -> Result<(), String> {
+    let (_module, _comments, _cm, warnings) = parse_with_diagnostics(file_content)?;
+    if warnings.is_empty() {
+        Ok(())
+    } else {
+        Err(format!("Invalid module: {}", warnings.join("; ")))
+    }
+}
+
+/// Parses and re-emits the JavaScript source code, also returning the non-fatal issues the
+/// parser recovered from, so they can be surfaced without failing.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the emitted code and the warnings with their position, or an
+/// error message if the code can not be parsed.
+pub fn parse_with_diagnostics_from_ast(
+    file_content: &str,
+) -> Result<(String, Vec<String>), String> {
+    let (mut module, comments, cm, warnings) = parse_with_diagnostics(file_content)?;
+
+    let code = code_gen_from_ast_module(&mut module, comments, cm)?;
+    Ok((code, warnings))
+}
+
+// ###################################################################################
+// #################### (▰˘◡˘▰) Work with AST Statements (▰˘◡˘▰) #####################
+// ###################################################################################
+
+/// Parses a snippet of one or more statements to be inserted into another module.
+fn parse_statements(statement_source: &str) -> Result<Vec<Stmt>, String> {
+    let (module, _comments, _cm) = parse(statement_source)?;
+
+    let mut statements = vec![];
+    for item in module.body {
+        match item {
+            ModuleItem::Stmt(mut stmt) => {
+                stmt.visit_mut_with(&mut SpanRemover);
+                statements.push(stmt);
+            }
+            ModuleItem::ModuleDecl(_) => {
+                return Err("Only statements can be inserted, not imports or exports".to_string())
+            }
+        }
+    }
+
+    if statements.is_empty() {
+        Err("No statement to insert".to_string())
+    } else {
+        Ok(statements)
+    }
+}
+
+/// Inserts one or more statements right after the last import, or at the top of the file
+/// when it has no imports, e.g. `window.liveSocket = liveSocket;`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `statement_source`: The statements to insert, as JavaScript source code.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if either source does not
+/// parse or the snippet contains imports or exports.
+pub fn insert_statement_after_imports_ast(
+    file_content: &str,
+    statement_source: &str,
+) -> Result<String, String> {
+    let statements = parse_statements(statement_source)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    let position = module
+        .body
+        .iter()
+        .rposition(|item| matches!(item, ModuleItem::ModuleDecl(ModuleDecl::Import(_))))
+        .map_or(0, |index| index + 1);
+    module.body.splice(
+        position..position,
+        statements.into_iter().map(ModuleItem::Stmt),
+    );
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+/// Finds `window.addEventListener("<event_name>", ...)` calls.
+struct WindowEventListenerFinder<'a> {
+    event_name: &'a str,
+    found: bool,
+}
+
+impl Visit for WindowEventListenerFinder<'_> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if let Callee::Expr(callee) = &call_expr.callee {
+            if let Expr::Member(MemberExpr { obj, prop, .. }) = &**callee {
+                if let (Expr::Ident(obj), MemberProp::Ident(prop)) = (&**obj, prop) {
+                    if obj.sym == "window" && prop.sym == "addEventListener" {
+                        if let Some(Expr::Lit(Lit::Str(event))) =
+                            call_expr.args.first().map(|arg| &*arg.expr)
+                        {
+                            if event.value == self.event_name {
+                                self.found = true;
+                            }
+                        }
+                    }
+                }
+            }
+        }
+
+        call_expr.visit_children_with(self)
+    }
+}
+
+/// Appends `window.addEventListener("<event_name>", <handler>);` to the end of the file,
+/// unless a `window.addEventListener` call for the same event already exists.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `event_name`: The event name, e.g. `phx:page-loading-start`.
+/// - `handler_source`: The listener as JavaScript source, e.g. `(_info) => topbar.show(300)`.
+///
+/// # Returns
+/// A `Result` containing the updated code, unchanged apart from formatting when a listener
+/// exists, or an error message if the file or the handler does not parse.
+pub fn ensure_window_event_listener_ast(
+    file_content: &str,
+    event_name: &str,
+    handler_source: &str,
+) -> Result<String, String> {
+    let handler = parse_expression(handler_source)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    let mut finder = WindowEventListenerFinder {
+        event_name,
+        found: false,
+    };
+    module.visit_with(&mut finder);
+
+    if !finder.found {
+        let listener = Expr::Call(CallExpr {
+            span: DUMMY_SP,
+            ctxt: SyntaxContext::empty(),
+            callee: Callee::Expr(Box::new(Expr::Member(MemberExpr {
+                span: DUMMY_SP,
+                obj: Box::new(Expr::Ident(ident("window"))),
+                prop: MemberProp::Ident(IdentName::new("addEventListener".into(), DUMMY_SP)),
+            }))),
+            args: vec![
+                ExprOrSpread {
+                    spread: None,
+                    expr: Box::new(Expr::Lit(Lit::Str(Str {
+                        span: DUMMY_SP,
+                        value: event_name.into(),
+                        raw: None,
+                    }))),
+                },
+                ExprOrSpread {
+                    spread: None,
+                    expr: handler,
+                },
+            ],
+            type_args: None,
+        });
+
+        module.body.push(ModuleItem::Stmt(Stmt::Expr(ExprStmt {
+            span: DUMMY_SP,
+            expr: Box::new(listener),
+        })));
+    }
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+/// The dotted path of an identifier or a member expression, e.g. `window.liveSocket`;
+/// `window["liveSocket"]` gives the same path.
+fn member_path(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Ident(ident) => Some(ident.sym.to_string()),
+        Expr::Member(member_expr) => member_expr_path(member_expr),
+        Expr::Paren(paren) => member_path(&paren.expr),
+        _ => None,
+    }
+}
+
+fn member_expr_path(member_expr: &MemberExpr) -> Option<String> {
+    let object = member_path(&member_expr.obj)?;
+    let property = match &member_expr.prop {
+        MemberProp::Ident(ident) => ident.sym.to_string(),
+        MemberProp::Computed(ComputedPropName { expr, .. }) => match &**expr {
+            Expr::Lit(Lit::Str(property)) => property.value.to_string(),
+            _ => return None,
+        },
+        MemberProp::PrivateName(_) => return None,
+    };
+
+    Some(format!("{}.{}", object, property))
+}
+
+fn assign_target_path(target: &AssignTarget) -> Option<String> {
+    match target {
+        AssignTarget::Simple(SimpleAssignTarget::Ident(ident)) => Some(ident.id.sym.to_string()),
+        AssignTarget::Simple(SimpleAssignTarget::Member(member_expr)) => {
+            member_expr_path(member_expr)
+        }
+        AssignTarget::Simple(SimpleAssignTarget::Paren(paren)) => member_path(&paren.expr),
+        _ => None,
+    }
+}
+
+/// Finds `=` assignments to a given left-hand side path.
+struct AssignmentFinder<'a> {
+    path: &'a str,
+    found: bool,
+}
+
+impl Visit for AssignmentFinder<'_> {
+    fn visit_assign_expr(&mut self, assign_expr: &AssignExpr) {
+        if assign_expr.op == AssignOp::Assign
+            && assign_target_path(&assign_expr.left).as_deref() == Some(self.path)
+        {
+            self.found = true;
+        }
+
+        assign_expr.visit_children_with(self)
+    }
+}
+
+/// Appends `<lhs> = <rhs>;` to the end of the file, unless an assignment to the same
+/// left-hand side already exists anywhere in it, e.g. `window.liveSocket = liveSocket;`.
+///
+/// The left-hand sides are compared by their dotted path, so `window["liveSocket"]` matches
+/// `window.liveSocket`. The value of an existing assignment is not checked.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `lhs`: An identifier or member expression, e.g. `window.liveSocket`.
+/// - `rhs`: The assigned value as JavaScript source, e.g. `liveSocket`.
+///
+/// # Returns
+/// A `Result` containing the updated code, unchanged apart from formatting when the
+/// assignment exists, or an error message if the file or the assignment does not parse.
+pub fn ensure_global_assignment_ast(
+    file_content: &str,
+    lhs: &str,
+    rhs: &str,
+) -> Result<String, String> {
+    let statements = parse_statements(&format!("{} = {};", lhs, rhs))?;
+    let path = match statements.as_slice() {
+        [Stmt::Expr(ExprStmt { expr, .. })] => match &**expr {
+            Expr::Assign(assign_expr) => assign_target_path(&assign_expr.left),
+            _ => None,
+        },
+        _ => None,
+    }
+    .ok_or_else(|| format!("`{}` is not an identifier or a member expression", lhs))?;
+
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    let mut finder = AssignmentFinder {
+        path: &path,
+        found: false,
+    };
+    module.visit_with(&mut finder);
+
+    if !finder.found {
+        module
+            .body
+            .extend(statements.into_iter().map(ModuleItem::Stmt));
+    }
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+/// Checks if a statement is a `console.log(...)` call.
+fn is_console_log_stmt(stmt: &Stmt) -> bool {
+    let Stmt::Expr(ExprStmt { expr, .. }) = stmt else {
+        return false;
+    };
+    let Expr::Call(CallExpr {
+        callee: Callee::Expr(callee),
+        ..
+    }) = &**expr
+    else {
+        return false;
+    };
+
+    matches!(&**callee, Expr::Member(MemberExpr {
+        obj,
+        prop: MemberProp::Ident(prop),
+        ..
+    }) if prop.sym == "log" && matches!(&**obj, Expr::Ident(console) if console.sym == "console"))
+}
+
+/// Wraps every top-level `console.log(...)` statement in `if (flag_expr) { ... }`, so the
+/// logs can be turned off in production. Other console methods, e.g. `console.error`, and
+/// logs that are already inside an `if` are left as they are.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `flag_expr`: The guard condition as JavaScript source, e.g. `window.DEBUG`.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message if the file or the flag
+/// expression does not parse.
+pub fn guard_console_logs_ast(file_content: &str, flag_expr: &str) -> Result<String, String> {
+    let flag = parse_expression(flag_expr)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    for item in &mut module.body {
+        let ModuleItem::Stmt(stmt) = item else {
+            continue;
+        };
+        if !is_console_log_stmt(stmt) {
+            continue;
+        }
+
+        let log = std::mem::replace(stmt, Stmt::Empty(EmptyStmt { span: DUMMY_SP }));
+        *stmt = Stmt::If(IfStmt {
+            span: DUMMY_SP,
+            test: flag.clone(),
+            cons: Box::new(Stmt::Block(BlockStmt {
+                span: DUMMY_SP,
+                ctxt: SyntaxContext::empty(),
+                stmts: vec![log],
+            })),
+            alt: None,
+        });
+    }
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use super::*;
+
+    #[test]
+    fn test_is_module_imported_from_ast() {
+        let code = r#"
+            import "phoenix_html";
+            import { Socket, SocketV1 } from "phoenix";
+            import { TS } from "tsobject";
+
+            // This is first test we need to have
+            console.log("We are here");
+
+            const min = ()          => {return "Shahryar" + "Tavakkoli"};
+            "#;
+
+        let import = r#"
+                import "phoenix_html";
+                import { Socket, SocketV1 } from "phoenix";
+                import { TS } from "tsobject";
+            "#;
+        let result = is_module_imported_from_ast(code, import);
+
+        assert!(result.is_ok(), "Expected Ok(true), but got {:?}", result);
+
+        let import = r#"
+                import { NoneRepeated } from "orepeat";
+            "#;
+        let result = is_module_imported_from_ast(code, import);
+        assert!(result.is_err(), "Expected Ok(true), but got {:?}", result);
+
+        let import = r#"
+                import "phoenix_html";
+                import { NoneRepeated } from "orepeat";
+                import { TS } from "tsobject";
+            "#;
+        let result = is_module_imported_from_ast(code, import);
+
+        assert!(result.is_err(), "Expected Ok(true), but got {:?}", result);
+    }
+    #[test]
+    fn test_insert_import_to_ast() {
+        let code = r#"
+            import "phoenix_html";
+            import { Socket, SocketV1 } from "phoenix";
+            import { TS } from "tsobject";
+            import ScrollArea from "./scrollArea.js";
+
+            // This is first test we need to have
+            console.log("We are here");
+
+            const min = ()          => {return "Shahryar" + "Tavakkoli"};
+            "#;
+
+        let import = r#"
+                import "phoenix_html";
+                import { Socket, SocketV1 } from "phoenix";
+                import { TS } from "tsobject";
+                import { NoneRepeated } from "orepeat";
+                import ScrollArea from "./scrollArea.js";
+            "#;
+        let result = insert_import_to_ast(code, import).expect("Failed to generate code");
+
+        assert!(result.contains("import \"phoenix_html\";"));
+        assert!(result.contains("import { Socket, SocketV1 } from \"phoenix\";"));
+        assert!(result.contains("import { TS } from \"tsobject\";"));
+        assert!(result.contains("import { NoneRepeated } from \"orepeat\";"));
+
+        let imports_start = result.find("import \"phoenix_html\";").unwrap();
+        let imports_end = result
+            .find("import { NoneRepeated } from \"orepeat\";")
+            .unwrap();
+        assert!(imports_start < imports_end);
+
+        assert!(result.contains("// This is first test we need to have"));
+
+        println!("{}", result)
+    }
+
+    #[test]
+    fn test_insert_default_and_named_import() {
+        let code = r#"
+            import { useState } from "react";
+            import { Socket } from "phoenix";
+
+            const [count, setCount] = useState(0);
+            "#;
+
+        let import = "import React, { useState, useEffect } from \"react\";";
+        let result = insert_import_to_ast(code, import).unwrap();
+        assert!(result.contains("import React, { useState, useEffect } from \"react\";"));
+        assert_eq!(result.matches("from \"react\"").count(), 1);
+
+        let result = insert_import_to_ast(&result, import).unwrap();
+        assert_eq!(result.matches("from \"react\"").count(), 1);
+
+        let result = insert_import_to_ast(code, "import Socket from \"./socket\";").unwrap();
+        assert!(result.contains("import Socket from \"./socket\";"));
+
+        let code = "import R from \"react\";\n";
+        let result = insert_import_to_ast(code, import).unwrap();
+        assert!(result.contains("import R from \"react\";"));
+        assert!(result.contains("import React, { useState, useEffect } from \"react\";"));
+    }
+
+    #[test]
+    fn test_insert_import_to_ast_preserving_tabs() {
+        let code = "import { Socket } from \"phoenix\";\nlet liveSocket = new LiveSocket(\"/live\", Socket, {\n\thooks: {\n\t\t...Hooks\n\t},\n\tlongPollFallbackMs: 2500\n});\n";
+        let import = "import topbar from \"../vendor/topbar\";";
+
+        let options = EmitOptions {
+            preserve_formatting: true,
+            ..Default::default()
+        };
+        let result = insert_import_to_ast_with_options(code, import, &options).unwrap();
+        assert!(result.contains("import topbar from \"../vendor/topbar\";"));
+        assert!(result.contains("{\n\thooks: {\n\t\t...Hooks\n\t},\n\tlongPollFallbackMs: 2500\n}"));
+
+        let result = insert_import_to_ast(code, import).unwrap();
+        assert!(result.contains("{\n    hooks: {\n        ...Hooks\n    },"));
+    }
+
+    #[test]
+    fn test_insert_import_to_ast_with_crlf() {
+        let code = "import { Socket } from \"phoenix\";\r\nlet liveSocket = new LiveSocket(\"/live\", Socket, {\r\n  hooks: { ...Hooks }\r\n});\r\n";
+        let import = "import topbar from \"../vendor/topbar\";";
+
+        let options = EmitOptions {
+            new_line: NewLine::CrLf,
+            ..Default::default()
+        };
+        let result = insert_import_to_ast_with_options(code, import, &options).unwrap();
+        assert!(result.contains("import topbar from \"../vendor/topbar\";\r\n"));
+        assert_eq!(result.matches("\r\n").count(), result.matches('\n').count());
+
+        let result = remove_import_from_ast_with_options(&result, import, &options).unwrap();
+        assert!(!result.contains("topbar"));
+        assert_eq!(result.matches("\r\n").count(), result.matches('\n').count());
+
+        let result = insert_import_to_ast(code, import).unwrap();
+        assert!(!result.contains('\r'));
+    }
+
+    #[test]
+    fn test_insert_import_to_ast_with_spans() {
+        let code = r#"
+            import { Socket } from "phoenix";
+            import topbar from "../vendor/topbar";
+
+            let Hooks = {};
+            "#;
+
+        let import = r#"
+                import { Socket } from "phoenix";
+                import { NoneRepeated } from "orepeat";
+                import * as Vendor from "./vendor.js";
+            "#;
+
+        let (result, spans) =
+            insert_import_to_ast_with_spans(code, import).expect("Failed to generate code");
+
+        assert_eq!(spans.len(), 2);
+        assert_eq!(
+            &result[spans[0].0..spans[0].1],
+            "import { NoneRepeated } from \"orepeat\";"
+        );
+        assert_eq!(
+            &result[spans[1].0..spans[1].1],
+            "import * as Vendor from \"./vendor.js\";"
+        );
+        assert!(result[spans[1].1..].starts_with("\nlet Hooks = {};"));
+    }
+
+    #[test]
+    fn test_remove_import_from_ast() {
+        let code = r#"
+            import "phoenix_html";
+            import { Socket, SocketV1 } from "phoenix";
+            import { TS } from "tsobject";
+
+            // This is first test we need to have
+            console.log("We are here");
+
+            const min = ()          => {return "Shahryar" + "Tavakkoli"};
+            "#;
+
+        let import = r#"
+                import { TS } from "tsobject";
+                import { Socket, SocketV1 } from "phoenix";
+                import { NoneRepeated } from "orepeat";
+                import { NoneRepeated1 } from "orepeat1";
+            "#;
+        let result = remove_import_from_ast(code, import).expect("Failed to generate code");
+
+        assert!(result.contains("import \"phoenix_html\";"));
+        assert!(!result.contains("import { Socket, SocketV1 } from \"phoenix\";"));
+        assert!(!result.contains("import { TS } from \"tsobject\";"));
+
+        let code = r#"
+        import { foo } from "module-name";
+        import bar from "another-module";
+        let Hooks = {};
+        "#;
+
+        let result = remove_import_from_ast(code, "import bar from \"another-module\";")
+            .expect("Failed to generate code");
+
+        println!("{}", result);
+    }
+
+    #[test]
+    fn test_statistics_from_ast() {
+        let code = r#"
+            import { foo } from 'bar';
+            import * as jar from 'jar';
+            console.log('Start JS file');
+            class Foo {
+                constructor() {
+                    debugger;
+                    console.log('Hello');
+                }
+            }
+            function bar() {
+                console.log('World');
+                debugger;
+            }
+        "#;
+        let parsed = statistics_from_ast(code).unwrap();
+        assert_eq!(parsed.functions, 1);
+        assert_eq!(parsed.classes, 1);
+        assert_eq!(parsed.debuggers, 2);
+        assert_eq!(parsed.imports, 2);
+        assert_eq!(parsed.trys, 0);
+        assert_eq!(parsed.throws, 0);
+        assert_eq!(parsed.exports, 0);
+    }
+
+    #[test]
+    fn test_statistics_fixtures_are_stable() {
+        let inline = r#"
+            import { foo } from 'bar';
+            import * as jar from 'jar';
+            console.log('Start JS file');
+            class Foo {
+                constructor() {
+                    debugger;
+                    console.log('Hello');
+                }
+            }
+            function bar() {
+                console.log('World');
+                debugger;
+            }
+        "#;
+        let asset = include_str!("../../../../../test/assets/validASTStatistics.js");
+
+        let inline = statistics_from_ast(inline).unwrap();
+        let asset = statistics_from_ast(asset).unwrap();
+
+        let counts = |s: &ASTStatistics| {
+            (
+                s.functions,
+                s.classes,
+                s.debuggers,
+                s.imports,
+                s.trys,
+                s.throws,
+                s.exports,
+            )
+        };
+        assert_eq!(counts(&inline), counts(&asset));
+        assert_eq!(counts(&asset), (1, 1, 2, 2, 0, 0, 0));
+    }
+
+    #[test]
+    fn test_statistics_exports_from_ast() {
+        let code = r#"
+            const Components = {};
+            export const version = "1.0.0";
+            export function setup() {}
+            export default Components;
+        "#;
+        let parsed = statistics_from_ast(code).unwrap();
+        assert_eq!(parsed.exports, 3);
+        assert_eq!(parsed.functions, 1);
+    }
+
+    #[test]
+    fn test_statistics_var_kinds_from_ast() {
+        let code = r#"
+            const Hooks = {};
+            const csrfToken = "token", debug = false;
+            let liveSocket = null;
+            var legacy = 1, older = 2, oldest = 3;
+            function setup() {
+                let counter = 0;
+                for (var i = 0; i < 10; i++) {
+                    counter += i;
+                }
+            }
+        "#;
+        let parsed = statistics_from_ast(code).unwrap();
+        assert_eq!(parsed.consts, 3);
+        assert_eq!(parsed.lets, 2);
+        assert_eq!(parsed.vars, 4);
+    }
+
+    #[test]
+    fn test_extend_var_object_property_by_names_to_ast() {
+        let code = r#"
+            const Components = {...Hoks, PreOrderd};
+
+            // Export the components as default
+            export default Components;
+            "#;
+
+        let object_names = [
+            "...ExtendedObject".to_string(),
+            "ObjectOne".to_string(),
+            "PreOrderd".to_string(),
+            "CopyCodeHooks".to_string(),
+            "...Hoks".to_string(),
+            "ObjectOne".to_string(),
+        ];
+        let unique_names: HashSet<String> = object_names.into_iter().collect();
+        let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+        vec_of_strs.sort();
+
+        let result =
+            extend_var_object_property_by_names_to_ast(code, "Components", vec_of_strs.clone());
+        assert!(result.is_ok());
+        println!("{}", result.unwrap());
+
+        let result =
+            extend_var_object_property_by_names_to_ast(code, "NoneComponent", vec_of_strs.clone());
+        assert!(result.is_err());
+
+        let code = r#"
+            const Components = () => {1 + 1};
+
+            // Export the components as default
+            export default Components;
+            "#;
+
+        let result =
+            extend_var_object_property_by_names_to_ast(code, "Components", vec_of_strs.clone());
+        assert!(result.is_err());
+
+        let code = r#"
+            import ScrollArea from "./scrollArea.js";
+
+            const Components = {
+              ScrollArea,
+            };
+
+            export default Components;
+            "#;
+
+        let object_names = ["ScrollArea", "NoneComponent"];
+
+        let result = extend_var_object_property_by_names_to_ast(code, "Components", object_names);
+        assert!(result.is_ok());
+
+        let code = r#"
+            import ScrollArea from "./scrollArea.js";
+
+            const Components = {};
+
+            export default Components;
+            "#;
+
+        let object_names = ["ScrollArea", "NoneComponent", "...NoneComponent"];
+        let result = extend_var_object_property_by_names_to_ast(code, "Components", object_names);
+
+        assert!(result.is_ok());
+    }
+
+    #[test]
+    fn test_extend_var_object_for_every_declaration_kind() {
+        for kind in ["const", "let", "var"] {
+            let code = format!("{} Components = {{}};\nexport default Components;\n", kind);
+
+            let result =
+                extend_var_object_property_by_names_to_ast(&code, "Components", ["ScrollArea"]);
+            assert_eq!(
+                result,
+                Ok(format!(
+                    "{} Components = {{\n    ScrollArea\n}};\nexport default Components;\n",
+                    kind
+                ))
+            );
+        }
+
+        let code = "const Components = ({});\n";
+        let result = extend_var_object_property_by_names_to_ast(code, "Components", ["ScrollArea"]);
+        assert!(result.is_ok());
+        assert!(result.unwrap().contains("ScrollArea"));
+
+        let code = r#"
+            const Components = {};
+
+            function register() {
+              const Components = window.Components;
+              return Components;
+            }
+
+            export default Components;
+            "#;
+        let result = extend_var_object_property_by_names_to_ast(code, "Components", ["ScrollArea"]);
+        assert!(
+            result.is_ok(),
+            "Shadowed declaration failed: {:?}",
+            result.err()
+        );
+        assert!(result.unwrap().contains("ScrollArea"));
+    }
+
+    #[test]
+    fn test_extend_var_object_skips_existing_spread() {
+        let code = "const Components = {...Hoks, PreOrderd};\n";
+
+        let unchanged =
+            extend_var_object_property_by_names_to_ast(code, "Components", ["PreOrderd"])
+                .expect("Extending failed");
+        let result = extend_var_object_property_by_names_to_ast(
+            code,
+            "Components",
+            ["...Hoks", "PreOrderd"],
+        )
+        .expect("Extending failed");
+        assert_eq!(result, unchanged);
+        assert_eq!(result.matches("...Hoks").count(), 1);
+
+        let result =
+            extend_var_object_property_by_names_to_ast(code, "Components", ["...Hoks", "...Other"])
+                .expect("Extending failed");
+        assert_eq!(result.matches("...Hoks").count(), 1);
+        assert!(result.contains("...Other"));
+    }
+
+    #[test]
+    fn test_invalid_javascript_returns_error() {
+        let valid = r#"
+            import { Socket } from "phoenix";
+            let liveSocket = new LiveSocket("/live", Socket, {});
+            "#;
+        let invalid = "let x = ;";
+
+        assert!(is_module_imported_from_ast(valid, invalid).is_err());
+        assert!(
+            is_module_imported_from_ast(invalid, "import { Socket } from \"phoenix\";").is_err()
+        );
+
+        assert!(insert_import_to_ast(valid, invalid).is_err());
+        assert!(insert_import_to_ast(invalid, "import { Socket } from \"phoenix\";").is_err());
+
+        assert!(remove_import_from_ast(valid, invalid).is_err());
+        assert!(remove_import_from_ast(invalid, "phoenix").is_err());
+
+        assert!(contains_variable_from_ast(invalid, "liveSocket").is_err());
+
+        assert!(extend_var_object_property_by_names_to_ast(invalid, "Hooks", ["Hook"]).is_err());
+    }
+
+    #[test]
+    fn test_contains_variable_from_ast() {
+        let code = r#"
+            let liveSocket = new LiveSocket("/live", Socket, {
+              hooks: { ...Hooks, CopyMixInstallationHook },
+              longPollFallbackMs: 2500,
+              params: { _csrf_token: csrfToken },
+            });
+            "#;
+
+        let result = contains_variable_from_ast(code, "liveSocket");
+
+        println!("{:#?}", result.unwrap());
+
+        let code = "const Components = {};\nvar Hooks = {};\n";
+        assert_eq!(contains_variable_from_ast(code, "Components"), Ok(true));
+        assert_eq!(contains_variable_from_ast(code, "Hooks"), Ok(true));
+        assert_eq!(contains_variable_from_ast(code, "liveSocket"), Err(false));
+    }
+
+    #[test]
+    fn test_minify_js() {
+        let code = r#"
+            // Adds two numbers
+            function add(first, second) {
+                return first + second;
+            }
+
+            const total = add(1, 2);
+            "#;
+
+        let result = minify_js(code).unwrap();
+        assert!(result.starts_with("function add(first,second){return first+second"));
+        assert!(result.contains("const total=add(1,2)"));
+        assert!(!result.contains("Adds two numbers"));
+        assert!(!result.trim_end().contains('\n'));
+
+        assert!(minify_js("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_strip_comments_js() {
+        let code = r#"
+            // Leading comment
+            /* Block comment */
+            import { Socket } from "phoenix"; // inline comment
+
+            /**
+             * Doc comment
+             */
+            function add(first, /* inline */ second) {
+                return first + second; // trailing
+            }
+            "#;
+
+        let result = strip_comments_js(code).unwrap();
+        assert!(!result.contains("//"));
+        assert!(!result.contains("/*"));
+        assert!(result.contains("import { Socket } from \"phoenix\";"));
+        assert!(result.contains("function add(first, second) {"));
+
+        assert!(strip_comments_js("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_ast_fingerprint() {
+        let code = "function add(a, b) {\n  return a + b;\n}\n";
+        let reformatted = r#"
+            // Adds two numbers
+            function add(a,b){return a+b}
+            "#;
+
+        let fingerprint = ast_fingerprint(code).unwrap();
+        assert_eq!(fingerprint.len(), 16);
+        assert_eq!(ast_fingerprint(reformatted).unwrap(), fingerprint);
+        assert_ne!(
+            ast_fingerprint("function add(a, b) { return a - b; }").unwrap(),
+            fingerprint
+        );
+
+        assert!(ast_fingerprint("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_contains_export_from_ast() {
+        let code = r#"
+            const Hooks = {};
+            const internal = 1;
+            export const version = "1.0.0";
+            export let counter = 0;
+            export function setup() {}
+            export class Socket {}
+            export { Hooks, internal as publicName };
+            export { topbar } from "../vendor/topbar";
+            "#;
+
+        assert!(contains_export_from_ast(code, "version").is_ok());
+        assert!(contains_export_from_ast(code, "counter").is_ok());
+        assert!(contains_export_from_ast(code, "setup").is_ok());
+        assert!(contains_export_from_ast(code, "Socket").is_ok());
+        assert!(contains_export_from_ast(code, "Hooks").is_ok());
+        assert!(contains_export_from_ast(code, "publicName").is_ok());
+        assert!(contains_export_from_ast(code, "topbar").is_ok());
+        assert!(contains_export_from_ast(code, "internal").is_err());
+        assert!(contains_export_from_ast(code, "default").is_err());
+
+        let code = "const Components = {};\nexport default Components;";
+        assert!(contains_export_from_ast(code, "default").is_ok());
+        assert!(contains_export_from_ast(code, "Components").is_err());
+
+        let code = "export default function () {}";
+        assert!(contains_export_from_ast(code, "default").is_ok());
+
+        assert!(contains_export_from_ast("export const = ;", "default").is_err());
+    }
+
+    #[test]
+    fn test_wrap_in_try_catch_ast() {
+        let code = r#"
+            function connect(socket) {
+                socket.connect();
+                return socket;
+            }
+
+            export function disconnect(socket) {
+                socket.disconnect();
+            }
+            "#;
+
+        let trys_before = statistics_from_ast(code).unwrap().trys;
+
+        let result = wrap_in_try_catch_ast(code, "connect");
+        assert!(result.is_ok(), "Wrapping failed: {:?}", result.err());
+        let output = result.unwrap();
+        assert!(output.contains("console.error(e)"));
+        assert_eq!(statistics_from_ast(&output).unwrap().trys, trys_before + 1);
+
+        let output = wrap_in_try_catch_ast(&output, "disconnect").unwrap();
+        assert_eq!(statistics_from_ast(&output).unwrap().trys, trys_before + 2);
+
+        assert!(wrap_in_try_catch_ast(code, "missing").is_err());
+    }
+
+    #[test]
+    fn test_remove_debugger_statements_ast() {
+        let code = include_str!("../../../../../test/assets/validASTStatistics.js");
+        assert_eq!(statistics_from_ast(code).unwrap().debuggers, 2);
+
+        let result = remove_debugger_statements_ast_with_count(code);
+        assert!(result.is_ok(), "Removing failed: {:?}", result.err());
+        let (output, removed) = result.unwrap();
+        assert_eq!(removed, 2);
+        assert_eq!(statistics_from_ast(&output).unwrap().debuggers, 0);
+
+        let code = "debugger;\nif (ready) debugger;\nconsole.log(ready);";
+        let output = remove_debugger_statements_ast(code).unwrap();
+        assert!(!output.contains("debugger"));
+        assert!(output.contains("console.log(ready)"));
+    }
+
+    #[test]
+    fn test_rename_function_ast() {
+        let code = r#"
+            function add(a, b) {
+                return a + b;
+            }
+            const total = add(1, 2);
+            const api = { add };
+            export { add };
+            "#;
+
+        let result = rename_function_ast(code, "add", "sum");
+        assert!(result.is_ok(), "Renaming failed: {:?}", result.err());
+        let output = result.unwrap();
+        assert!(output.contains("function sum(a, b)"));
+        assert!(output.contains("const total = sum(1, 2);"));
+        assert!(output.contains("add: sum"));
+        assert!(output.contains("sum as add"));
+
+        assert!(rename_function_ast(code, "missing", "sum").is_err());
+        assert!(rename_function_ast("const add = () => 1;", "add", "sum").is_err());
+    }
+
+    #[test]
+    fn test_rename_function_ast_keeps_shadowed_names() {
+        let code = r#"
+            function add(a, b) {
+                return a + b;
+            }
+            function withParam(add) {
+                return add(1, 2);
+            }
+            function withLocal() {
+                const add = (x) => x;
+                return add(3);
+            }
+            function withVar() {
+                if (true) {
+                    var add = null;
+                }
+                return add;
+            }
+            add(5, 6);
+            "#;
+
+        let output = rename_function_ast(code, "add", "sum").unwrap();
+        assert!(output.contains("function sum(a, b)"));
+        assert!(output.contains("sum(5, 6);"));
+        assert!(output.contains("function withParam(add)"));
+        assert!(output.contains("return add(1, 2);"));
+        assert!(output.contains("const add = (x)=>x;"));
+        assert!(output.contains("return add(3);"));
+        assert!(output.contains("var add = null;"));
+        assert!(output.contains("return add;"));
+    }
+
+    #[test]
+    fn test_rename_identifier_ast() {
+        let code = r#"
+            const endpoint = "/live";
+            let socket = new LiveSocket(endpoint, Socket);
+            const config = { endpoint, debug: false };
+            function connect() {
+                return fetch(endpoint);
+            }
+            function withLocal() {
+                const endpoint = "/other";
+                return endpoint;
+            }
+            const handler = (endpoint) => endpoint.length;
+            const { endpoint: alias } = config;
+            export { endpoint };
+            "#;
+
+        let output = rename_identifier_ast(code, "endpoint", "liveEndpoint").unwrap();
+        assert!(output.contains("const liveEndpoint = \"/live\";"));
+        assert!(output.contains("new LiveSocket(liveEndpoint, Socket)"));
+        assert!(output.contains("endpoint: liveEndpoint,"));
+        assert!(output.contains("return fetch(liveEndpoint);"));
+        assert!(output.contains("const endpoint = \"/other\";"));
+        assert!(output.contains("return endpoint;"));
+        assert!(output.contains("(endpoint)=>endpoint.length"));
+        assert!(output.contains("liveEndpoint as endpoint"));
+
+        let code = "class Store {}\nconst store = new Store();\n";
+        let output = rename_identifier_ast(code, "Store", "AppStore").unwrap();
+        assert!(output.contains("class AppStore {"));
+        assert!(output.contains("new AppStore()"));
+
+        let code = "const { a, b = 1 } = props;\nconsole.log(b);\n";
+        let output = rename_identifier_ast(code, "b", "c").unwrap();
+        assert!(output.contains("b: c = 1"));
+        assert!(output.contains("console.log(c);"));
+
+        assert!(rename_identifier_ast(code, "missing", "c").is_err());
+        assert!(rename_function_ast(code, "b", "c").is_err());
+    }
+
+    #[test]
+    fn test_insert_import_before_ast() {
+        let code = r#"
+            import "phoenix_html";
+            import { Socket } from "phoenix";
+            import topbar from "../vendor/topbar";
+            let Hooks = {};
+            "#;
+
+        let result = insert_import_before_ast(
+            code,
+            "import a from \"a\";\nimport b from \"b\";",
+            "../vendor/topbar",
+        );
+        assert!(result.is_ok(), "Insertion failed: {:?}", result.err());
+        let output = result.unwrap();
+
+        let position = |output: &str, needle: &str| output.find(needle).unwrap();
+        assert!(position(&output, "from \"phoenix\"") < position(&output, "from \"a\""));
+        assert!(position(&output, "from \"a\"") < position(&output, "from \"b\""));
+        assert!(position(&output, "from \"b\"") < position(&output, "from \"../vendor"));
+
+        // Unknown marker falls back to appending after the last import
+        let output = insert_import_before_ast(code, "import a from \"a\";", "missing").unwrap();
+        assert!(position(&output, "from \"../vendor") < position(&output, "from \"a\""));
+        assert!(position(&output, "from \"a\"") < position(&output, "let Hooks"));
+    }
+
+    #[test]
+    fn test_import_would_change() {
+        let code = "import { Socket } from \"phoenix\";\nlet Hooks = {};";
+
+        assert_eq!(
+            insert_import_to_ast_would_change(code, "import topbar from \"topbar\";"),
+            Ok(true)
+        );
+        assert_eq!(
+            insert_import_to_ast_would_change(code, "import { Socket } from \"phoenix\";"),
+            Ok(false)
+        );
+
+        assert_eq!(
+            remove_import_from_ast_would_change(code, "import { Socket } from \"phoenix\";"),
+            Ok(true)
+        );
+        assert_eq!(
+            remove_import_from_ast_would_change(code, "import topbar from \"topbar\";"),
+            Ok(false)
+        );
+        assert!(insert_import_to_ast_would_change("let x = ;", "import a from \"a\";").is_err());
+    }
+
+    #[test]
+    fn test_insert_import_preserves_shebang() {
+        let code = "#!/usr/bin/env node\nconsole.log(\"run\");";
+
+        let result = insert_import_to_ast(code, "import fs from \"fs\";");
+        assert!(result.is_ok(), "Insertion failed: {:?}", result.err());
+        let output = result.unwrap();
+
+        let mut lines = output.lines();
+        assert_eq!(lines.next(), Some("#!/usr/bin/env node"));
+        assert_eq!(lines.next(), Some("import fs from \"fs\";"));
+    }
+
+    #[test]
+    fn test_detect_module_kind_from_ast() {
+        assert_eq!(
+            detect_module_kind_from_ast("import { Socket } from \"phoenix\";"),
+            Ok(ModuleKind::Esm)
+        );
+        assert_eq!(
+            detect_module_kind_from_ast("const a = 1;\nexport default a;"),
+            Ok(ModuleKind::Esm)
+        );
+        assert_eq!(
+            detect_module_kind_from_ast("const fs = require(\"fs\");\nmodule.exports = { fs };"),
+            Ok(ModuleKind::CommonJs)
+        );
+        assert_eq!(
+            detect_module_kind_from_ast("exports.answer = 42;"),
+            Ok(ModuleKind::CommonJs)
+        );
+        assert_eq!(
+            detect_module_kind_from_ast("window.answer = 42;"),
+            Ok(ModuleKind::Script)
+        );
+        // A mixed file prefers ESM
+        assert_eq!(
+            detect_module_kind_from_ast(
+                "import a from \"a\";\nconst b = require(\"b\");\nmodule.exports = b;"
+            ),
+            Ok(ModuleKind::Esm)
+        );
+        assert!(detect_module_kind_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_statistics_detailed_from_ast() {
+        let code = include_str!("../../../../../test/assets/validASTStatistics.js");
+
+        let result = statistics_detailed_from_ast(code);
+        assert!(result.is_ok(), "Statistics failed: {:?}", result.err());
+        let detailed = result.unwrap();
+
+        assert_eq!(detailed.statistics.imports, 2);
+        assert_eq!(
+            detailed.imports,
+            vec![
+                ImportLocation {
+                    source: "bar".to_string(),
+                    line: 1,
+                    column: 1
+                },
+                ImportLocation {
+                    source: "jar".to_string(),
+                    line: 2,
+                    column: 1
+                },
+            ]
+        );
+
+        let detailed = statistics_detailed_from_ast("let a = 1;\n  import b from \"b\";").unwrap();
+        assert_eq!(detailed.imports[0].line, 2);
+        assert_eq!(detailed.imports[0].column, 3);
+
+        assert!(statistics_detailed_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_statistics_counts_comments() {
+        let code = r#"
+            // Establish Phoenix Socket and LiveView configuration.
+            import { Socket } from "phoenix";
+
+            /**
+             * Hooks of the application
+             */
+            let Hooks = {}; // filled below
+
+            /* inline */ Hooks.Copy = {};
+            // connect if there are any LiveViews on the page
+            "#;
+
+        let statistics = statistics_from_ast(code).unwrap();
+        assert_eq!(statistics.line_comments, 3);
+        assert_eq!(statistics.block_comments, 2);
+
+        assert!(statistics_from_ast("let x = ; // broken").is_err());
+    }
+
+    #[test]
+    fn test_extract_jsdoc_from_ast() {
+        let code = include_str!("../../../../../test/assets/jsdocExports.js");
+
+        assert_eq!(
+            extract_jsdoc_from_ast(code),
+            Ok(vec![
+                JsDoc {
+                    name: "connect".to_string(),
+                    doc: Some(
+                        "Connects the socket of the given user.\n\n@param {string} token - The user token."
+                            .to_string()
+                    ),
+                },
+                JsDoc {
+                    name: "disconnect".to_string(),
+                    doc: None,
+                },
+                JsDoc {
+                    name: "Hooks".to_string(),
+                    doc: Some("A hook registry.".to_string()),
+                },
+                JsDoc {
+                    name: "default".to_string(),
+                    doc: None,
+                },
+            ])
+        );
+
+        assert!(
+            extract_jsdoc_from_ast("/* plain */\nexport class A {}").unwrap()[0]
+                .doc
+                .is_none()
+        );
+        assert!(extract_jsdoc_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_is_import_used_ast() {
+        let code = include_str!("../../../../../test/assets/validApp.js");
+        assert_eq!(is_import_used_ast(code, "../vendor/topbar"), Ok(true));
+        assert_eq!(is_import_used_ast(code, "phoenix_html"), Ok(true));
+
+        let code = r#"
+        import topbar from "../vendor/topbar";
+        import * as utils from "./utils";
+        import { a, b as c } from "./letters";
+        const config = { topbar: true };
+        config.topbar = false;
+        utils: for (;;) { break utils; }
+        export { x as c } from "./other";
+        "#;
+        assert_eq!(is_import_used_ast(code, "../vendor/topbar"), Ok(false));
+        assert_eq!(is_import_used_ast(code, "./utils"), Ok(false));
+        assert_eq!(is_import_used_ast(code, "./letters"), Ok(false));
+
+        let code = "import * as utils from \"./utils\";\nutils.run();\n";
+        assert_eq!(is_import_used_ast(code, "./utils"), Ok(true));
+
+        let code = "import { b as c } from \"./letters\";\nexport { c };\n";
+        assert_eq!(is_import_used_ast(code, "./letters"), Ok(true));
+
+        assert!(is_import_used_ast(code, "./missing").is_err());
+        assert!(is_import_used_ast("let x = ;", "./letters").is_err());
+    }
+
+    #[test]
+    fn test_find_duplicate_import_sources_from_ast() {
+        let code = include_str!("../../../../../test/assets/duplicateImports.js");
+        assert_eq!(
+            find_duplicate_import_sources_from_ast(code),
+            Ok(vec!["react".to_string(), "./styles.css".to_string()])
+        );
+
+        let code = r#"
+        import { a } from "./a";
+        import { b } from "./b";
+        import { c } from "./b";
+        import "./a";
+        "#;
+        assert_eq!(
+            find_duplicate_import_sources_from_ast(code),
+            Ok(vec!["./a".to_string(), "./b".to_string()])
+        );
+
+        let normalized = normalize_imports_ast(code).unwrap();
+        assert_eq!(
+            find_duplicate_import_sources_from_ast(&normalized),
+            Ok(vec!["./a".to_string()])
+        );
+
+        assert_eq!(
+            find_duplicate_import_sources_from_ast("import { a } from \"a\";"),
+            Ok(vec![])
+        );
+        assert!(find_duplicate_import_sources_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_normalize_imports_ast() {
+        let code = r#"
+            import { useState, useEffect } from "react";
+            import * as ReactDOM from "react-dom";
+            import React, { useEffect as effect, memo, useState } from "react";
+            import "phoenix_html";
+            import "phoenix_html";
+            let Hooks = {};
+            "#;
+
+        let result = normalize_imports_ast(code);
+        assert!(result.is_ok(), "Normalizing failed: {:?}", result.err());
+        let output = result.unwrap();
+
+        assert_eq!(
+            output,
+            "import React, { memo, useEffect as effect, useEffect, useState } from \"react\";\n\
+             import * as ReactDOM from \"react-dom\";\n\
+             import \"phoenix_html\";\n\
+             let Hooks = {};\n"
+        );
+
+        // Running it again is a no-op
+        assert_eq!(normalize_imports_ast(&output), Ok(output.clone()));
+
+        // Different default imports of one source can not be collapsed
+        let output =
+            normalize_imports_ast("import a from \"m\";\nimport b, { c } from \"m\";").unwrap();
+        assert_eq!(
+            output,
+            "import a from \"m\";\nimport b, { c } from \"m\";\n"
+        );
+    }
+
+    #[test]
+    fn test_hoist_imports_ast() {
+        let code = r#"
+            import { Socket } from "phoenix";
+            let Hooks = {};
+            import topbar from "../vendor/topbar";
+            topbar.config({});
+            import "phoenix_html";
+            "#;
+
+        let result = hoist_imports_ast(code);
+        assert!(result.is_ok(), "Hoisting failed: {:?}", result.err());
+
+        assert_eq!(
+            result.unwrap(),
+            "import { Socket } from \"phoenix\";\n\
+             import topbar from \"../vendor/topbar\";\n\
+             import \"phoenix_html\";\n\
+             let Hooks = {};\n\
+             topbar.config({});\n"
+        );
+
+        assert!(hoist_imports_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_count_node_kinds_from_ast() {
+        let code = r#"
+            const load = async () => {
+                const response = await fetch("/api");
+                return await response.json();
+            };
+            [1, 2].map((n) => n * 2);
+            "#;
+
+        let result =
+            count_node_kinds_from_ast(code, vec!["ArrowExpr".to_string(), "AwaitExpr".to_string()]);
+        assert!(result.is_ok(), "Counting failed: {:?}", result.err());
+        let counts = result.unwrap();
+
+        assert_eq!(counts.len(), 2);
+        assert_eq!(counts["ArrowExpr"], 2);
+        assert_eq!(counts["AwaitExpr"], 2);
+
+        assert_eq!(
+            count_node_kinds_from_ast(code, vec!["TryStmt".to_string()]).unwrap()["TryStmt"],
+            0
+        );
+        assert!(count_node_kinds_from_ast(code, vec!["Unknown".to_string()]).is_err());
+    }
+
+    #[test]
+    fn test_validate_js_from_ast() {
+        let code = include_str!("../../../../../test/assets/validApp.js");
+        assert_eq!(validate_js_from_ast(code), Ok(()));
+
+        let result = validate_js_from_ast("let liveSocket = new LiveSocket(\"/live\", {");
+        let error = result.err().expect("Expected a validation error");
+        assert!(!error.is_empty());
+        assert!(error.contains("line 1"), "Unexpected message: {}", error);
+
+        let result = validate_js_from_ast("let scope = {};\nwith (scope) {}\n");
+        let error = result.err().expect("Expected a recovered error");
+        assert!(error.contains("line 2"), "Unexpected message: {}", error);
+    }
+
+    #[test]
+    fn test_extend_var_objects_batch_ast() {
+        let code = r#"
+            const Components = { ScrollArea };
+            let Hooks = {};
+            let counter = 0;
+            "#;
+
+        let result = extend_var_objects_batch_ast(
+            code,
+            vec![
+                (
+                    "Components".to_string(),
+                    vec!["Dialog".to_string(), "ScrollArea".to_string()],
+                ),
+                ("Hooks".to_string(), vec!["CopyHook".to_string()]),
+                ("counter".to_string(), vec!["Nothing".to_string()]),
+                ("Missing".to_string(), vec!["Nothing".to_string()]),
+            ],
+        );
+        assert!(result.is_ok(), "Batch failed: {:?}", result.err());
+        let batch = result.unwrap();
+
+        assert_eq!(batch.extended, vec!["Components", "Hooks"]);
+        assert_eq!(batch.failed, vec!["counter", "Missing"]);
+        assert!(batch.code.contains("ScrollArea,\n    Dialog"));
+        assert!(batch.code.contains("let Hooks = {\n    CopyHook\n};"));
+        assert!(batch.code.contains("let counter = 0;"));
+    }
+
+    #[test]
+    fn test_extend_var_object_properties_to_ast() {
+        let code = "let opts = { timeout: 1000, retries };";
+
+        let result = extend_var_object_properties_to_ast(
+            code,
+            "opts",
+            vec![
+                ("Hooks".to_string(), None),
+                ("timeout".to_string(), Some("3000".to_string())),
+                (
+                    "params".to_string(),
+                    Some("{ _csrf_token: csrfToken }".to_string()),
+                ),
+                ("retries".to_string(), None),
+            ],
+        );
+        assert!(result.is_ok(), "Extending failed: {:?}", result.err());
+        let output = result.unwrap();
+
+        assert!(output.contains("timeout: 1000"));
+        assert!(!output.contains("timeout: 3000"));
+        assert!(output.contains("Hooks,"));
+        assert!(output.contains("_csrf_token: csrfToken"));
+        assert_eq!(output.matches("retries").count(), 1);
+
+        let result = extend_var_object_properties_to_ast(
+            code,
+            "opts",
+            vec![("broken".to_string(), Some("1 +".to_string()))],
+        );
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_extend_var_object_properties_to_ast_quotes_keys() {
+        let code = r#"let opts = { "Hook": MyHook };"#;
+
+        let result = extend_var_object_properties_to_ast(
+            code,
+            "opts",
+            vec![
+                ("Hook".to_string(), Some("OtherHook".to_string())),
+                ("data-id".to_string(), Some("1".to_string())),
+                ("2x".to_string(), Some("true".to_string())),
+            ],
+        );
+        assert!(result.is_ok(), "Extending failed: {:?}", result.err());
+        let output = result.unwrap();
+
+        assert!(!output.contains("OtherHook"));
+        assert!(output.contains(r#""data-id": 1"#));
+        assert!(output.contains(r#""2x": true"#));
+
+        let result =
+            extend_var_object_properties_to_ast(code, "opts", vec![("data-id".to_string(), None)]);
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_insert_statement_after_imports_ast() {
+        let code = r#"
+            import { Socket } from "phoenix";
+            import { LiveSocket } from "phoenix_live_view";
+
+            let liveSocket = new LiveSocket("/live", Socket, {});
+            liveSocket.connect();
+            "#;
+
+        let result = insert_statement_after_imports_ast(
+            code,
+            "window.liveSocket = liveSocket;\nconsole.log(\"ready\");",
+        );
+        assert!(result.is_ok(), "Inserting failed: {:?}", result.err());
+        let output = result.unwrap();
+
+        let position = |needle: &str| output.find(needle).expect(needle);
+        assert!(position("phoenix_live_view") < position("window.liveSocket = liveSocket;"));
+        assert!(position("window.liveSocket") < position("console.log(\"ready\");"));
+        assert!(position("console.log") < position("let liveSocket"));
+        assert!(output.trim_end().ends_with("liveSocket.connect();"));
+
+        let result = insert_statement_after_imports_ast("foo();\n", "\"use strict\";");
+        assert_eq!(result, Ok("\"use strict\";\nfoo();\n".to_string()));
+
+        assert!(insert_statement_after_imports_ast(code, "window.liveSocket = ;").is_err());
+        assert!(insert_statement_after_imports_ast(code, "import { a } from \"a\";").is_err());
+        assert!(insert_statement_after_imports_ast(code, "  ").is_err());
+        assert!(insert_statement_after_imports_ast("let x = ;", "foo();").is_err());
+    }
+
+    #[test]
+    fn test_top_level_await_round_trips() {
+        let code = r#"
+            import { Socket } from "phoenix";
+
+            const config = await fetch("/config.json").then((response) => response.json());
+            for await (const chunk of stream) {
+              console.log(chunk);
+            }
+            "#;
+
+        assert!(validate_js_from_ast(code).is_ok());
+
+        let result =
+            insert_import_to_ast(code, "import { LiveSocket } from \"phoenix_live_view\";");
+        assert!(result.is_ok(), "Inserting failed: {:?}", result.err());
+        let output = result.unwrap();
+        assert!(output.contains("import { LiveSocket } from \"phoenix_live_view\";"));
+        assert!(output.contains("const config = await fetch(\"/config.json\")"));
+        assert!(output.contains("for await (const chunk of stream)"));
 
-    use super::*;
+        let result = remove_import_from_ast(&output, "import { Socket } from \"phoenix\";");
+        assert!(result.is_ok(), "Removing failed: {:?}", result.err());
+        assert!(result.unwrap().contains("await fetch"));
+    }
 
     #[test]
-    fn test_is_module_imported_from_ast() {
+    fn test_ensure_window_event_listener_ast() {
         let code = r#"
-            import "phoenix_html";
-            import { Socket, SocketV1 } from "phoenix";
-            import { TS } from "tsobject";
+            import topbar from "../vendor/topbar";
+            window.addEventListener("phx:page-loading-start", (_info) => topbar.show(300));
+            "#;
 
-            // This is first test we need to have
-            console.log("We are here");
+        let result = ensure_window_event_listener_ast(
+            code,
+            "phx:page-loading-stop",
+            "(_info) => topbar.hide()",
+        );
+        assert!(result.is_ok(), "Inserting failed: {:?}", result.err());
+        let output = result.unwrap();
+        assert!(output.trim_end().ends_with(
+            "window.addEventListener(\"phx:page-loading-stop\", (_info)=>topbar.hide());"
+        ));
 
-            const min = ()          => {return "Shahryar" + "Tavakkoli"};
-            "#;
+        let again = ensure_window_event_listener_ast(
+            &output,
+            "phx:page-loading-stop",
+            "(_info) => topbar.hide()",
+        )
+        .unwrap();
+        assert_eq!(again, output);
+        assert_eq!(again.matches("phx:page-loading-stop").count(), 1);
 
-        let import = r#"
-                import "phoenix_html";
-                import { Socket, SocketV1 } from "phoenix";
-                import { TS } from "tsobject";
-            "#;
-        let result = is_module_imported_from_ast(code, import);
+        let result = ensure_window_event_listener_ast(
+            code,
+            "phx:page-loading-start",
+            "(_info) => topbar.show(500)",
+        )
+        .unwrap();
+        assert!(!result.contains("show(500)"));
 
-        assert!(result.is_ok(), "Expected Ok(true), but got {:?}", result);
+        assert!(ensure_window_event_listener_ast(code, "load", "() =>").is_err());
+    }
 
-        let import = r#"
-                import { NoneRepeated } from "orepeat";
-            "#;
-        let result = is_module_imported_from_ast(code, import);
-        assert!(result.is_err(), "Expected Ok(true), but got {:?}", result);
+    #[test]
+    fn test_module_import_presence_from_ast() {
+        let code = r#"
+            import { Socket } from "phoenix";
 
-        let import = r#"
-                import "phoenix_html";
-                import { NoneRepeated } from "orepeat";
-                import { TS } from "tsobject";
+            const loadChart = () => import("./chart.js").then((chart) => chart.default);
             "#;
-        let result = is_module_imported_from_ast(code, import);
 
-        assert!(result.is_err(), "Expected Ok(true), but got {:?}", result);
+        assert_eq!(
+            module_import_presence_from_ast(code, "phoenix", true),
+            Ok(ImportPresence::Static)
+        );
+        assert_eq!(
+            module_import_presence_from_ast(code, "./chart.js", true),
+            Ok(ImportPresence::Dynamic)
+        );
+        assert_eq!(
+            module_import_presence_from_ast(code, "./chart.js", false),
+            Err(false)
+        );
+        assert_eq!(
+            module_import_presence_from_ast(code, "phoenix_live_view", true),
+            Err(false)
+        );
+        assert!(is_module_imported_from_ast(code, "import chart from \"./chart.js\";").is_err());
+        assert_eq!(
+            module_import_presence_from_ast("let x = ;", "phoenix", true),
+            Err(false)
+        );
     }
+
     #[test]
-    fn test_insert_import_to_ast() {
+    fn test_get_function_source_from_ast() {
         let code = r#"
-            import "phoenix_html";
-            import { Socket, SocketV1 } from "phoenix";
-            import { TS } from "tsobject";
-            import ScrollArea from "./scrollArea.js";
+            import { Socket } from "phoenix";
 
-            // This is first test we need to have
-            console.log("We are here");
+            // Adds two numbers
+            function add(first, second) {
+                return first + second;
+            }
 
-            const min = ()          => {return "Shahryar" + "Tavakkoli"};
+            const double = (value) => value * 2, other = 1;
+            export function setup() {}
+            const answer = 42;
             "#;
 
-        let import = r#"
-                import "phoenix_html";
-                import { Socket, SocketV1 } from "phoenix";
-                import { TS } from "tsobject";
-                import { NoneRepeated } from "orepeat";
-                import ScrollArea from "./scrollArea.js";
-            "#;
-        let result = insert_import_to_ast(code, import).expect("Failed to generate code");
+        assert_eq!(
+            get_function_source_from_ast(code, "add"),
+            Ok(
+                "// Adds two numbers\nfunction add(first, second) {\n    return first + second;\n}"
+                    .to_string()
+            )
+        );
+        assert_eq!(
+            get_function_source_from_ast(code, "double"),
+            Ok("const double = (value)=>value * 2;".to_string())
+        );
+        assert_eq!(
+            get_function_source_from_ast(code, "setup"),
+            Ok("function setup() {}".to_string())
+        );
 
-        assert!(result.contains("import \"phoenix_html\";"));
-        assert!(result.contains("import { Socket, SocketV1 } from \"phoenix\";"));
-        assert!(result.contains("import { TS } from \"tsobject\";"));
-        assert!(result.contains("import { NoneRepeated } from \"orepeat\";"));
+        assert!(get_function_source_from_ast(code, "answer").is_err());
+        assert!(get_function_source_from_ast(code, "missing").is_err());
+        assert!(get_function_source_from_ast("let x = ;", "add").is_err());
+    }
 
-        let imports_start = result.find("import \"phoenix_html\";").unwrap();
-        let imports_end = result
-            .find("import { NoneRepeated } from \"orepeat\";")
-            .unwrap();
-        assert!(imports_start < imports_end);
+    #[test]
+    fn test_list_todo_comments_from_ast() {
+        let code = include_str!("../../../../../test/assets/todoComments.js");
 
-        assert!(result.contains("// This is first test we need to have"));
+        let todos = list_todo_comments_from_ast(code, &DEFAULT_TODO_KEYWORDS).unwrap();
+        assert_eq!(
+            todos,
+            vec![
+                TodoComment {
+                    keyword: "TODO".to_string(),
+                    text: "TODO: move the socket setup into its own module".to_string(),
+                    line: 3,
+                },
+                TodoComment {
+                    keyword: "FIXME".to_string(),
+                    text: "FIXME the fallback is too short, see TODO below".to_string(),
+                    line: 6,
+                },
+                TodoComment {
+                    keyword: "TODO".to_string(),
+                    text: "TODO(topbar): read the color from the theme".to_string(),
+                    line: 11,
+                },
+            ]
+        );
 
-        println!("{}", result)
+        let todos = list_todo_comments_from_ast(code, &["HACK"]).unwrap();
+        assert!(todos.is_empty());
+
+        assert!(list_todo_comments_from_ast("let x = ;", &DEFAULT_TODO_KEYWORDS).is_err());
     }
 
     #[test]
-    fn test_remove_import_from_ast() {
+    fn test_extract_string_literals_from_ast() {
+        let code = r#"import { Socket } from "phoenix";
+const labels = { "title": "Welcome back", ["sub" + "title"]: 'Sign in' };
+const topbar = import("../vendor/topbar");
+alert("Saved!");
+"#;
+
+        let result = extract_string_literals_from_ast(code);
+        assert!(result.is_ok(), "Extracting failed: {:?}", result.err());
+
+        let literal = |value: &str, line| StringLiteral {
+            value: value.to_string(),
+            line,
+        };
+        assert_eq!(
+            result.unwrap(),
+            vec![
+                literal("Welcome back", 2),
+                literal("sub", 2),
+                literal("title", 2),
+                literal("Sign in", 2),
+                literal("Saved!", 4),
+            ]
+        );
+
+        assert!(extract_string_literals_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_detect_js_features_from_ast() {
         let code = r#"
-            import "phoenix_html";
-            import { Socket, SocketV1 } from "phoenix";
-            import { TS } from "tsobject";
+        const token = document.querySelector("meta")?.getAttribute("content");
+        const timeout = window.config?.timeout ?? 2500;
+        async function load() {
+            await import("./hooks.js");
+        }
+        "#;
 
-            // This is first test we need to have
-            console.log("We are here");
+        let features = detect_js_features_from_ast(code).unwrap();
+        assert_eq!(
+            features,
+            JSFeatures {
+                optional_chaining: true,
+                nullish_coalescing: true,
+                top_level_await: false,
+                dynamic_import: true,
+            }
+        );
 
-            const min = ()          => {return "Shahryar" + "Tavakkoli"};
-            "#;
+        let code = r#"
+        const hooks = await import("./hooks.js");
+        let socket;
+        socket ??= connect();
+        "#;
+        let features = detect_js_features_from_ast(code).unwrap();
+        assert!(features.top_level_await);
+        assert!(features.nullish_coalescing);
+        assert!(!features.optional_chaining);
 
-        let import = r#"
-                import { TS } from "tsobject";
-                import { Socket, SocketV1 } from "phoenix";
-                import { NoneRepeated } from "orepeat";
-                import { NoneRepeated1 } from "orepeat1";
-            "#;
-        let result = remove_import_from_ast(code, import).expect("Failed to generate code");
+        assert_eq!(
+            detect_js_features_from_ast("let a = b.c || 1;").unwrap(),
+            JSFeatures::default()
+        );
+        assert!(detect_js_features_from_ast("let x = ;").is_err());
+    }
 
-        assert!(result.contains("import \"phoenix_html\";"));
-        assert!(!result.contains("import { Socket, SocketV1 } from \"phoenix\";"));
-        assert!(!result.contains("import { TS } from \"tsobject\";"));
+    #[test]
+    fn test_insert_import_to_ast_with_quote_style() {
+        let code = "import { Socket } from 'phoenix';\nimport topbar from \"../vendor/topbar\";\n";
+        let import = "import { LiveSocket } from \"phoenix_live_view\";";
+
+        let result = insert_import_to_ast(code, import).unwrap();
+        assert!(result.contains("import { Socket } from 'phoenix';"));
+        assert!(result.contains("import topbar from \"../vendor/topbar\";"));
+        assert!(result.contains("import { LiveSocket } from \"phoenix_live_view\";"));
+
+        let result =
+            insert_import_to_ast_with_quote_style(code, import, QuoteStyle::Single).unwrap();
+        assert!(result.contains("import { Socket } from 'phoenix';"));
+        assert!(result.contains("import topbar from \"../vendor/topbar\";"));
+        assert!(result.contains("import { LiveSocket } from 'phoenix_live_view';"));
+
+        let import = "import Hooks from './it\\'s.js';";
+        let result =
+            insert_import_to_ast_with_quote_style(code, import, QuoteStyle::Double).unwrap();
+        assert!(result.contains("import Hooks from \"./it's.js\";"));
 
+        let result =
+            insert_import_to_ast_with_quote_style(code, import, QuoteStyle::AsWritten).unwrap();
+        assert!(result.contains("import Hooks from './it\\'s.js';"));
+    }
+
+    #[test]
+    fn test_ensure_global_assignment_ast() {
         let code = r#"
-        import { foo } from "module-name";
-        import bar from "another-module";
-        let Hooks = {};
+        let liveSocket = new LiveSocket("/live", Socket, {});
+        liveSocket.connect();
         "#;
 
-        let result = remove_import_from_ast(code, "import bar from \"another-module\";")
-            .expect("Failed to generate code");
+        let result = ensure_global_assignment_ast(code, "window.liveSocket", "liveSocket").unwrap();
+        assert!(result
+            .trim_end()
+            .ends_with("window.liveSocket = liveSocket;"));
 
-        println!("{}", result);
+        let again =
+            ensure_global_assignment_ast(&result, "window.liveSocket", "liveSocket").unwrap();
+        assert_eq!(again.matches("window.liveSocket =").count(), 1);
+
+        let code = r#"window["liveSocket"] = liveSocket;"#;
+        let result = ensure_global_assignment_ast(code, "window.liveSocket", "socket").unwrap();
+        assert_eq!(result.matches(" = ").count(), 1);
+
+        let result = ensure_global_assignment_ast(code, "window.socket", "socket").unwrap();
+        assert!(result.contains("window.socket = socket;"));
+
+        assert!(ensure_global_assignment_ast(code, "window.f()", "1").is_err());
+        assert!(ensure_global_assignment_ast("let x = ;", "window.a", "1").is_err());
     }
 
     #[test]
-    fn test_statistics_from_ast() {
+    fn test_find_import_by_local_ast() {
         let code = r#"
-            import { foo } from 'bar';
-            import * as jar from 'jar';
-            console.log('Start JS file');
-            class Foo {
-                constructor() {
-                    debugger;
-                    console.log('Hello');
-                }
+        import topbar from "../vendor/topbar";
+        import { Socket as PhxSocket, Presence } from "phoenix";
+        import * as Hooks from "./hooks";
+        "#;
+
+        assert_eq!(
+            find_import_by_local_ast(code, "topbar").unwrap(),
+            ImportInfo {
+                source: "../vendor/topbar".to_string(),
+                kind: ImportBindingKind::Default,
+                imported: None,
             }
-            function bar() {
-                console.log('World');
-                debugger;
+        );
+        assert_eq!(
+            find_import_by_local_ast(code, "PhxSocket").unwrap(),
+            ImportInfo {
+                source: "phoenix".to_string(),
+                kind: ImportBindingKind::Named,
+                imported: Some("Socket".to_string()),
             }
-        "#;
-        let parsed = statistics_from_ast(code).unwrap();
-        assert_eq!(parsed.functions, 1);
-        assert_eq!(parsed.classes, 1);
-        assert_eq!(parsed.debuggers, 2);
-        assert_eq!(parsed.imports, 2);
-        assert_eq!(parsed.trys, 0);
-        assert_eq!(parsed.throws, 0);
+        );
+        assert_eq!(
+            find_import_by_local_ast(code, "Presence").unwrap().imported,
+            Some("Presence".to_string())
+        );
+        assert_eq!(
+            find_import_by_local_ast(code, "Hooks").unwrap(),
+            ImportInfo {
+                source: "./hooks".to_string(),
+                kind: ImportBindingKind::Namespace,
+                imported: None,
+            }
+        );
+
+        assert!(find_import_by_local_ast(code, "Socket").is_err());
+        assert!(find_import_by_local_ast("let x = ;", "topbar").is_err());
     }
 
     #[test]
-    fn test_extend_var_object_property_by_names_to_ast() {
-        let code = r#"
-            const Components = {...Hoks, PreOrderd};
+    fn test_convert_default_export_to_named_ast() {
+        let code = "const Components = {};\nexport default Components;\n";
+        assert_eq!(
+            convert_default_export_to_named_ast(code, None).unwrap(),
+            "const Components = {};\nexport { Components };\n"
+        );
 
-            // Export the components as default
-            export default Components;
-            "#;
+        let code = "export default function Foo() {\n    return 1;\n}\n";
+        assert_eq!(
+            convert_default_export_to_named_ast(code, None).unwrap(),
+            "export function Foo() {\n    return 1;\n}\n"
+        );
 
-        let object_names = [
-            "...ExtendedObject".to_string(),
-            "ObjectOne".to_string(),
-            "PreOrderd".to_string(),
-            "CopyCodeHooks".to_string(),
-            "...Hoks".to_string(),
-            "ObjectOne".to_string(),
-        ];
-        let unique_names: HashSet<String> = object_names.into_iter().collect();
-        let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
-        vec_of_strs.sort();
+        let code = "export default class Bar {}\n";
+        let result = convert_default_export_to_named_ast(code, None).unwrap();
+        assert!(result.starts_with("export class Bar {"));
 
-        let result =
-            extend_var_object_property_by_names_to_ast(code, "Components", vec_of_strs.clone());
-        assert!(result.is_ok());
-        println!("{}", result.unwrap());
+        let code = "const Foo = 1;\nexport { Foo as default };\n";
+        assert_eq!(
+            convert_default_export_to_named_ast(code, None).unwrap(),
+            "const Foo = 1;\nexport { Foo };\n"
+        );
 
-        let result =
-            extend_var_object_property_by_names_to_ast(code, "NoneComponent", vec_of_strs.clone());
-        assert!(result.is_err());
+        assert!(convert_default_export_to_named_ast("export const a = 1;", None).is_err());
+        assert!(convert_default_export_to_named_ast("let x = ;", None).is_err());
+    }
 
-        let code = r#"
-            const Components = () => {1 + 1};
+    #[test]
+    fn test_convert_anonymous_default_export_to_named_ast() {
+        assert_eq!(
+            convert_default_export_to_named_ast("export default function () {}\n", Some("setup"))
+                .unwrap(),
+            "export function setup() {}\n"
+        );
+        assert!(
+            convert_default_export_to_named_ast("export default class {}\n", None)
+                .unwrap()
+                .starts_with("export class defaultExport {")
+        );
+        assert_eq!(
+            convert_default_export_to_named_ast("export default { a: 1 };\n", Some("config"))
+                .unwrap(),
+            "const config = {\n    a: 1\n};\nexport { config };\n"
+        );
+    }
 
-            // Export the components as default
-            export default Components;
-            "#;
+    #[test]
+    fn test_parse_with_diagnostics_from_ast() {
+        let (code, warnings) =
+            parse_with_diagnostics_from_ast("let scope = {};\nwith (scope) {}\n").unwrap();
+        assert_eq!(code, "let scope = {};\nwith (scope) {}\n");
+        assert_eq!(warnings.len(), 1);
+
+        let (_code, warnings) = parse_with_diagnostics_from_ast("let a = 1;").unwrap();
+        assert!(warnings.is_empty());
+        assert!(parse_with_diagnostics_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_insert_import_if_missing_ast() {
+        let code = r#"import { Socket } from "phoenix";
+let socket = new Socket("/socket");
+"#;
+
+        let (output, inserted) =
+            insert_import_if_missing_ast(code, r#"import { Socket } from "phoenix";"#).unwrap();
+        assert!(!inserted);
+        assert_eq!(output, code);
+
+        let (output, inserted) =
+            insert_import_if_missing_ast(code, r#"import topbar from "../vendor/topbar";"#)
+                .unwrap();
+        assert!(inserted);
+        assert!(output.contains(r#"import topbar from "../vendor/topbar";"#));
+        assert!(
+            is_module_imported_from_ast(&output, r#"import topbar from "../vendor/topbar";"#)
+                .unwrap()
+        );
+
+        assert!(insert_import_if_missing_ast("let x = ;", r#"import "a";"#).is_err());
+    }
+
+    #[test]
+    fn test_insert_import_to_ast_preserving_blank_lines() {
+        let code = include_str!("../../../../../test/assets/importGroups.js");
+        let import = "import { initDarkMode } from \"../vendor/darkmode\";";
+
+        let options = EmitOptions {
+            preserve_blank_lines: true,
+            ..Default::default()
+        };
+        let result = insert_import_to_ast_with_options(code, import, &options).unwrap();
+        assert!(result.contains(
+            "import { LiveSocket } from \"phoenix_live_view\";\n\nimport topbar from \"../vendor/topbar\";\n"
+        ));
+        assert!(result.contains(
+            "import CopyMixInstallationHook from \"../vendor/mixCopy\";\nimport { initDarkMode } from \"../vendor/darkmode\";"
+        ));
+        assert_eq!(result.matches("\n\n").count(), 1);
+
+        let result = insert_import_to_ast(code, import).unwrap();
+        assert!(!result.contains("\n\n"));
+    }
+
+    #[test]
+    fn test_extend_object_at_path_ast() {
+        let code = r#"const config = { params: { token: csrfToken }, debug: false };"#;
+
+        let result = extend_object_at_path_ast(
+            code,
+            "config",
+            vec!["params".to_string(), "user".to_string()],
+            ["userId", "...extra"],
+        )
+        .unwrap();
+        assert!(result.contains("token: csrfToken,"));
+        assert!(result.contains("user: {\n            userId,\n            ...extra\n        }"));
+
+        let result = extend_object_at_path_ast(
+            &result,
+            "config",
+            vec!["params".to_string(), "user".to_string()],
+            ["userId"],
+        )
+        .unwrap();
+        assert_eq!(result.matches("userId").count(), 1);
 
         let result =
-            extend_var_object_property_by_names_to_ast(code, "Components", vec_of_strs.clone());
-        assert!(result.is_err());
+            extend_object_at_path_ast(code, "config", vec!["params".to_string()], ["locale"])
+                .unwrap();
+        assert!(result.contains("token: csrfToken,\n        locale\n"));
 
-        let code = r#"
-            import ScrollArea from "./scrollArea.js";
+        assert!(
+            extend_object_at_path_ast(code, "config", vec!["debug".to_string()], ["x"]).is_err()
+        );
+        assert!(
+            extend_object_at_path_ast(code, "missing", vec!["params".to_string()], ["x"]).is_err()
+        );
+    }
 
-            const Components = {
-              ScrollArea,
-            };
+    #[test]
+    fn test_extend_object_at_path_ast_with_quoted_keys() {
+        let code = r#"const config = { "params": { token: csrfToken } };"#;
 
-            export default Components;
+        let result = extend_object_at_path_ast(
+            code,
+            "config",
+            vec!["params".to_string(), "data-attrs".to_string()],
+            ["userId"],
+        )
+        .unwrap();
+        assert_eq!(result.matches("params").count(), 1);
+        assert!(result.contains(r#""data-attrs": {"#));
+    }
+
+    #[test]
+    fn test_count_sloc_from_ast() {
+        let code = include_str!("../../../../../test/assets/slocSample.js");
+        assert_eq!(count_sloc_from_ast(code), Ok(6));
+
+        assert_eq!(count_sloc_from_ast(""), Ok(0));
+        assert_eq!(count_sloc_from_ast("// only a comment\n\n"), Ok(0));
+        assert!(count_sloc_from_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_extend_var_object_property_by_names_to_ast_with_status() {
+        let code = "const Components = {};\nexport default Components;\n";
+
+        let (status, output) =
+            extend_var_object_property_by_names_to_ast_with_status(code, "Components", ["Hook"])
+                .unwrap();
+        assert_eq!(status, FindCondition::Found);
+        assert!(output.contains("Hook"));
+
+        let (status, output) =
+            extend_var_object_property_by_names_to_ast_with_status(code, "Missing", ["Hook"])
+                .unwrap();
+        assert!(matches!(status, FindCondition::NotFound(_)));
+        assert_eq!(output, code);
+
+        let code = "const Components = 1;";
+        let (status, output) =
+            extend_var_object_property_by_names_to_ast_with_status(code, "Components", ["Hook"])
+                .unwrap();
+        assert!(matches!(status, FindCondition::FoundError(_)));
+        assert_eq!(output, code);
+
+        assert!(
+            extend_var_object_property_by_names_to_ast_with_status("let x = ;", "x", ["Hook"])
+                .is_err()
+        );
+    }
+
+    #[test]
+    fn test_decorated_classes() {
+        let code = r#"
+            @Component({ selector: "app-root" })
+            export class AppComponent {
+                @Input() title = "app";
+            }
+            @Injectable()
+            class AppService {}
             "#;
 
-        let object_names = ["ScrollArea", "NoneComponent"];
+        let statistics = statistics_from_ast(code).unwrap();
+        assert_eq!(statistics.classes, 2);
 
-        let result = extend_var_object_property_by_names_to_ast(code, "Components", object_names);
-        assert!(result.is_ok());
+        let output =
+            insert_import_to_ast(code, r#"import { Input } from "@angular/core";"#).unwrap();
+        assert!(output.contains("@Component({"));
+        assert!(output.contains("@Input()"));
+        assert!(output.contains("@Injectable()"));
+    }
 
+    #[test]
+    fn test_remove_export_from_ast() {
         let code = r#"
-            import ScrollArea from "./scrollArea.js";
+            const a = 1;
+            const b = 2;
+            export { a, b };
+            export { c } from "./c";
+            "#;
 
-            const Components = {};
+        let output = remove_export_from_ast(code, "a").unwrap();
+        assert!(output.contains("export { b };"));
+        assert!(output.contains("const a = 1;"));
 
-            export default Components;
+        let output = remove_export_from_ast(&output, "b").unwrap();
+        assert!(!output.contains("export { b"));
+        assert!(output.contains("export { c } from \"./c\";"));
+
+        let code = r#"
+            export const name = "app";
+            export function setup() {}
+            export class Store {}
+            export const first = 1, second = 2;
             "#;
 
-        let object_names = ["ScrollArea", "NoneComponent", "...NoneComponent"];
-        let result = extend_var_object_property_by_names_to_ast(code, "Components", object_names);
+        let output = remove_export_from_ast(code, "name").unwrap();
+        assert!(output.contains("const name = \"app\";"));
+        assert!(!output.contains("export const name"));
 
-        assert!(result.is_ok());
+        let output = remove_export_from_ast(code, "setup").unwrap();
+        assert!(output.contains("function setup() {}"));
+        assert!(!output.contains("export function setup"));
+
+        let output = remove_export_from_ast(code, "Store").unwrap();
+        assert!(!output.contains("export class Store"));
+
+        let output = remove_export_from_ast(code, "second").unwrap();
+        assert!(output.contains("export const first = 1;\nconst second = 2;"));
+
+        // The declarators keep their order, so `second` can still read `first`
+        let code = "export const first = 1, second = first + 1, third = 3;\n";
+        let output = remove_export_from_ast(code, "second").unwrap();
+        assert_eq!(
+            output,
+            "export const first = 1;\nconst second = first + 1;\nexport const third = 3;\n"
+        );
+        let output = remove_export_from_ast(code, "first").unwrap();
+        let expected = "const first = 1;\nexport const second = first + 1, third = 3;";
+        assert!(output.starts_with(expected), "{}", output);
+
+        // The other names of a destructuring pattern stay exported
+        let code = "export const { a, name } = obj;\n";
+        let output = remove_export_from_ast(code, "name").unwrap();
+        assert_eq!(output, "const { a, name } = obj;\nexport { a };\n");
+
+        assert!(remove_export_from_ast(code, "missing").is_err());
     }
 
     #[test]
-    fn test_contains_variable_from_ast() {
+    fn test_guard_console_logs_ast() {
         let code = r#"
-            let liveSocket = new LiveSocket("/live", Socket, {
-              hooks: { ...Hooks, CopyMixInstallationHook },
-              longPollFallbackMs: 2500,
-              params: { _csrf_token: csrfToken },
-            });
+            console.log("booting");
+            let socket = connect();
+            console.log("connected", socket);
+            console.error("never guarded");
+            if (window.DEBUG) {
+                console.log("already guarded");
+            }
             "#;
 
-        let result = contains_variable_from_ast(code, "liveSocket");
+        let output = guard_console_logs_ast(code, "window.DEBUG").unwrap();
+        assert_eq!(output.matches("if (window.DEBUG)").count(), 3);
+        assert!(output.contains("if (window.DEBUG) {\n    console.log(\"booting\");\n}"));
+        assert!(output.contains("if (window.DEBUG) {\n    console.log(\"connected\", socket);\n}"));
+        assert!(output.contains("}\nconsole.error(\"never guarded\");"));
+
+        let output = guard_console_logs_ast(&output, "window.DEBUG").unwrap();
+        assert_eq!(output.matches("if (window.DEBUG)").count(), 3);
+
+        assert!(guard_console_logs_ast(code, "window.").is_err());
+    }
+
+    #[test]
+    fn test_import_edges_from_ast() {
+        let code = include_str!("../../../../../test/assets/importEdges.js");
+
+        let edge = |to: &str| ImportEdge {
+            from: "assets/js/app.js".to_string(),
+            to: to.to_string(),
+        };
+        assert_eq!(
+            import_edges_from_ast(code, "assets/js/app.js").unwrap(),
+            vec![
+                edge("phoenix_html"),
+                edge("phoenix"),
+                edge("../vendor/topbar")
+            ]
+        );
+
+        assert!(import_edges_from_ast("let x = ;", "app.js").is_err());
+    }
+
+    #[test]
+    fn test_editors_on_empty_input() {
+        use crate::helpers::EMPTY_SOURCE_ERROR;
+
+        // Editors with nothing to clean up or reorder are a no-op
+        assert_eq!(remove_import_from_ast("", "phoenix"), Ok("".to_string()));
+        assert_eq!(normalize_imports_ast(""), Ok("".to_string()));
+        assert_eq!(hoist_imports_ast(""), Ok("".to_string()));
+        assert_eq!(minify_js(""), Ok("".to_string()));
+        assert_eq!(strip_comments_js(""), Ok("".to_string()));
+        assert_eq!(remove_debugger_statements_ast(""), Ok("".to_string()));
+        assert_eq!(guard_console_logs_ast("", "DEBUG"), Ok("".to_string()));
+
+        // Editors that add code return only the added code
+        assert_eq!(
+            insert_import_to_ast("", "import { Socket } from \"phoenix\";"),
+            Ok("import { Socket } from \"phoenix\";\n".to_string())
+        );
+        assert_eq!(
+            insert_statement_after_imports_ast("", "let Hooks = {};"),
+            Ok("let Hooks = {};\n".to_string())
+        );
+        assert_eq!(
+            insert_import_if_missing_ast("", "import { Socket } from \"phoenix\";"),
+            Ok(("import { Socket } from \"phoenix\";\n".to_string(), true))
+        );
+        assert_eq!(
+            insert_import_to_ast_with_quote_style("", "import a from \"a\";", QuoteStyle::Single),
+            Ok("import a from 'a';\n".to_string())
+        );
+        assert_eq!(
+            ensure_window_event_listener_ast("", "resize", "handleResize"),
+            Ok("window.addEventListener(\"resize\", handleResize);\n".to_string())
+        );
+        assert_eq!(
+            ensure_global_assignment_ast("", "window.liveSocket", "liveSocket"),
+            Ok("window.liveSocket = liveSocket;\n".to_string())
+        );
 
-        println!("{:#?}", result.unwrap())
+        // Editors that need an edit target fail with a clear message
+        let empty = Err(EMPTY_SOURCE_ERROR.to_string());
+        assert_eq!(
+            extend_var_object_property_by_names_to_ast("", "Hooks", vec!["CopyHook"]),
+            empty
+        );
+        assert_eq!(
+            extend_object_at_path_ast("", "config", vec!["hooks".to_string()], vec!["CopyHook"]),
+            empty
+        );
+        let properties = vec![("CopyHook".to_string(), None)];
+        assert_eq!(
+            extend_var_object_properties_to_ast("", "Hooks", properties),
+            empty
+        );
+        assert_eq!(convert_default_export_to_named_ast("", None), empty);
+        assert_eq!(remove_export_from_ast("", "add"), empty);
+        assert_eq!(wrap_in_try_catch_ast("", "add"), empty);
+        assert_eq!(rename_function_ast("", "add", "sum"), empty);
+        assert_eq!(rename_identifier_ast("", "total", "sum"), empty);
+        assert_eq!(rename_identifier_ast(" \n", "total", "sum"), empty);
+        assert_eq!(
+            insert_import_before_ast("", "import a from \"a\";", "phoenix"),
+            empty
+        );
+        let result = extend_var_object_property_by_names_to_ast_with_status("", "Hooks", ["A"]);
+        assert_eq!(result.err().as_deref(), Some(EMPTY_SOURCE_ERROR));
+        let extensions = vec![("Hooks".to_string(), vec!["CopyHook".to_string()])];
+        let result = extend_var_objects_batch_ast("", extensions);
+        assert_eq!(result.err().as_deref(), Some(EMPTY_SOURCE_ERROR));
     }
 }
 
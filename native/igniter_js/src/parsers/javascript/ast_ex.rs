@@ -3,10 +3,11 @@ use std::collections::HashSet;
 use crate::atoms;
 use crate::helpers::encode_response;
 use crate::parsers::javascript::ast::*;
+use crate::parsers::javascript::helpers::{EmitOptions, NewLine, SemicolonStyle};
 use crate::parsers::javascript::phoenix::*;
-use rustler::{Env, NifResult, NifStruct, NifTaggedEnum, Term};
+use rustler::{Env, NifMap, NifResult, NifStruct, NifTaggedEnum, Term};
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn is_module_imported_from_ast_nif(
     env: Env,
     file_content: String,
@@ -21,31 +22,138 @@ pub fn is_module_imported_from_ast_nif(
     encode_response(env, status, fn_atom, result)
 }
 
-#[rustler::nif]
+#[derive(Debug, NifTaggedEnum)]
+pub enum ImportPresenceResultType {
+    Static,
+    Dynamic,
+}
+
+impl From<ImportPresence> for ImportPresenceResultType {
+    fn from(presence: ImportPresence) -> Self {
+        match presence {
+            ImportPresence::Static => ImportPresenceResultType::Static,
+            ImportPresence::Dynamic => ImportPresenceResultType::Dynamic,
+        }
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn module_import_presence_from_ast_nif(
+    env: Env,
+    file_content: String,
+    module_source: String,
+    include_dynamic: bool,
+) -> NifResult<Term> {
+    let fn_atom = atoms::module_import_presence_from_ast_nif();
+    match module_import_presence_from_ast(&file_content, &module_source, include_dynamic) {
+        Ok(presence) => encode_response(
+            env,
+            atoms::ok(),
+            fn_atom,
+            ImportPresenceResultType::from(presence),
+        ),
+        Err(_) => encode_response(env, atoms::error(), fn_atom, false),
+    }
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum SemicolonStyleType {
+    Keep,
+    Always,
+    AsNeeded,
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum NewLineType {
+    Lf,
+    Crlf,
+}
+
+/// The [`EmitOptions`] passed from Elixir as a map, e.g.
+/// `%{semicolons: :always, new_line: :lf, preserve_blank_lines: false, ascii_only: false,
+/// preserve_formatting: false}`.
+#[derive(Debug, NifMap)]
+pub struct EmitOptionsType {
+    pub semicolons: SemicolonStyleType,
+    pub new_line: NewLineType,
+    pub preserve_blank_lines: bool,
+    pub ascii_only: bool,
+    pub preserve_formatting: bool,
+}
+
+impl From<EmitOptionsType> for EmitOptions {
+    fn from(options: EmitOptionsType) -> Self {
+        let semicolons = match options.semicolons {
+            SemicolonStyleType::Keep => SemicolonStyle::Keep,
+            SemicolonStyleType::Always => SemicolonStyle::Always,
+            SemicolonStyleType::AsNeeded => SemicolonStyle::AsNeeded,
+        };
+        let new_line = match options.new_line {
+            NewLineType::Lf => NewLine::Lf,
+            NewLineType::Crlf => NewLine::CrLf,
+        };
+
+        EmitOptions {
+            semicolons,
+            new_line,
+            preserve_blank_lines: options.preserve_blank_lines,
+            ascii_only: options.ascii_only,
+            preserve_formatting: options.preserve_formatting,
+            ..Default::default()
+        }
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn insert_import_to_ast_nif(
     env: Env,
     file_content: String,
     import_lines: String,
+    options: EmitOptionsType,
 ) -> NifResult<Term> {
-    let (status, result) = match insert_import_to_ast(&file_content, &import_lines) {
-        Ok(updated_code) => (atoms::ok(), updated_code),
-        Err(error_msg) => (atoms::error(), error_msg),
-    };
+    let options = options.into();
+    let (status, result) =
+        match insert_import_to_ast_with_options(&file_content, &import_lines, &options) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
 
     encode_response(env, status, atoms::insert_import_to_ast_nif(), result)
 }
 
-#[rustler::nif]
-fn remove_import_from_ast_nif(env: Env, file_content: String, modules: String) -> NifResult<Term> {
-    let (status, result) = match remove_import_from_ast(&file_content, &modules) {
-        Ok(updated_code) => (atoms::ok(), updated_code),
-        Err(error_msg) => (atoms::error(), error_msg),
-    };
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_import_to_ast_with_spans_nif(
+    env: Env,
+    file_content: String,
+    import_lines: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::insert_import_to_ast_with_spans_nif();
+    match insert_import_to_ast_with_spans(&file_content, &import_lines) {
+        Ok(updated_code_with_spans) => {
+            encode_response(env, atoms::ok(), fn_atom, updated_code_with_spans)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+fn remove_import_from_ast_nif(
+    env: Env,
+    file_content: String,
+    modules: String,
+    options: EmitOptionsType,
+) -> NifResult<Term> {
+    let options = options.into();
+    let (status, result) =
+        match remove_import_from_ast_with_options(&file_content, &modules, &options) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
 
     encode_response(env, status, atoms::remove_import_from_ast_nif(), result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn find_live_socket_node_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
     let fn_atom = atoms::find_live_socket_node_from_ast();
 
@@ -57,7 +165,35 @@ pub fn find_live_socket_node_from_ast_nif(env: Env, file_content: String) -> Nif
     encode_response(env, status, fn_atom, result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn has_socket_connect_from_ast_nif(
+    env: Env,
+    file_content: String,
+    socket_var_name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::has_socket_connect_from_ast_nif();
+
+    let (status, result) = match has_socket_connect_from_ast(&file_content, &socket_var_name) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn has_csrf_token_setup_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::has_csrf_token_setup_from_ast_nif();
+
+    let (status, result) = match has_csrf_token_setup_from_ast(&file_content) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn contains_variable_from_ast_nif(
     env: Env,
     file_content: String,
@@ -73,24 +209,75 @@ pub fn contains_variable_from_ast_nif(
     encode_response(env, status, fn_atom, result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn extend_hook_object_to_ast_nif(
     env: Env,
     file_content: String,
     names: Vec<String>,
+    options: EmitOptionsType,
 ) -> NifResult<Term> {
     let unique_names: HashSet<String> = names.into_iter().collect();
     let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
     vec_of_strs.sort();
-    let (status, result) = match extend_hook_object_to_ast(&file_content, vec_of_strs) {
-        Ok(updated_code) => (atoms::ok(), updated_code),
-        Err(error_msg) => (atoms::error(), error_msg),
-    };
+    let options = options.into();
+    let (status, result) =
+        match extend_hook_object_to_ast_with_options(&file_content, vec_of_strs, &options) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
 
     encode_response(env, status, atoms::extend_hook_object_to_ast_nif(), result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_import_if_missing_ast_nif(
+    env: Env,
+    file_content: String,
+    import_lines: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::insert_import_if_missing_ast_nif();
+    match insert_import_if_missing_ast(&file_content, &import_lines) {
+        Ok(result) => encode_response(env, atoms::ok(), fn_atom, result),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum LiveSocketTargetType {
+    Only,
+    Name(String),
+    Index(usize),
+}
+
+impl LiveSocketTargetType {
+    fn as_target(&self) -> LiveSocketTarget<'_> {
+        match self {
+            LiveSocketTargetType::Only => LiveSocketTarget::Only,
+            LiveSocketTargetType::Name(name) => LiveSocketTarget::Name(name),
+            LiveSocketTargetType::Index(index) => LiveSocketTarget::Index(*index),
+        }
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_hook_object_to_ast_for_socket_nif(
+    env: Env,
+    file_content: String,
+    names: Vec<String>,
+    target: LiveSocketTargetType,
+) -> NifResult<Term> {
+    let fn_atom = atoms::extend_hook_object_to_ast_for_socket_nif();
+    let unique_names: HashSet<String> = names.into_iter().collect();
+    let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+    vec_of_strs.sort();
+
+    match extend_hook_object_to_ast_for_socket(&file_content, vec_of_strs, target.as_target()) {
+        Ok(updated_code) => encode_response(env, atoms::ok(), fn_atom, updated_code),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
 fn remove_objects_of_hooks_from_ast_nif(
     env: Env,
     file_content: String,
@@ -115,6 +302,31 @@ pub struct ASTStatisticsResult {
     pub imports: usize,
     pub trys: usize,
     pub throws: usize,
+    pub exports: usize,
+    pub consts: usize,
+    pub lets: usize,
+    pub vars: usize,
+    pub line_comments: usize,
+    pub block_comments: usize,
+}
+
+impl From<ASTStatistics> for ASTStatisticsResult {
+    fn from(statistics: ASTStatistics) -> Self {
+        Self {
+            imports: statistics.imports,
+            classes: statistics.classes,
+            debuggers: statistics.debuggers,
+            functions: statistics.functions,
+            throws: statistics.throws,
+            trys: statistics.trys,
+            exports: statistics.exports,
+            consts: statistics.consts,
+            lets: statistics.lets,
+            vars: statistics.vars,
+            line_comments: statistics.line_comments,
+            block_comments: statistics.block_comments,
+        }
+    }
 }
 
 #[derive(Debug, NifTaggedEnum)]
@@ -123,21 +335,14 @@ pub enum ASTStatisticsResultType {
     Error(String),
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 fn statistics_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
     let fn_atom = atoms::statistics_from_ast_nif();
 
     let (status, result) = match statistics_from_ast(&file_content) {
-        Ok(updated_code) => (
+        Ok(statistics) => (
             atoms::ok(),
-            ASTStatisticsResultType::Statistics(ASTStatisticsResult {
-                imports: updated_code.imports,
-                classes: updated_code.classes,
-                debuggers: updated_code.debuggers,
-                functions: updated_code.functions,
-                throws: updated_code.throws,
-                trys: updated_code.trys,
-            }),
+            ASTStatisticsResultType::Statistics(statistics.into()),
         ),
         Err(error_msg) => (atoms::error(), ASTStatisticsResultType::Error(error_msg)),
     };
@@ -145,7 +350,57 @@ fn statistics_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
     encode_response(env, status, fn_atom, result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_var_object_property_by_names_to_ast_with_status_nif(
+    env: Env,
+    file_content: String,
+    var_name: String,
+    object_names: Vec<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::extend_var_object_property_by_names_to_ast_with_status_nif();
+    let unique_names: HashSet<String> = object_names.into_iter().collect();
+    let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+    vec_of_strs.sort();
+
+    match extend_var_object_property_by_names_to_ast_with_status(
+        &file_content,
+        &var_name,
+        vec_of_strs,
+    ) {
+        Ok((FindCondition::Found, code)) => {
+            let message = FindCondition::Found.message().to_string();
+            encode_response(env, atoms::ok(), fn_atom, (code, message))
+        }
+        Ok((find, code)) => encode_response(
+            env,
+            atoms::error(),
+            fn_atom,
+            (code, find.message().to_string()),
+        ),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, (file_content, error_msg)),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_object_at_path_ast_nif(
+    env: Env,
+    file_content: String,
+    var_name: String,
+    path: Vec<String>,
+    object_names: Vec<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::extend_object_at_path_ast_nif();
+    let unique_names: HashSet<String> = object_names.into_iter().collect();
+    let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+    vec_of_strs.sort();
+
+    match extend_object_at_path_ast(&file_content, &var_name, path, vec_of_strs) {
+        Ok(updated_code) => encode_response(env, atoms::ok(), fn_atom, updated_code),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn extend_var_object_property_by_names_to_ast_nif(
     env: Env,
     file_content: String,
@@ -169,3 +424,891 @@ pub fn extend_var_object_property_by_names_to_ast_nif(
         result,
     )
 }
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn minify_js_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let (status, result) = match minify_js(&file_content) {
+        Ok(minified_code) => (atoms::ok(), minified_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::minify_js_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn strip_comments_js_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let (status, result) = match strip_comments_js(&file_content) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::strip_comments_js_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn ast_fingerprint_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let (status, result) = match ast_fingerprint(&file_content) {
+        Ok(fingerprint) => (atoms::ok(), fingerprint),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::ast_fingerprint_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn contains_export_from_ast_nif(
+    env: Env,
+    file_content: String,
+    name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::contains_export_from_ast_nif();
+
+    let (status, result) = match contains_export_from_ast(&file_content, &name) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn wrap_in_try_catch_ast_nif(
+    env: Env,
+    file_content: String,
+    target_fn_name: String,
+) -> NifResult<Term> {
+    let (status, result) = match wrap_in_try_catch_ast(&file_content, &target_fn_name) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::wrap_in_try_catch_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn remove_debugger_statements_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::remove_debugger_statements_ast_nif();
+    match remove_debugger_statements_ast_with_count(&file_content) {
+        Ok(updated_code_with_count) => {
+            encode_response(env, atoms::ok(), fn_atom, updated_code_with_count)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn rename_identifier_ast_nif(
+    env: Env,
+    file_content: String,
+    old_name: String,
+    new_name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::rename_identifier_ast_nif();
+    match rename_identifier_ast(&file_content, &old_name, &new_name) {
+        Ok(updated_code) => encode_response(env, atoms::ok(), fn_atom, updated_code),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn rename_function_ast_nif(
+    env: Env,
+    file_content: String,
+    old_name: String,
+    new_name: String,
+) -> NifResult<Term> {
+    let (status, result) = match rename_function_ast(&file_content, &old_name, &new_name) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::rename_function_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_import_before_ast_nif(
+    env: Env,
+    file_content: String,
+    import_lines: String,
+    before_source: String,
+) -> NifResult<Term> {
+    let (status, result) =
+        match insert_import_before_ast(&file_content, &import_lines, &before_source) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
+
+    encode_response(env, status, atoms::insert_import_before_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_import_to_ast_would_change_nif(
+    env: Env,
+    file_content: String,
+    import_lines: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::insert_import_to_ast_would_change_nif();
+    match insert_import_to_ast_would_change(&file_content, &import_lines) {
+        Ok(changed) => encode_response(env, atoms::ok(), fn_atom, changed),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn remove_import_from_ast_would_change_nif(
+    env: Env,
+    file_content: String,
+    modules: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::remove_import_from_ast_would_change_nif();
+    match remove_import_from_ast_would_change(&file_content, &modules) {
+        Ok(changed) => encode_response(env, atoms::ok(), fn_atom, changed),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_hook_object_to_ast_would_change_nif(
+    env: Env,
+    file_content: String,
+    names: Vec<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::extend_hook_object_to_ast_would_change_nif();
+    let vec_of_strs: Vec<&str> = names.iter().map(|s| s.as_str()).collect();
+    match extend_hook_object_to_ast_would_change(&file_content, vec_of_strs) {
+        Ok(changed) => encode_response(env, atoms::ok(), fn_atom, changed),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn remove_objects_of_hooks_from_ast_would_change_nif(
+    env: Env,
+    file_content: String,
+    object_names: Vec<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::remove_objects_of_hooks_from_ast_would_change_nif();
+    let vec_of_strs: Vec<&str> = object_names.iter().map(|s| s.as_str()).collect();
+    match remove_objects_of_hooks_from_ast_would_change(&file_content, vec_of_strs) {
+        Ok(changed) => encode_response(env, atoms::ok(), fn_atom, changed),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum ModuleKindResultType {
+    Esm,
+    CommonJs,
+    Script,
+}
+
+impl From<ModuleKind> for ModuleKindResultType {
+    fn from(kind: ModuleKind) -> Self {
+        match kind {
+            ModuleKind::Esm => ModuleKindResultType::Esm,
+            ModuleKind::CommonJs => ModuleKindResultType::CommonJs,
+            ModuleKind::Script => ModuleKindResultType::Script,
+        }
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn detect_module_kind_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::detect_module_kind_from_ast_nif();
+    match detect_module_kind_from_ast(&file_content) {
+        Ok(kind) => encode_response(env, atoms::ok(), fn_atom, ModuleKindResultType::from(kind)),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.ImportLocation"]
+pub struct ImportLocationResult {
+    pub source: String,
+    pub line: usize,
+    pub column: usize,
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.ASTDetailedStatisticsResult"]
+pub struct ASTDetailedStatisticsResult {
+    pub statistics: ASTStatisticsResult,
+    pub imports: Vec<ImportLocationResult>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn statistics_detailed_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::statistics_detailed_from_ast_nif();
+    match statistics_detailed_from_ast(&file_content) {
+        Ok(detailed) => {
+            let result = ASTDetailedStatisticsResult {
+                statistics: detailed.statistics.into(),
+                imports: detailed
+                    .imports
+                    .into_iter()
+                    .map(|import| ImportLocationResult {
+                        source: import.source,
+                        line: import.line,
+                        column: import.column,
+                    })
+                    .collect(),
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.TodoComment"]
+pub struct TodoCommentResult {
+    pub keyword: String,
+    pub text: String,
+    pub line: usize,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn count_sloc_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::count_sloc_from_ast_nif();
+    match count_sloc_from_ast(&file_content) {
+        Ok(sloc) => encode_response(env, atoms::ok(), fn_atom, sloc),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn list_todo_comments_from_ast_nif(
+    env: Env,
+    file_content: String,
+    keywords: Vec<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::list_todo_comments_from_ast_nif();
+    let keywords: Vec<&str> = keywords.iter().map(|keyword| keyword.as_str()).collect();
+
+    match list_todo_comments_from_ast(&file_content, &keywords) {
+        Ok(todos) => {
+            let result: Vec<TodoCommentResult> = todos
+                .into_iter()
+                .map(|todo| TodoCommentResult {
+                    keyword: todo.keyword,
+                    text: todo.text,
+                    line: todo.line,
+                })
+                .collect();
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.StringLiteral"]
+pub struct StringLiteralResult {
+    pub value: String,
+    pub line: usize,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extract_string_literals_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::extract_string_literals_from_ast_nif();
+
+    match extract_string_literals_from_ast(&file_content) {
+        Ok(literals) => {
+            let result: Vec<StringLiteralResult> = literals
+                .into_iter()
+                .map(|literal| StringLiteralResult {
+                    value: literal.value,
+                    line: literal.line,
+                })
+                .collect();
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.JSFeatures"]
+pub struct JSFeaturesResult {
+    pub optional_chaining: bool,
+    pub nullish_coalescing: bool,
+    pub top_level_await: bool,
+    pub dynamic_import: bool,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn detect_js_features_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::detect_js_features_from_ast_nif();
+
+    match detect_js_features_from_ast(&file_content) {
+        Ok(features) => {
+            let result = JSFeaturesResult {
+                optional_chaining: features.optional_chaining,
+                nullish_coalescing: features.nullish_coalescing,
+                top_level_await: features.top_level_await,
+                dynamic_import: features.dynamic_import,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum QuoteStyleType {
+    AsWritten,
+    Single,
+    Double,
+}
+
+impl From<QuoteStyleType> for QuoteStyle {
+    fn from(quote_style: QuoteStyleType) -> Self {
+        match quote_style {
+            QuoteStyleType::AsWritten => QuoteStyle::AsWritten,
+            QuoteStyleType::Single => QuoteStyle::Single,
+            QuoteStyleType::Double => QuoteStyle::Double,
+        }
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_import_to_ast_with_quote_style_nif(
+    env: Env,
+    file_content: String,
+    import_lines: String,
+    quote_style: QuoteStyleType,
+) -> NifResult<Term> {
+    let fn_atom = atoms::insert_import_to_ast_with_quote_style_nif();
+    let (status, result) = match insert_import_to_ast_with_quote_style(
+        &file_content,
+        &import_lines,
+        quote_style.into(),
+    ) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[derive(Debug, NifTaggedEnum)]
+pub enum ImportBindingKindType {
+    Default,
+    Named,
+    Namespace,
+}
+
+impl From<ImportBindingKind> for ImportBindingKindType {
+    fn from(kind: ImportBindingKind) -> Self {
+        match kind {
+            ImportBindingKind::Default => ImportBindingKindType::Default,
+            ImportBindingKind::Named => ImportBindingKindType::Named,
+            ImportBindingKind::Namespace => ImportBindingKindType::Namespace,
+        }
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.ImportInfo"]
+pub struct ImportInfoResult {
+    pub source: String,
+    pub kind: ImportBindingKindType,
+    pub imported: Option<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn find_import_by_local_ast_nif(
+    env: Env,
+    file_content: String,
+    local_name: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::find_import_by_local_ast_nif();
+    match find_import_by_local_ast(&file_content, &local_name) {
+        Ok(info) => {
+            let result = ImportInfoResult {
+                source: info.source,
+                kind: info.kind.into(),
+                imported: info.imported,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.ImportEdge"]
+pub struct ImportEdgeResult {
+    pub from: String,
+    pub to: String,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn import_edges_from_ast_nif(
+    env: Env,
+    file_content: String,
+    from_label: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::import_edges_from_ast_nif();
+    match import_edges_from_ast(&file_content, &from_label) {
+        Ok(edges) => {
+            let result: Vec<ImportEdgeResult> = edges
+                .into_iter()
+                .map(|edge| ImportEdgeResult {
+                    from: edge.from,
+                    to: edge.to,
+                })
+                .collect();
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.JSDoc"]
+pub struct JsDocResult {
+    pub name: String,
+    pub doc: Option<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extract_jsdoc_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::extract_jsdoc_from_ast_nif();
+    match extract_jsdoc_from_ast(&file_content) {
+        Ok(docs) => {
+            let result: Vec<JsDocResult> = docs
+                .into_iter()
+                .map(|doc| JsDocResult {
+                    name: doc.name,
+                    doc: doc.doc,
+                })
+                .collect();
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn convert_default_export_to_named_ast_nif(
+    env: Env,
+    file_content: String,
+    name: Option<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::convert_default_export_to_named_ast_nif();
+    let (status, result) = match convert_default_export_to_named_ast(&file_content, name.as_deref())
+    {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn remove_export_from_ast_nif(env: Env, file_content: String, name: String) -> NifResult<Term> {
+    let fn_atom = atoms::remove_export_from_ast_nif();
+    let (status, result) = match remove_export_from_ast(&file_content, &name) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn parse_with_diagnostics_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::parse_with_diagnostics_nif();
+    match parse_with_diagnostics_from_ast(&file_content) {
+        Ok(result) => encode_response(env, atoms::ok(), fn_atom, result),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn normalize_imports_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let (status, result) = match normalize_imports_ast(&file_content) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::normalize_imports_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn hoist_imports_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let (status, result) = match hoist_imports_ast(&file_content) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::hoist_imports_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn find_duplicate_import_sources_from_ast_nif(
+    env: Env,
+    file_content: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::find_duplicate_import_sources_from_ast_nif();
+    match find_duplicate_import_sources_from_ast(&file_content) {
+        Ok(sources) => encode_response(env, atoms::ok(), fn_atom, sources),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn is_import_used_ast_nif(env: Env, file_content: String, source: String) -> NifResult<Term> {
+    let fn_atom = atoms::is_import_used_ast_nif();
+    let (status, result) = match is_import_used_ast(&file_content, &source) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn count_node_kinds_from_ast_nif(
+    env: Env,
+    file_content: String,
+    kinds: Vec<String>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::count_node_kinds_from_ast_nif();
+    match count_node_kinds_from_ast(&file_content, kinds) {
+        Ok(counts) => encode_response(env, atoms::ok(), fn_atom, counts),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn validate_js_from_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::validate_js_from_ast_nif();
+    match validate_js_from_ast(&file_content) {
+        Ok(()) => encode_response(env, atoms::ok(), fn_atom, true),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.ExtendVarObjectsResult"]
+pub struct ExtendVarObjectsResult {
+    pub code: String,
+    pub extended: Vec<String>,
+    pub failed: Vec<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_var_objects_batch_ast_nif(
+    env: Env,
+    file_content: String,
+    extensions: Vec<(String, Vec<String>)>,
+) -> NifResult<Term> {
+    let fn_atom = atoms::extend_var_objects_batch_ast_nif();
+    match extend_var_objects_batch_ast(&file_content, extensions) {
+        Ok(batch) => {
+            let result = ExtendVarObjectsResult {
+                code: batch.code,
+                extended: batch.extended,
+                failed: batch.failed,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_var_object_properties_to_ast_nif(
+    env: Env,
+    file_content: String,
+    var_name: String,
+    properties: Vec<(String, Option<String>)>,
+) -> NifResult<Term> {
+    let (status, result) =
+        match extend_var_object_properties_to_ast(&file_content, &var_name, properties) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
+
+    encode_response(
+        env,
+        status,
+        atoms::extend_var_object_properties_to_ast_nif(),
+        result,
+    )
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn insert_statement_after_imports_ast_nif(
+    env: Env,
+    file_content: String,
+    statement_source: String,
+) -> NifResult<Term> {
+    let (status, result) =
+        match insert_statement_after_imports_ast(&file_content, &statement_source) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
+
+    encode_response(
+        env,
+        status,
+        atoms::insert_statement_after_imports_ast_nif(),
+        result,
+    )
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn add_hook_ast_nif(
+    env: Env,
+    file_content: String,
+    import_line: String,
+    hook_name: String,
+) -> NifResult<Term> {
+    let (status, result) = match add_hook_ast(&file_content, &import_line, &hook_name) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::add_hook_ast_nif(), result)
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.HookExtensionResult"]
+pub struct HookExtensionResult {
+    pub code: String,
+    pub added: Vec<String>,
+    pub skipped: Vec<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_hook_object_to_ast_with_report_nif(
+    env: Env,
+    file_content: String,
+    names: Vec<String>,
+) -> NifResult<Term> {
+    let unique_names: HashSet<String> = names.into_iter().collect();
+    let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+    vec_of_strs.sort();
+
+    let fn_atom = atoms::extend_hook_object_to_ast_with_report_nif();
+    match extend_hook_object_to_ast_with_report(&file_content, vec_of_strs) {
+        Ok(report) => {
+            let result = HookExtensionResult {
+                code: report.code,
+                added: report.added,
+                skipped: report.skipped,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn extend_hook_object_to_ast_strict_nif(
+    env: Env,
+    file_content: String,
+    names: Vec<String>,
+) -> NifResult<Term> {
+    let unique_names: HashSet<String> = names.into_iter().collect();
+    let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+    vec_of_strs.sort();
+
+    let fn_atom = atoms::extend_hook_object_to_ast_strict_nif();
+    match extend_hook_object_to_ast_strict(&file_content, vec_of_strs) {
+        Ok(updated_code) => encode_response(env, atoms::ok(), fn_atom, updated_code),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.HookRemovalResult"]
+pub struct HookRemovalResult {
+    pub code: String,
+    pub removed: Vec<String>,
+    pub not_found: Vec<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn remove_objects_of_hooks_from_ast_with_report_nif(
+    env: Env,
+    file_content: String,
+    object_names: Vec<String>,
+) -> NifResult<Term> {
+    let unique_names: HashSet<String> = object_names.into_iter().collect();
+    let mut vec_of_strs: Vec<&str> = unique_names.iter().map(|s| s.as_str()).collect();
+    vec_of_strs.sort();
+
+    let fn_atom = atoms::remove_objects_of_hooks_from_ast_with_report_nif();
+    match remove_objects_of_hooks_from_ast_with_report(&file_content, vec_of_strs) {
+        Ok(report) => {
+            let result = HookRemovalResult {
+                code: report.code,
+                removed: report.removed,
+                not_found: report.not_found,
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn guard_console_logs_ast_nif(
+    env: Env,
+    file_content: String,
+    flag_expr: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::guard_console_logs_ast_nif();
+    let (status, result) = match guard_console_logs_ast(&file_content, &flag_expr) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn ensure_global_assignment_ast_nif(
+    env: Env,
+    file_content: String,
+    lhs: String,
+    rhs: String,
+) -> NifResult<Term> {
+    let fn_atom = atoms::ensure_global_assignment_ast_nif();
+    let (status, result) = match ensure_global_assignment_ast(&file_content, &lhs, &rhs) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn hook_exists_in_ast_nif(
+    env: Env,
+    file_content: String,
+    hook_name: String,
+    target: LiveSocketTargetType,
+) -> NifResult<Term> {
+    let fn_atom = atoms::hook_exists_in_ast_nif();
+    let (status, result) = match hook_exists_in_ast(&file_content, &hook_name, target.as_target()) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn get_live_socket_options_from_ast_nif(
+    env: Env,
+    file_content: String,
+    target: LiveSocketTargetType,
+) -> NifResult<Term> {
+    let fn_atom = atoms::get_live_socket_options_from_ast_nif();
+    match get_live_socket_options_from_ast(&file_content, target.as_target()) {
+        Ok(options) => encode_response(env, atoms::ok(), fn_atom, options),
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[derive(Debug, NifStruct)]
+#[module = "IgniterJs.Native.Parsers.Javascript.LiveSocketDiagnosis"]
+pub struct LiveSocketDiagnosisResult {
+    pub live_socket_declared: bool,
+    pub new_live_socket: bool,
+    pub has_options: bool,
+    pub has_hooks: bool,
+    pub problem: Option<String>,
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn diagnose_live_socket_from_ast_nif(
+    env: Env,
+    file_content: String,
+    target: LiveSocketTargetType,
+) -> NifResult<Term> {
+    let fn_atom = atoms::diagnose_live_socket_from_ast_nif();
+    match diagnose_live_socket_from_ast(&file_content, target.as_target()) {
+        Ok(diagnosis) => {
+            let result = LiveSocketDiagnosisResult {
+                live_socket_declared: diagnosis.live_socket_declared,
+                new_live_socket: diagnosis.new_live_socket,
+                has_options: diagnosis.has_options,
+                has_hooks: diagnosis.has_hooks,
+                problem: diagnosis.problem().map(str::to_string),
+            };
+            encode_response(env, atoms::ok(), fn_atom, result)
+        }
+        Err(error_msg) => encode_response(env, atoms::error(), fn_atom, error_msg),
+    }
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn ensure_window_event_listener_ast_nif(
+    env: Env,
+    file_content: String,
+    event_name: String,
+    handler_source: String,
+) -> NifResult<Term> {
+    let (status, result) =
+        match ensure_window_event_listener_ast(&file_content, &event_name, &handler_source) {
+            Ok(updated_code) => (atoms::ok(), updated_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        };
+
+    encode_response(
+        env,
+        status,
+        atoms::ensure_window_event_listener_ast_nif(),
+        result,
+    )
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn replace_hook_spread_ast_nif(
+    env: Env,
+    file_content: String,
+    old_spread: String,
+    new_spread: String,
+) -> NifResult<Term> {
+    let (status, result) = match replace_hook_spread_ast(&file_content, &old_spread, &new_spread) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::replace_hook_spread_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn normalize_hooks_order_ast_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let (status, result) = match normalize_hooks_order_ast(&file_content) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, atoms::normalize_hooks_order_ast_nif(), result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn get_function_source_from_ast_nif(
+    env: Env,
+    file_content: String,
+    fn_name: String,
+) -> NifResult<Term> {
+    let (status, result) = match get_function_source_from_ast(&file_content, &fn_name) {
+        Ok(source) => (atoms::ok(), source),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(
+        env,
+        status,
+        atoms::get_function_source_from_ast_nif(),
+        result,
+    )
+}
@@ -2,9 +2,141 @@ use biome_formatter::{IndentStyle, IndentWidth};
 use biome_js_formatter::context::JsFormatOptions;
 use biome_js_formatter::format_node;
 use biome_js_parser::{parse, JsParserOptions};
-use biome_js_syntax::{JsFileSource, ModuleKind};
+use biome_js_syntax::{
+    AnyJsImportClause, AnyJsModuleItem, AnyJsRoot, JsFileSource, JsImport, JsModuleSource,
+    ModuleKind,
+};
+use biome_rowan::AstNode;
+
+/// Options of [`format_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct FormatOptions {
+    /// Sorts the leading block of imports by their source before formatting, see
+    /// [`organize_imports`].
+    pub organize_imports: bool,
+}
 
 pub fn format(source_code: &str) -> Result<String, String> {
+    format_with_options(source_code, &FormatOptions::default())
+}
+
+/// Same as [`format`], applying the given [`FormatOptions`] first.
+pub fn format_with_options(source_code: &str, options: &FormatOptions) -> Result<String, String> {
+    if options.organize_imports {
+        let organized = organize_imports(source_code)?;
+        return format_source(&organized);
+    }
+
+    format_source(source_code)
+}
+
+/// The module source of an import statement, and whether it is a side-effect import
+/// (`import "module"`) without bindings.
+fn import_source(import: &JsImport) -> (String, bool) {
+    let side_effect = matches!(
+        import.import_clause(),
+        Ok(AnyJsImportClause::JsImportBareClause(_))
+    );
+    let source = import
+        .syntax()
+        .descendants()
+        .find_map(JsModuleSource::cast)
+        .and_then(|source| source.value_token().ok())
+        .map(|token| token.text_trimmed().trim_matches(['"', '\'']).to_string())
+        .unwrap_or_default();
+
+    (source, side_effect)
+}
+
+/// Where the comments attached to an import start: the lines right above it, back to a blank
+/// line or the end of the previous statement. `leading_start..start` is its leading trivia.
+fn attached_comments_start(source_code: &str, leading_start: usize, start: usize) -> usize {
+    let mut attached = start;
+    let mut offset = start;
+    for line in source_code[leading_start..start]
+        .split_inclusive('\n')
+        .rev()
+    {
+        offset -= line.len();
+        if line.ends_with('\n') && line.trim().is_empty() {
+            break;
+        }
+        attached = offset;
+    }
+
+    attached
+}
+
+/// Sorts the imports at the top of the module by their source, so the import block comes
+/// out in the same order whatever order they were added in. Side-effect imports such as
+/// `import "phoenix_html"` may depend on their position, so they stay where they are and
+/// only the imports between them are sorted. Comments directly above an import or at the
+/// end of its line move with it, blank lines keep their place.
+pub fn organize_imports(source_code: &str) -> Result<String, String> {
+    let parsed = parse(
+        source_code,
+        JsFileSource::default().with_module_kind(ModuleKind::Module),
+        JsParserOptions::default(),
+    );
+
+    if parsed.has_errors() {
+        return Err("Parsing failed due to syntax errors.".into());
+    }
+
+    let AnyJsRoot::JsModule(module) = parsed.tree() else {
+        return Err("Parsing failed due to syntax errors.".into());
+    };
+
+    // (start, end, source, side effect) of every import of the leading block, with its
+    // attached comments
+    let imports: Vec<(usize, usize, String, bool)> = module
+        .items()
+        .into_iter()
+        .map_while(|item| match item {
+            AnyJsModuleItem::JsImport(import) => Some(import),
+            _ => None,
+        })
+        .map(|import| {
+            let range = import.syntax().text_range();
+            let trimmed_start = usize::from(import.syntax().text_trimmed_range().start());
+            let (source, side_effect) = import_source(&import);
+            (
+                attached_comments_start(source_code, usize::from(range.start()), trimmed_start),
+                usize::from(range.end()),
+                source,
+                side_effect,
+            )
+        })
+        .collect();
+
+    let mut sorted: Vec<&str> = Vec::with_capacity(imports.len());
+    let mut group: Vec<(&str, &str)> = Vec::new();
+    for (start, end, source, side_effect) in &imports {
+        let import = &source_code[*start..*end];
+        if *side_effect {
+            group.sort_by_key(|(source, _)| *source);
+            sorted.extend(group.drain(..).map(|(_, import)| import));
+            sorted.push(import);
+        } else {
+            group.push((source.as_str(), import));
+        }
+    }
+    group.sort_by_key(|(source, _)| *source);
+    sorted.extend(group.drain(..).map(|(_, import)| import));
+
+    let mut output = String::with_capacity(source_code.len());
+    let mut position = 0;
+    for ((start, end, _, _), import) in imports.iter().zip(sorted) {
+        output.push_str(&source_code[position..*start]);
+        output.push_str(import);
+        position = *end;
+    }
+    output.push_str(&source_code[position..]);
+
+    Ok(output)
+}
+
+fn format_source(source_code: &str) -> Result<String, String> {
     let parsed = parse(
         source_code,
         JsFileSource::default().with_module_kind(ModuleKind::Module),
@@ -33,11 +165,118 @@ pub fn is_formatted(source_code: &str) -> Result<bool, String> {
     Ok(formatted_code.trim() == source_code.trim())
 }
 
+/// Checks whether the indentation of the raw source mixes tabs and spaces, which
+/// [`format`] would normalize to spaces.
+///
+/// Blank lines are ignored, and so are the ` * ` continuation lines of block comments,
+/// which are aligned with a space after the indentation of the comment. It is mixed when
+/// some lines are indented with tabs and others with spaces, or when a tab follows a space
+/// in the indentation of a line. Spaces after the leading tabs are alignment and don't
+/// count.
+pub fn has_mixed_indentation(source_code: &str) -> Result<bool, String> {
+    let mut tabs = false;
+    let mut spaces = false;
+
+    for line in source_code.lines() {
+        let code = line.trim_start_matches([' ', '\t']);
+        if code.is_empty() || code.starts_with('*') {
+            continue;
+        }
+
+        let indent = &line[..line.len() - code.len()];
+        let tab_indent = indent.trim_start_matches('\t');
+        if tab_indent.contains('\t') {
+            return Ok(true);
+        }
+
+        tabs |= indent.starts_with('\t');
+        spaces |= indent.starts_with(' ');
+    }
+
+    Ok(tabs && spaces)
+}
+
+/// Formats only the top-level statements overlapping the `start_byte..end_byte` range and
+/// splices them back, so everything outside of them stays byte-for-byte identical and a small
+/// edit doesn't turn into a whole-file diff. A statement is taken as a whole, e.g. a range
+/// inside a function body formats that function.
+pub fn format_range(
+    source_code: &str,
+    start_byte: usize,
+    end_byte: usize,
+) -> Result<String, String> {
+    if start_byte > end_byte || end_byte > source_code.len() {
+        return Err(format!(
+            "Invalid range {}..{} for a source of {} bytes.",
+            start_byte,
+            end_byte,
+            source_code.len()
+        ));
+    }
+
+    let parsed = parse(
+        source_code,
+        JsFileSource::default().with_module_kind(ModuleKind::Module),
+        JsParserOptions::default(),
+    );
+
+    if parsed.has_errors() {
+        return Err("Parsing failed due to syntax errors.".into());
+    }
+
+    let AnyJsRoot::JsModule(module) = parsed.tree() else {
+        return Err("Parsing failed due to syntax errors.".into());
+    };
+
+    // An empty range still selects the statement the cursor is in.
+    let range_end = end_byte.max(start_byte + 1);
+    let overlapping: Vec<(usize, usize)> = module
+        .items()
+        .into_iter()
+        .map(|item| {
+            let range = item.syntax().text_trimmed_range();
+            (usize::from(range.start()), usize::from(range.end()))
+        })
+        .filter(|(item_start, item_end)| *item_start < range_end && *item_end > start_byte)
+        .collect();
+
+    let (Some((region_start, _)), Some((_, region_end))) =
+        (overlapping.first(), overlapping.last())
+    else {
+        return Ok(source_code.to_string());
+    };
+
+    let formatted = format(&source_code[*region_start..*region_end])?;
+
+    Ok(format!(
+        "{}{}{}",
+        &source_code[..*region_start],
+        formatted.trim_end(),
+        &source_code[*region_end..]
+    ))
+}
+
+/// Reads the file at `path` on the Rust side and formats its content, so big sources don't
+/// have to be copied across the NIF boundary.
+pub fn format_file(path: &str) -> Result<String, String> {
+    let source_code =
+        std::fs::read_to_string(path).map_err(|err| format!("Failed to read {}: {}", path, err))?;
+
+    format(&source_code)
+}
+
+/// Formats several sources one after another, keeping the input order in the output.
+/// A failing source does not stop the rest of the batch.
+pub fn format_batch(sources: &[String]) -> Vec<Result<String, String>> {
+    sources.iter().map(|source| format(source)).collect()
+}
+
 #[cfg(test)]
-mod tests {
+pub(crate) mod tests {
     use super::*;
 
-    fn app_js() -> &'static str {
+    /// The sample Phoenix `app.js` shared by the JavaScript parser tests.
+    pub(crate) fn app_js() -> &'static str {
         r##"
         // If you want to use Phoenix channels, run `mix help phx.gen.channel`
         // to get started and then uncomment the line below.
@@ -114,4 +353,123 @@ mod tests {
         let formatted = format(js_code_formatted).unwrap();
         assert_eq!(is_formatted(&formatted).unwrap(), true);
     }
+
+    #[test]
+    fn test_format_batch_js() {
+        let sources = vec![
+            "let a=1".to_string(),
+            "let b = ;".to_string(),
+            "function c(){return 1}".to_string(),
+        ];
+
+        let results = format_batch(&sources);
+        assert_eq!(results.len(), 3);
+        assert_eq!(results[0], Ok("let a = 1;\n".to_string()));
+        assert!(results[1].is_err());
+        assert_eq!(
+            results[2],
+            Ok("function c() {\n  return 1;\n}\n".to_string())
+        );
+    }
+
+    #[test]
+    fn test_format_file_js() {
+        let path = std::env::temp_dir().join("igniter_js_format_file_test.js");
+        std::fs::write(&path, "let a=1").unwrap();
+        assert_eq!(
+            format_file(path.to_str().unwrap()),
+            Ok("let a = 1;\n".to_string())
+        );
+
+        std::fs::write(&path, "let b = ;").unwrap();
+        assert!(format_file(path.to_str().unwrap()).is_err());
+        std::fs::remove_file(&path).unwrap();
+
+        let missing = format_file("/none/existing/path/file.js");
+        assert!(missing.unwrap_err().starts_with("Failed to read"));
+    }
+
+    #[test]
+    fn test_format_range_js() {
+        let code = "function a(){return   1}\n\nfunction b(){\n  const x={y:1};\n  return x}\n\nfunction c(){return   3}\n";
+        let start = code.find("const x").unwrap();
+
+        let formatted = format_range(code, start, start + 5).unwrap();
+        assert_eq!(
+            formatted,
+            "function a(){return   1}\n\nfunction b() {\n  const x = { y: 1 };\n  return x;\n}\n\nfunction c(){return   3}\n"
+        );
+
+        let untouched = code.find("\n\nfunction b").unwrap() + 1;
+        assert_eq!(format_range(code, untouched, untouched + 1).unwrap(), code);
+        assert!(format_range(code, 10, 5).is_err());
+        assert!(format_range(code, 0, code.len() + 1).is_err());
+        assert!(format_range("let b = ;", 0, 3).is_err());
+    }
+
+    #[test]
+    fn test_format_with_organize_imports() {
+        let code = "import topbar from \"../vendor/topbar\";\nimport \"phoenix_html\";\nimport { Socket } from 'phoenix';\nimport MishkaComponents from \"../vendor/mishka_components.js\";\nimport { LiveSocket } from \"phoenix_live_view\";\nlet a = 1;\n";
+
+        let options = FormatOptions {
+            organize_imports: true,
+        };
+        assert_eq!(
+            format_with_options(code, &options).unwrap(),
+            "import topbar from \"../vendor/topbar\";\nimport \"phoenix_html\";\nimport MishkaComponents from \"../vendor/mishka_components.js\";\nimport { Socket } from \"phoenix\";\nimport { LiveSocket } from \"phoenix_live_view\";\nlet a = 1;\n"
+        );
+
+        assert_eq!(
+            format(code).unwrap(),
+            "import topbar from \"../vendor/topbar\";\nimport \"phoenix_html\";\nimport { Socket } from \"phoenix\";\nimport MishkaComponents from \"../vendor/mishka_components.js\";\nimport { LiveSocket } from \"phoenix_live_view\";\nlet a = 1;\n"
+        );
+
+        assert_eq!(
+            organize_imports("import b from \"b\";\n// a comes first\nimport a from \"a\";\n")
+                .unwrap(),
+            "// a comes first\nimport a from \"a\";\nimport b from \"b\";\n"
+        );
+
+        assert_eq!(
+            organize_imports(
+                "// Header\n\nimport c from \"c\"; // c\n/* a */\nimport a from \"a\";\n\nimport b from \"b\";\n"
+            )
+            .unwrap(),
+            "// Header\n\n/* a */\nimport a from \"a\";\nimport b from \"b\";\n\nimport c from \"c\"; // c\n"
+        );
+
+        // A source containing `from` is still read as the source, not as a barrier
+        assert_eq!(
+            organize_imports("import b from \"./fromNow\";\nimport a from \"./afrom\";\n").unwrap(),
+            "import a from \"./afrom\";\nimport b from \"./fromNow\";\n"
+        );
+    }
+
+    #[test]
+    fn test_has_mixed_indentation() {
+        let mixed = include_str!("../../../../../test/assets/mixedIndentation.js");
+        let consistent = include_str!("../../../../../test/assets/validApp.js");
+
+        assert_eq!(has_mixed_indentation(mixed), Ok(true));
+        assert_eq!(has_mixed_indentation(consistent), Ok(false));
+
+        // Alignment spaces after tabs and whitespace-only lines don't count
+        assert_eq!(
+            has_mixed_indentation("/**\n\t * Doc\n\t */\n\tlet a = 1;\n  \n"),
+            Ok(false)
+        );
+        assert_eq!(has_mixed_indentation("if (a) {\n \tb();\n}\n"), Ok(true));
+
+        // A top-level JSDoc in a tab-indented file
+        let jsdoc = "/**\n * Adds two numbers.\n */\nfunction add(a, b) {\n\treturn a + b;\n}\n";
+        assert_eq!(has_mixed_indentation(jsdoc), Ok(false));
+        assert_eq!(
+            has_mixed_indentation(&format!(
+                "{}function sub(a, b) {{\n  return a - b;\n}}\n",
+                jsdoc
+            )),
+            Ok(true)
+        );
+        assert_eq!(has_mixed_indentation(""), Ok(false));
+    }
 }
@@ -2,9 +2,9 @@ use crate::atoms;
 use crate::helpers::encode_response;
 use crate::parsers::javascript::formatter::*;
 
-use rustler::{Env, NifResult, Term};
+use rustler::{Atom, Env, NifResult, Term};
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn format_js_nif(env: Env, file_content: String) -> NifResult<Term> {
     let fn_atom = atoms::format_js_nif();
     let (status, result) = match format(&file_content) {
@@ -15,7 +15,23 @@ pub fn format_js_nif(env: Env, file_content: String) -> NifResult<Term> {
     encode_response(env, status, fn_atom, result)
 }
 
-#[rustler::nif]
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn format_js_with_options_nif(
+    env: Env,
+    file_content: String,
+    organize_imports: bool,
+) -> NifResult<Term> {
+    let fn_atom = atoms::format_js_with_options_nif();
+    let options = FormatOptions { organize_imports };
+    let (status, result) = match format_with_options(&file_content, &options) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
 pub fn is_js_formatted_nif(env: Env, file_content: String) -> NifResult<Term> {
     let fn_atom = atoms::is_js_formatted_nif();
     let (status, result) = match is_formatted(&file_content) {
@@ -25,3 +41,55 @@ pub fn is_js_formatted_nif(env: Env, file_content: String) -> NifResult<Term> {
 
     encode_response(env, status, fn_atom, result)
 }
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn has_mixed_indentation_nif(env: Env, file_content: String) -> NifResult<Term> {
+    let fn_atom = atoms::has_mixed_indentation_nif();
+    let (status, result) = match has_mixed_indentation(&file_content) {
+        Ok(true) => (atoms::ok(), true),
+        _ => (atoms::error(), false),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn format_js_batch_nif(env: Env, files: Vec<String>) -> NifResult<Term> {
+    let fn_atom = atoms::format_js_batch_nif();
+    let results: Vec<(Atom, String)> = format_batch(&files)
+        .into_iter()
+        .map(|result| match result {
+            Ok(formatted_code) => (atoms::ok(), formatted_code),
+            Err(error_msg) => (atoms::error(), error_msg),
+        })
+        .collect();
+
+    encode_response(env, atoms::ok(), fn_atom, results)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn format_js_file_nif(env: Env, path: String) -> NifResult<Term> {
+    let fn_atom = atoms::format_js_file_nif();
+    let (status, result) = match format_file(&path) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
+
+#[rustler::nif(schedule = "DirtyCpu")]
+pub fn format_js_range_nif(
+    env: Env,
+    file_content: String,
+    start_byte: usize,
+    end_byte: usize,
+) -> NifResult<Term> {
+    let fn_atom = atoms::format_js_range_nif();
+    let (status, result) = match format_range(&file_content, start_byte, end_byte) {
+        Ok(updated_code) => (atoms::ok(), updated_code),
+        Err(error_msg) => (atoms::error(), error_msg),
+    };
+
+    encode_response(env, status, fn_atom, result)
+}
@@ -1,21 +1,45 @@
-use swc_ecma_ast::{ImportSpecifier, Module, ModuleDecl, ModuleItem};
+use swc_ecma_ast::{
+    ClassProp, EsVersion, Expr, ImportDecl, ImportSpecifier, Module, ModuleDecl, ModuleItem,
+    PrivateProp, Stmt,
+};
 use swc_ecma_codegen::{text_writer::JsWriter, Config, Emitter};
-use swc_ecma_visit::{VisitMut, VisitMutWith};
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
 use swc_common::{
-    comments::SingleThreadedComments,
-    errors::{ColorConfig, Handler},
+    comments::{Comments, SingleThreadedComments},
     sync::Lrc,
-    FileName, SourceMap,
+    EqIgnoreSpan, FileName, SourceMap, Span, Spanned, DUMMY_SP,
+};
+
+use swc_ecma_parser::{
+    error::Error, lexer::Lexer, token::Token, Capturing, EsSyntax, Parser, StringInput, Syntax,
 };
 
-use swc_ecma_parser::{lexer::Lexer, Capturing, Parser, StringInput, Syntax};
+use std::ops::Range;
 
+/// Parses JavaScript source code into a module.
+///
+/// A leading shebang (`#!/usr/bin/env node`) is read by the lexer into `Module::shebang`
+/// and written back first by the emitter, so edited Node scripts keep it.
+///
+/// The lexer targets the latest ECMAScript version, so module scope syntax such as
+/// top-level `await` is accepted. Decorators (`@Component() class App {}`) are enabled,
+/// also in front of `export`, so decorated classes parse and are emitted back as written.
 pub fn parse(
     file_content: &str,
 ) -> Result<(Module, SingleThreadedComments, Lrc<SourceMap>), String> {
+    parse_with_diagnostics(file_content)
+        .map(|(module, comments, cm, _warnings)| (module, comments, cm))
+}
+
+/// Same as [`parse`], also returning the errors the parser recovered from (e.g. a `with`
+/// statement, which strict mode forbids) as warnings, described like the fatal error, in
+/// source order.
+#[allow(clippy::type_complexity)]
+pub fn parse_with_diagnostics(
+    file_content: &str,
+) -> Result<(Module, SingleThreadedComments, Lrc<SourceMap>, Vec<String>), String> {
     let cm: Lrc<SourceMap> = Default::default();
-    let handler = Handler::with_tty_emitter(ColorConfig::Auto, true, false, Some(cm.clone()));
 
     let fm = cm.new_source_file(
         FileName::Custom("virtual_file.js".into()).into(),
@@ -25,8 +49,12 @@ pub fn parse(
     let comments = SingleThreadedComments::default();
 
     let lexer = Lexer::new(
-        Syntax::Es(Default::default()),
-        Default::default(),
+        Syntax::Es(EsSyntax {
+            decorators: true,
+            decorators_before_export: true,
+            ..Default::default()
+        }),
+        EsVersion::latest(),
         StringInput::from(&*fm),
         Some(&comments),
     );
@@ -35,85 +63,504 @@ pub fn parse(
 
     let mut parser = Parser::new_from(capturing);
 
-    for e in parser.take_errors() {
-        e.into_diagnostic(&handler).emit();
-    }
-
-    // let module = parser.parse_module().expect("Failed to parse module");
     let module = match parser.parse_module() {
         Ok(m) => m,
-        Err(_e) => {
-            return Err("Failed to parse module".to_string());
+        Err(error) => {
+            // Recoverable errors are reported before the fatal one, in source order
+            let mut messages: Vec<String> = parser
+                .take_errors()
+                .iter()
+                .map(|e| describe_parse_error(&cm, e))
+                .collect();
+            messages.push(describe_parse_error(&cm, &error));
+
+            return Err(format!("Failed to parse module: {}", messages.join("; ")));
         }
     };
 
-    Ok((module, comments, cm))
+    let warnings = parser
+        .take_errors()
+        .iter()
+        .map(|e| describe_parse_error(&cm, e))
+        .collect();
+
+    Ok((module, comments, cm, warnings))
 }
 
-pub fn code_gen_from_ast_vist<T>(file_content: &str, mut visitor: T) -> Result<String, String>
+/// Resets every span of a node parsed from a snippet, so the node does not pick up the
+/// comments of the module it is inserted into at the same byte positions.
+pub struct SpanRemover;
+
+impl VisitMut for SpanRemover {
+    fn visit_mut_span(&mut self, span: &mut Span) {
+        *span = DUMMY_SP;
+    }
+}
+
+/// Parses a single JavaScript expression, e.g. the value of an object property.
+pub fn parse_expression(source: &str) -> Result<Box<Expr>, String> {
+    let (module, _comments, _cm) = parse(&format!("({}\n);", source))
+        .map_err(|error| format!("Invalid expression `{}`: {}", source, error))?;
+
+    let mut body = module.body;
+    match (body.pop(), body.is_empty()) {
+        (Some(ModuleItem::Stmt(Stmt::Expr(expr_stmt))), true) => {
+            let mut expr = match *expr_stmt.expr {
+                Expr::Paren(paren) => paren.expr,
+                expr => Box::new(expr),
+            };
+            expr.visit_mut_with(&mut SpanRemover);
+            Ok(expr)
+        }
+        _ => Err(format!("Invalid expression `{}`", source)),
+    }
+}
+
+/// Turns a parser error into a `message at line L, column C` string (both 1-based).
+pub fn describe_parse_error(cm: &Lrc<SourceMap>, error: &Error) -> String {
+    let loc = cm.lookup_char_pos(error.span().lo);
+    format!(
+        "{} at line {}, column {}",
+        error.kind().msg(),
+        loc.line,
+        loc.col.0 + 1
+    )
+}
+
+/// Line ending written between the lines of emitted code.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum NewLine {
+    #[default]
+    Lf,
+    CrLf,
+}
+
+impl NewLine {
+    pub fn as_str(self) -> &'static str {
+        match self {
+            NewLine::Lf => "\n",
+            NewLine::CrLf => "\r\n",
+        }
+    }
+}
+
+/// Whether emitted statements end with a semicolon, see [`EmitOptions::semicolons`].
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum SemicolonStyle {
+    /// Follows the original source: semicolons are left out like [`SemicolonStyle::AsNeeded`]
+    /// when none of its lines ends with one.
+    Keep,
+    /// Ends every statement with a semicolon, as the codegen does.
+    #[default]
+    Always,
+    /// Leaves out the semicolons automatic semicolon insertion makes up for, i.e. at the end
+    /// of a line unless the next line starts with `(`, `[`, `` ` ``, `+`, `-` or `/`.
+    AsNeeded,
+}
+
+/// Options applied when a module is emitted back to JavaScript source.
+#[derive(Debug, Clone, Default)]
+pub struct EmitOptions {
+    /// Indents the output with the dominant indentation of the input (tabs or its space
+    /// width) instead of the codegen default of four spaces.
+    pub preserve_formatting: bool,
+    /// Emits the module without any formatting whitespace.
+    pub minify: bool,
+    /// Line ending of the output, `\n` unless `\r\n` is requested, e.g. for files that come
+    /// from Windows.
+    pub new_line: NewLine,
+    /// Semicolon style of the emitted statements, so an edit of a semicolon-less file does
+    /// not add semicolons everywhere.
+    pub semicolons: SemicolonStyle,
+    /// Keeps the blank lines that separate groups of top-level imports in the input, e.g.
+    /// vendor and local imports, which the codegen would collapse.
+    pub preserve_blank_lines: bool,
+    /// Escapes non-ASCII characters of string literals and identifiers, e.g. `é` as `\xe9`
+    /// in a string and `\u00e9` in an identifier, for build tools that only accept ASCII
+    /// source. Comments are kept as written.
+    pub ascii_only: bool,
+}
+
+pub fn code_gen_from_ast_vist<T>(file_content: &str, visitor: T) -> Result<String, String>
 where
     T: VisitMut,
 {
-    let (mut module, comments, cm) = match parse(file_content) {
-        Ok(result) => result,
-        Err(_) => return Err("Failed to parse JavaScript content".to_string()),
-    };
+    code_gen_from_ast_vist_with_options(file_content, visitor, &EmitOptions::default())
+}
+
+pub fn code_gen_from_ast_vist_with_options<T>(
+    file_content: &str,
+    mut visitor: T,
+    options: &EmitOptions,
+) -> Result<String, String>
+where
+    T: VisitMut,
+{
+    let (mut module, comments, cm) = parse(file_content)?;
 
     module.visit_mut_with(&mut visitor);
-    let mut buf = vec![];
 
-    let mut emitter = Emitter {
-        cfg: Config::default().with_minify(false),
-        cm: cm.clone(),
-        comments: Some(&comments),
-        wr: JsWriter::new(cm.clone(), "\n", &mut buf, None),
-    };
+    emit_module(&module, Some(&comments), cm, file_content, options)
+}
 
-    if emitter.emit_module(&module).is_err() {
-        return Err("Failed to emit module".to_string());
-    }
+/// Runs `visitor` over the parsed module without emitting code and reports whether it
+/// changed the module. Nodes are compared ignoring their spans, so an edit that only
+/// re-formats the code is not a change.
+pub fn changes_from_ast_vist<T>(file_content: &str, mut visitor: T) -> Result<bool, String>
+where
+    T: VisitMut,
+{
+    let (mut module, _comments, _cm) = parse(file_content)?;
+    let original = module.clone();
 
-    String::from_utf8(buf).map_err(|_| "Invalid UTF-8".to_string())
+    module.visit_mut_with(&mut visitor);
+
+    Ok(!original.eq_ignore_span(&module))
 }
 
 pub fn code_gen_from_ast_module(
     module: &mut Module,
     comments: SingleThreadedComments,
     cm: Lrc<SourceMap>,
-) -> String {
+) -> Result<String, String> {
+    emit_module(module, Some(&comments), cm, "", &EmitOptions::default())
+}
+
+/// Same as [`code_gen_from_ast_module`], `file_content` is the original source the module
+/// was parsed from and is only read when an option depends on it.
+pub fn code_gen_from_ast_module_with_options(
+    module: &mut Module,
+    comments: SingleThreadedComments,
+    cm: Lrc<SourceMap>,
+    file_content: &str,
+    options: &EmitOptions,
+) -> Result<String, String> {
+    emit_module(module, Some(&comments), cm, file_content, options)
+}
+
+fn emit_module(
+    module: &Module,
+    comments: Option<&SingleThreadedComments>,
+    cm: Lrc<SourceMap>,
+    file_content: &str,
+    options: &EmitOptions,
+) -> Result<String, String> {
     let mut buf = vec![];
 
+    let mut writer = JsWriter::new(cm.clone(), options.new_line.as_str(), &mut buf, None);
+    if options.preserve_formatting {
+        writer.set_indent_str(detect_indent(file_content));
+    }
+
     let mut emitter = Emitter {
-        cfg: Config::default().with_minify(false),
+        cfg: Config::default()
+            .with_minify(options.minify)
+            .with_ascii_only(options.ascii_only),
         cm: cm.clone(),
-        comments: Some(&comments),
-        wr: JsWriter::new(cm.clone(), "\n", &mut buf, None),
+        comments: comments.map(|comments| comments as &dyn Comments),
+        wr: writer,
+    };
+
+    if emitter.emit_module(module).is_err() {
+        return Err("Failed to emit module".to_string());
+    }
+
+    let code = String::from_utf8(buf).map_err(|_| "Invalid UTF-8".to_string())?;
+
+    let strip_semicolons = match options.semicolons {
+        SemicolonStyle::Always => false,
+        SemicolonStyle::AsNeeded => true,
+        SemicolonStyle::Keep => {
+            !file_content.trim().is_empty()
+                && !file_content
+                    .lines()
+                    .any(|line| line.trim_end().ends_with(';'))
+        }
+    };
+
+    let code = if strip_semicolons && !options.minify {
+        strip_optional_semicolons(&code)
+    } else {
+        code
+    };
+
+    if options.preserve_blank_lines && !options.minify {
+        Ok(restore_import_blank_lines(
+            &code,
+            file_content,
+            options.new_line.as_str(),
+        ))
+    } else {
+        Ok(code)
+    }
+}
+
+/// Source and `(start, end)` byte range of every top-level import declaration, in order.
+fn import_ranges(code: &str) -> Option<Vec<(String, usize, usize)>> {
+    let (module, _comments, cm) = parse(code).ok()?;
+
+    let ranges = module
+        .body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(import_decl)) => Some((
+                import_decl.src.value.to_string(),
+                cm.lookup_byte_offset(import_decl.span.lo).pos.0 as usize,
+                cm.lookup_byte_offset(import_decl.span.hi).pos.0 as usize,
+            )),
+            _ => None,
+        })
+        .collect();
+
+    Some(ranges)
+}
+
+/// Whether the text between two statements has an empty line.
+fn has_blank_line(gap: &str) -> bool {
+    let lines: Vec<&str> = gap.split('\n').collect();
+    lines.len() > 2
+        && lines[1..lines.len() - 1]
+            .iter()
+            .any(|line| line.trim().is_empty())
+}
+
+/// Puts back the blank lines the original source had between two consecutive imports that
+/// are still consecutive in the emitted code. The code is returned unchanged if either
+/// side does not parse.
+fn restore_import_blank_lines(code: &str, file_content: &str, new_line: &str) -> String {
+    let (Some(original), Some(emitted)) = (import_ranges(file_content), import_ranges(code)) else {
+        return code.to_string();
+    };
+
+    let groups: Vec<(&str, &str)> = original
+        .windows(2)
+        .filter(|pair| {
+            pair[0].2 <= pair[1].1 && has_blank_line(&file_content[pair[0].2..pair[1].1])
+        })
+        .map(|pair| (pair[0].0.as_str(), pair[1].0.as_str()))
+        .collect();
+
+    let mut positions: Vec<usize> = emitted
+        .windows(2)
+        .filter(|pair| groups.contains(&(pair[0].0.as_str(), pair[1].0.as_str())))
+        .filter(|pair| pair[0].2 <= pair[1].1 && !has_blank_line(&code[pair[0].2..pair[1].1]))
+        .filter_map(|pair| {
+            code[pair[0].2..]
+                .find('\n')
+                .map(|offset| pair[0].2 + offset + 1)
+        })
+        .collect();
+    positions.sort_unstable();
+    positions.dedup();
+
+    let mut output = String::with_capacity(code.len() + positions.len() * new_line.len());
+    let mut position = 0;
+    for line_start in positions {
+        output.push_str(&code[position..line_start]);
+        output.push_str(new_line);
+        position = line_start;
+    }
+    output.push_str(&code[position..]);
+    output
+}
+
+/// Collects the end offsets of statements, module declarations and class fields, the
+/// places the codegen puts a semicolon.
+struct StatementEndCollector<'a> {
+    cm: &'a SourceMap,
+    ends: Vec<usize>,
+    empty_ends: Vec<usize>,
+}
+
+impl StatementEndCollector<'_> {
+    fn push(&mut self, span: Span) {
+        self.ends
+            .push(self.cm.lookup_byte_offset(span.hi).pos.0 as usize);
+    }
+}
+
+impl Visit for StatementEndCollector<'_> {
+    fn visit_stmt(&mut self, stmt: &Stmt) {
+        match stmt {
+            // `if (a);` must keep the semicolon of its empty body
+            Stmt::Empty(empty) => self
+                .empty_ends
+                .push(self.cm.lookup_byte_offset(empty.span.hi).pos.0 as usize),
+            stmt => self.push(stmt.span()),
+        }
+        stmt.visit_children_with(self);
+    }
+
+    fn visit_module_decl(&mut self, module_decl: &ModuleDecl) {
+        self.push(module_decl.span());
+        module_decl.visit_children_with(self);
+    }
+
+    fn visit_class_prop(&mut self, class_prop: &ClassProp) {
+        self.push(class_prop.span);
+        class_prop.visit_children_with(self);
+    }
+
+    fn visit_private_prop(&mut self, private_prop: &PrivateProp) {
+        self.push(private_prop.span);
+        private_prop.visit_children_with(self);
+    }
+}
+
+/// Removes the statement-ending semicolons of emitted code that automatic semicolon
+/// insertion makes up for. The code is returned unchanged if it does not parse, or if the
+/// code without the semicolons would not parse anymore.
+fn strip_optional_semicolons(code: &str) -> String {
+    let Ok((module, _comments, cm)) = parse(code) else {
+        return code.to_string();
+    };
+
+    let mut collector = StatementEndCollector {
+        cm: &cm,
+        ends: Vec::new(),
+        empty_ends: Vec::new(),
     };
+    module.visit_with(&mut collector);
 
-    emitter.emit_module(module).expect("Failed to emit module");
-    String::from_utf8(buf).expect("Invalid UTF-8")
+    let mut semicolons: Vec<usize> = collector
+        .ends
+        .into_iter()
+        .filter(|end| !collector.empty_ends.contains(end))
+        .filter(|&end| end > 0 && end <= code.len() && code[..end].ends_with(';'))
+        .filter(|&end| {
+            let rest = &code[end..];
+            let line_rest = rest.split('\n').next().unwrap_or_default();
+            let next = rest.trim_start().chars().next();
+
+            line_rest.trim().is_empty()
+                && !matches!(next, Some('(' | '[' | '`' | '+' | '-' | '/' | '*'))
+        })
+        .map(|end| end - 1)
+        .collect();
+    semicolons.sort_unstable();
+    semicolons.dedup();
+
+    let mut output = String::with_capacity(code.len());
+    let mut position = 0;
+    for semicolon in semicolons {
+        output.push_str(&code[position..semicolon]);
+        position = semicolon + 1;
+    }
+    output.push_str(&code[position..]);
+
+    if parse(&output).is_err() {
+        return code.to_string();
+    }
+    output
+}
+
+/// Guesses the indentation unit of a source: a tab when tab-indented lines dominate,
+/// otherwise the narrowest space indentation found (four spaces when nothing is indented).
+pub fn detect_indent(source: &str) -> &'static str {
+    const SPACES: &str = "        ";
+
+    let mut tab_lines = 0;
+    let mut space_lines = 0;
+    let mut narrowest = SPACES.len();
+
+    for line in source.lines() {
+        let trimmed = line.trim_start();
+        // Skip blank lines and the ` * ` continuation lines of block comments
+        if trimmed.is_empty() || trimmed.starts_with('*') {
+            continue;
+        }
+
+        if line.starts_with('\t') {
+            tab_lines += 1;
+        } else if line.starts_with(' ') {
+            space_lines += 1;
+            narrowest = narrowest.min(line.len() - line.trim_start_matches(' ').len());
+        }
+    }
+
+    if tab_lines > space_lines {
+        "\t"
+    } else if space_lines > 0 {
+        &SPACES[..narrowest]
+    } else {
+        &SPACES[..4]
+    }
 }
 
+/// The shape of an import declaration. A namespace import binds the whole module, so it
+/// is never merged with named or default imports of the same source.
+#[derive(Debug, PartialEq, Eq)]
+enum ImportKind {
+    SideEffect,
+    Namespace,
+    Named,
+}
+
+fn import_kind(import_decl: &ImportDecl) -> ImportKind {
+    if import_decl.specifiers.is_empty() {
+        ImportKind::SideEffect
+    } else if import_decl
+        .specifiers
+        .iter()
+        .any(|specifier| matches!(specifier, ImportSpecifier::Namespace(_)))
+    {
+        ImportKind::Namespace
+    } else {
+        ImportKind::Named
+    }
+}
+
+/// Checks if `new_import` is already covered by the imports of `body`.
+///
+/// - A side effect import (`import "m"`) is a duplicate of any import from the same source.
+/// - A namespace import is only a duplicate of another namespace import from the same
+///   source that binds the same local names.
+/// - Named and default specifiers are looked up across every non-namespace import from
+///   the same source.
 pub fn is_duplicate_import(new_import: &ModuleItem, body: &[ModuleItem]) -> bool {
-    if let ModuleItem::ModuleDecl(ModuleDecl::Import(new_import_decl)) = new_import {
-        for item in body {
-            if let ModuleItem::ModuleDecl(ModuleDecl::Import(existing_import_decl)) = item {
-                if new_import_decl.src.value == existing_import_decl.src.value {
-                    for new_spec in &new_import_decl.specifiers {
-                        if !existing_import_decl
-                            .specifiers
-                            .iter()
-                            .any(|existing_spec| specifier_equals(new_spec, existing_spec))
-                        {
-                            return false;
-                        }
-                    }
-                    return true;
-                }
+    let ModuleItem::ModuleDecl(ModuleDecl::Import(new_import_decl)) = new_import else {
+        return false;
+    };
+
+    let same_source: Vec<&ImportDecl> = body
+        .iter()
+        .filter_map(|item| match item {
+            ModuleItem::ModuleDecl(ModuleDecl::Import(existing_import_decl))
+                if existing_import_decl.src.value == new_import_decl.src.value =>
+            {
+                Some(existing_import_decl)
             }
+            _ => None,
+        })
+        .collect();
+
+    match import_kind(new_import_decl) {
+        ImportKind::SideEffect => !same_source.is_empty(),
+        ImportKind::Namespace => same_source
+            .iter()
+            .filter(|existing| import_kind(existing) == ImportKind::Namespace)
+            .any(|existing| {
+                new_import_decl.specifiers.iter().all(|new_spec| {
+                    existing
+                        .specifiers
+                        .iter()
+                        .any(|existing_spec| specifier_equals(new_spec, existing_spec))
+                })
+            }),
+        ImportKind::Named => {
+            let existing_specs: Vec<&ImportSpecifier> = same_source
+                .iter()
+                .filter(|existing| import_kind(existing) == ImportKind::Named)
+                .flat_map(|existing| existing.specifiers.iter())
+                .collect();
+
+            new_import_decl.specifiers.iter().all(|new_spec| {
+                existing_specs
+                    .iter()
+                    .any(|existing_spec| specifier_equals(new_spec, existing_spec))
+            })
         }
     }
-    false
 }
 
 fn specifier_equals(new_spec: &ImportSpecifier, existing_spec: &ImportSpecifier) -> bool {
@@ -132,5 +579,275 @@ fn specifier_equals(new_spec: &ImportSpecifier, existing_spec: &ImportSpecifier)
 }
 
 pub fn replace_four_spaces_with_tab(input: &str) -> String {
-    input.replace("    ", "\t")
+    replace_indent_spaces_with_tab(input, 4)
+}
+
+/// The byte ranges of the string and template literal tokens of `input`.
+fn literal_ranges(input: &str) -> Vec<Range<usize>> {
+    let cm: Lrc<SourceMap> = Default::default();
+    let fm = cm.new_source_file(
+        FileName::Custom("virtual_file.js".into()).into(),
+        input.into(),
+    );
+
+    let lexer = Lexer::new(
+        Syntax::Es(Default::default()),
+        EsVersion::latest(),
+        StringInput::from(&*fm),
+        None,
+    );
+
+    lexer
+        .filter(|token| matches!(token.token, Token::Str { .. } | Token::Template { .. }))
+        .map(|token| {
+            let start = (token.span.lo.0 - fm.start_pos.0) as usize;
+            let end = (token.span.hi.0 - fm.start_pos.0) as usize;
+            start..end
+        })
+        .collect()
+}
+
+/// Converts the indentation of every line from spaces to tabs, one tab per `indent_width`
+/// spaces. Only the whitespace at the start of lines is touched, so spaces inside code and
+/// strings are kept, and leftover spaces that don't fill a whole indent level (such as the
+/// alignment of ` * ` in block comments) stay as they are. Lines that start inside a
+/// multi-line template literal or string belong to its value and are kept as well. An
+/// `indent_width` of `0` returns the input unchanged.
+pub fn replace_indent_spaces_with_tab(input: &str, indent_width: usize) -> String {
+    if indent_width == 0 {
+        return input.to_string();
+    }
+
+    let literals = literal_ranges(input);
+    let mut line_start = 0;
+
+    input
+        .split_inclusive('\n')
+        .map(|line| {
+            let start = line_start;
+            line_start += line.len();
+            if literals
+                .iter()
+                .any(|literal| literal.start < start && start < literal.end)
+            {
+                return line.to_string();
+            }
+
+            let content = line.trim_start_matches([' ', '\t']);
+            let indent = &line[..line.len() - content.len()];
+
+            let mut converted = String::with_capacity(indent.len());
+            let mut spaces = 0;
+            for ch in indent.chars() {
+                if ch == ' ' {
+                    spaces += 1;
+                    if spaces == indent_width {
+                        converted.push('\t');
+                        spaces = 0;
+                    }
+                } else {
+                    converted.push_str(&" ".repeat(spaces));
+                    converted.push(ch);
+                    spaces = 0;
+                }
+            }
+            converted.push_str(&" ".repeat(spaces));
+
+            converted + content
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_error_reports_position() {
+        let result = parse("let x = ;");
+        let error = result.err().expect("Expected a parse error");
+
+        assert!(error.contains("line 1"), "Unexpected message: {}", error);
+        assert!(error.contains("column 9"), "Unexpected message: {}", error);
+    }
+
+    #[test]
+    fn test_detect_indent() {
+        assert_eq!(
+            detect_indent("let a = {\n\tb: 1,\n\tc: {\n\t\td: 2\n\t}\n};"),
+            "\t"
+        );
+        assert_eq!(
+            detect_indent("let a = {\n  b: 1,\n  c: {\n    d: 2\n  }\n};"),
+            "  "
+        );
+        assert_eq!(detect_indent("/**\n * Doc\n */\nlet a = 1;"), "    ");
+    }
+
+    #[test]
+    fn test_replace_indent_spaces_with_tab() {
+        let code = "function a() {\n  if (b) {\n    c(\"x    y\");\n  }\n}\n";
+        assert_eq!(
+            replace_indent_spaces_with_tab(code, 2),
+            "function a() {\n\tif (b) {\n\t\tc(\"x    y\");\n\t}\n}\n"
+        );
+
+        let code = "let a = {\n    b: \"    \",\n      c: 1,\n};";
+        assert_eq!(
+            replace_four_spaces_with_tab(code),
+            "let a = {\n\tb: \"    \",\n\t  c: 1,\n};"
+        );
+
+        assert_eq!(
+            replace_four_spaces_with_tab("/**\n * Doc\n */\n\t    a();"),
+            "/**\n * Doc\n */\n\t\ta();"
+        );
+        assert_eq!(replace_indent_spaces_with_tab("  a();", 0), "  a();");
+    }
+
+    fn first_item(code: &str) -> ModuleItem {
+        parse(code).unwrap().0.body.remove(0)
+    }
+
+    #[test]
+    fn test_is_duplicate_import_by_specifier_kind() {
+        let (module, _, _) =
+            parse("import * as a from \"m\";\nimport { b } from \"m\";\nimport c from \"m\";")
+                .unwrap();
+
+        assert!(is_duplicate_import(
+            &first_item("import * as a from \"m\";"),
+            &module.body
+        ));
+        assert!(is_duplicate_import(
+            &first_item("import { b } from \"m\";"),
+            &module.body
+        ));
+        assert!(is_duplicate_import(
+            &first_item("import c, { b } from \"m\";"),
+            &module.body
+        ));
+        assert!(is_duplicate_import(
+            &first_item("import \"m\";"),
+            &module.body
+        ));
+
+        // A named import is never a duplicate of a namespace import with the same name
+        assert!(!is_duplicate_import(
+            &first_item("import { a } from \"m\";"),
+            &module.body
+        ));
+        assert!(!is_duplicate_import(
+            &first_item("import * as b from \"m\";"),
+            &module.body
+        ));
+        assert!(!is_duplicate_import(
+            &first_item("import * as a from \"n\";"),
+            &module.body
+        ));
+    }
+
+    #[test]
+    fn test_parse_expression() {
+        assert!(matches!(*parse_expression("3000").unwrap(), Expr::Lit(_)));
+        assert!(matches!(
+            *parse_expression("{ a: 1 }").unwrap(),
+            Expr::Object(_)
+        ));
+        assert!(parse_expression("1 +").is_err());
+        assert!(parse_expression("1); alert(1").is_err());
+    }
+
+    #[test]
+    fn test_ascii_only_output() {
+        let emit = |source: &str, ascii_only: bool| {
+            let (mut module, comments, cm) = parse(source).unwrap();
+            let options = EmitOptions {
+                ascii_only,
+                ..Default::default()
+            };
+            code_gen_from_ast_module_with_options(&mut module, comments, cm, source, &options)
+                .unwrap()
+        };
+
+        let code = "let greeting = \"Café\";\n";
+        assert_eq!(emit(code, false), code);
+
+        let escaped = emit(code, true);
+        assert!(escaped.is_ascii(), "{}", escaped);
+        assert!(escaped.contains("\"Caf\\xe9\""), "{}", escaped);
+
+        // Identifiers can't use `\x`, so they are escaped with `\u`
+        let escaped = emit("let café = 1;\n", true);
+        assert_eq!(escaped, "let caf\\u00e9 = 1;\n");
+    }
+
+    #[test]
+    fn test_semicolon_styles() {
+        let emit = |source: &str, semicolons: SemicolonStyle| {
+            let (mut module, comments, cm) = parse(source).unwrap();
+            let options = EmitOptions {
+                semicolons,
+                ..Default::default()
+            };
+            code_gen_from_ast_module_with_options(&mut module, comments, cm, source, &options)
+                .unwrap()
+        };
+
+        let code = "let a = 1\nfoo()\n";
+        assert_eq!(emit(code, SemicolonStyle::Always), "let a = 1;\nfoo();\n");
+        assert_eq!(emit(code, SemicolonStyle::AsNeeded), "let a = 1\nfoo()\n");
+        assert_eq!(emit(code, SemicolonStyle::Keep), "let a = 1\nfoo()\n");
+        assert_eq!(
+            emit("let a = 1;\nfoo()", SemicolonStyle::Keep),
+            "let a = 1;\nfoo();\n"
+        );
+
+        // The next line would otherwise continue the statement
+        let code = emit("let a = b\n;(c || d).e()", SemicolonStyle::AsNeeded);
+        assert!(code.starts_with("let a = b;\n"), "{}", code);
+
+        let code = emit("if (a);\nfoo()", SemicolonStyle::AsNeeded);
+        assert!(code.lines().next().unwrap().ends_with(';'), "{}", code);
+
+        // A class field followed by a generator method
+        let code = emit(
+            "class A {\n  x = 1;\n  *gen() {}\n}\n",
+            SemicolonStyle::AsNeeded,
+        );
+        assert!(code.contains("x = 1;\n"), "{}", code);
+        assert!(parse(&code).is_ok(), "{}", code);
+    }
+
+    #[test]
+    fn test_parse_with_diagnostics() {
+        let (_module, _comments, _cm, warnings) =
+            parse_with_diagnostics("let a = 1;\nwith (a) {}\n").unwrap();
+        assert_eq!(warnings.len(), 1);
+        assert!(
+            warnings[0].contains("line 2"),
+            "Unexpected warning: {}",
+            warnings[0]
+        );
+
+        let (_module, _comments, _cm, warnings) = parse_with_diagnostics("let a = 1;").unwrap();
+        assert!(warnings.is_empty());
+
+        assert!(parse_with_diagnostics("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_replace_indent_spaces_with_tab_keeps_literals() {
+        let code = "const a = `\n    kept\n        ${b}\n`;\nif (a) {\n    c();\n}";
+        assert_eq!(
+            replace_four_spaces_with_tab(code),
+            "const a = `\n    kept\n        ${b}\n`;\nif (a) {\n\tc();\n}"
+        );
+
+        let code = "const s = \"a\\\n    b\";\n    d();";
+        assert_eq!(
+            replace_four_spaces_with_tab(code),
+            "const s = \"a\\\n    b\";\n\td();"
+        );
+    }
 }
@@ -6,18 +6,26 @@
 //! `liveSocket` initialization.
 //! Designed specifically for manipulating the JavaScript Abstract Syntax Tree (AST) using SWC.
 
+use crate::helpers::ensure_source_not_empty;
 use crate::parsers::javascript::helpers::*;
 
-use super::ast::{FindCondition, Operation};
-use swc_common::{SyntaxContext, DUMMY_SP};
+use super::ast::{insert_import_to_module, FindCondition, Operation};
+use swc_common::{EqIgnoreSpan, SyntaxContext, DUMMY_SP};
 use swc_ecma_ast::*;
-use swc_ecma_visit::{VisitMut, VisitMutWith};
+use swc_ecma_visit::{Visit, VisitMut, VisitMutWith, VisitWith};
 
 pub struct HookExtender<'a> {
     target_var_name: &'a str,
     new_objects: Vec<&'a str>,
     operation: Operation,
     find: FindCondition,
+    added: Vec<String>,
+    skipped: Vec<String>,
+    removed: Vec<String>,
+    /// Position (among the `new LiveSocket(...)` declarations) of the socket to edit, the
+    /// socket is picked by `target_var_name` when it is `None`.
+    socket_index: Option<usize>,
+    sockets_seen: usize,
 }
 
 impl<'a> HookExtender<'a> {
@@ -27,9 +35,20 @@ impl<'a> HookExtender<'a> {
             new_objects,
             find: FindCondition::NotFound("".to_string()),
             operation: Operation::Edit,
+            added: Vec::new(),
+            skipped: Vec::new(),
+            removed: Vec::new(),
+            socket_index: None,
+            sockets_seen: 0,
         }
     }
 
+    /// Edits only the `new LiveSocket(...)` declaration at `index`, in source order.
+    pub fn with_socket_index(mut self, index: usize) -> Self {
+        self.socket_index = Some(index);
+        self
+    }
+
     fn extend_or_create_hooks(&mut self, obj_expr: &mut ObjectLit) {
         if let Some(hooks_property) = obj_expr.props.iter_mut().find_map(|prop| {
             if let PropOrSpread::Prop(prop) = prop {
@@ -67,7 +86,10 @@ impl<'a> HookExtender<'a> {
                     }
                 });
 
-                if !already_exists {
+                if already_exists {
+                    self.skipped.push(new_object.to_string());
+                } else {
+                    self.added.push(new_object.to_string());
                     hooks_property
                         .props
                         .push(PropOrSpread::Prop(Box::new(Prop::Shorthand(Ident::new(
@@ -79,6 +101,8 @@ impl<'a> HookExtender<'a> {
             }
         } else {
             // Create hooks if it doesn't exist
+            self.added
+                .extend(self.new_objects.iter().map(|name| name.to_string()));
             let new_hooks = ObjectLit {
                 span: DUMMY_SP,
                 props: self
@@ -126,21 +150,26 @@ impl<'a> HookExtender<'a> {
             }
             None
         }) {
-            hooks_property.props.retain(|prop| match prop {
-                PropOrSpread::Prop(prop) => {
-                    if let Prop::Shorthand(ident) = &**prop {
-                        !objects_to_remove.contains(&&*ident.sym)
-                    } else {
-                        true
-                    }
+            let removed = &mut self.removed;
+            hooks_property.props.retain(|prop| {
+                let name = match prop {
+                    PropOrSpread::Prop(prop) => match &**prop {
+                        Prop::Shorthand(ident) => ident.sym.to_string(),
+                        _ => return true,
+                    },
+                    PropOrSpread::Spread(spread) => match &*spread.expr {
+                        Expr::Ident(ident) => format!("...{}", ident.sym),
+                        _ => return true,
+                    },
+                };
+
+                if !objects_to_remove.contains(&name.as_str()) {
+                    return true;
                 }
-                PropOrSpread::Spread(spread) => {
-                    if let Expr::Ident(ident) = &*spread.expr {
-                        !objects_to_remove.contains(&format!("...{}", ident.sym).as_str())
-                    } else {
-                        true
-                    }
+                if !removed.contains(&name) {
+                    removed.push(name);
                 }
+                false
             });
         }
     }
@@ -150,26 +179,32 @@ impl VisitMut for HookExtender<'_> {
     fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
         if matches!(self.operation, Operation::Edit) {
             for decl in &mut var_decl.decls {
-                if let Some(ident) = decl.name.as_ident() {
-                    if ident.sym == self.target_var_name {
-                        if let Some(init) = &mut decl.init {
-                            if let Expr::New(new_expr) = init.as_mut() {
-                                if let Expr::Ident(callee_ident) = &*new_expr.callee {
-                                    if callee_ident.sym == "LiveSocket" {
-                                        self.find = FindCondition::FoundError("".to_string());
-
-                                        if let Some(args) = &mut new_expr.args {
-                                            if let Some(ExprOrSpread { expr, .. }) = args.last_mut()
-                                            {
-                                                if let Expr::Object(obj_expr) = &mut **expr {
-                                                    self.find = FindCondition::Found;
-                                                    self.extend_or_create_hooks(obj_expr);
-                                                }
-                                            }
-                                        }
-                                    }
-                                }
-                            }
+                let Some(ident) = decl.name.as_ident() else {
+                    continue;
+                };
+                let Some(Expr::New(new_expr)) = decl.init.as_deref_mut() else {
+                    continue;
+                };
+                if !matches!(&*new_expr.callee, Expr::Ident(callee) if callee.sym == "LiveSocket") {
+                    continue;
+                }
+
+                let position = self.sockets_seen;
+                self.sockets_seen += 1;
+                let is_target = match self.socket_index {
+                    Some(index) => position == index,
+                    None => ident.sym == self.target_var_name,
+                };
+                if !is_target {
+                    continue;
+                }
+
+                self.find = FindCondition::FoundError("".to_string());
+                if let Some(args) = &mut new_expr.args {
+                    if let Some(ExprOrSpread { expr, .. }) = args.last_mut() {
+                        if let Expr::Object(obj_expr) = &mut **expr {
+                            self.find = FindCondition::Found;
+                            self.extend_or_create_hooks(obj_expr);
                         }
                     }
                 }
@@ -205,54 +240,968 @@ impl VisitMut for HookExtender<'_> {
 pub fn extend_hook_object_to_ast(
     file_content: &str,
     new_objects: Vec<&str>,
-) -> Result<String, String> {
+) -> Result<String, String> {
+    extend_hook_object_to_ast_with_options(file_content, new_objects, &EmitOptions::default())
+}
+
+/// Same as [`extend_hook_object_to_ast`], emitting the result with the given [`EmitOptions`].
+pub fn extend_hook_object_to_ast_with_options(
+    file_content: &str,
+    new_objects: Vec<&str>,
+    options: &EmitOptions,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut hook_extender = HookExtender::new("liveSocket", new_objects);
+
+    let result = code_gen_from_ast_vist_with_options(file_content, &mut hook_extender, options);
+    if hook_extender.find == FindCondition::Found {
+        result
+    } else {
+        Err(hook_extender.find.message().to_string())
+    }
+}
+
+/// Picks the `new LiveSocket(...)` declaration to edit when a file initializes several.
+#[derive(Debug, Clone, Copy)]
+pub enum LiveSocketTarget<'a> {
+    /// The only `new LiveSocket(...)` declaration of the file.
+    Only,
+    /// The declaration assigned to this variable name.
+    Name(&'a str),
+    /// The declaration at this position, in source order and starting at `0`.
+    Index(usize),
+}
+
+/// Collects the `new LiveSocket(...)` declarations in source order, with their variable
+/// name, and the names of every declared variable.
+struct LiveSocketDeclCollector {
+    sockets: Vec<(String, NewExpr)>,
+    declared: Vec<String>,
+}
+
+impl Visit for LiveSocketDeclCollector {
+    fn visit_var_decl(&mut self, var_decl: &VarDecl) {
+        for decl in &var_decl.decls {
+            let Some(ident) = decl.name.as_ident() else {
+                continue;
+            };
+            self.declared.push(ident.sym.to_string());
+            let Some(Expr::New(new_expr)) = decl.init.as_deref() else {
+                continue;
+            };
+            if matches!(&*new_expr.callee, Expr::Ident(callee) if callee.sym == "LiveSocket") {
+                self.sockets.push((ident.sym.to_string(), new_expr.clone()));
+            }
+        }
+
+        var_decl.visit_children_with(self)
+    }
+}
+
+fn collect_live_sockets(module: &Module) -> Vec<(String, NewExpr)> {
+    collect_declarations(module).sockets
+}
+
+fn collect_declarations(module: &Module) -> LiveSocketDeclCollector {
+    let mut collector = LiveSocketDeclCollector {
+        sockets: Vec::new(),
+        declared: Vec::new(),
+    };
+    module.visit_with(&mut collector);
+    collector
+}
+
+fn resolve_live_socket_target(module: &Module, target: LiveSocketTarget) -> Result<usize, String> {
+    live_socket_index(&collect_live_sockets(module), target)
+}
+
+/// The `new LiveSocket(...)` call of the declaration picked by `target`.
+fn find_live_socket(module: &Module, target: LiveSocketTarget) -> Result<NewExpr, String> {
+    let mut sockets = collect_live_sockets(module);
+    let index = live_socket_index(&sockets, target)?;
+    Ok(sockets.swap_remove(index).1)
+}
+
+/// The options object of a `new LiveSocket(...)` call, i.e. its last argument.
+fn live_socket_options(new_expr: &NewExpr) -> Option<&ObjectLit> {
+    match new_expr.args.as_ref()?.last().map(|arg| &*arg.expr)? {
+        Expr::Object(options) => Some(options),
+        _ => None,
+    }
+}
+
+/// The `hooks: { ... }` object of the LiveSocket options.
+fn live_socket_hooks(options: &ObjectLit) -> Option<&ObjectLit> {
+    options.props.iter().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        match &**prop {
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(key),
+                value,
+            }) if key.sym == "hooks" => match &**value {
+                Expr::Object(hooks) => Some(hooks),
+                _ => None,
+            },
+            _ => None,
+        }
+    })
+}
+
+fn live_socket_index(
+    sockets: &[(String, NewExpr)],
+    target: LiveSocketTarget,
+) -> Result<usize, String> {
+    let names: Vec<&str> = sockets.iter().map(|(name, _)| name.as_str()).collect();
+
+    if names.is_empty() {
+        return Err("No `new LiveSocket(...)` declaration found".to_string());
+    }
+
+    match target {
+        LiveSocketTarget::Only if names.len() == 1 => Ok(0),
+        LiveSocketTarget::Only => Err(format!(
+            "Found {} LiveSocket declarations ({}), pass a variable name or an index to pick one",
+            names.len(),
+            names.join(", ")
+        )),
+        LiveSocketTarget::Name(name) => {
+            let positions: Vec<usize> = names
+                .iter()
+                .enumerate()
+                .filter(|(_, socket)| **socket == name)
+                .map(|(position, _)| position)
+                .collect();
+
+            match positions.as_slice() {
+                [position] => Ok(*position),
+                [] => Err(format!(
+                    "No LiveSocket declaration is assigned to `{}`",
+                    name
+                )),
+                _ => Err(format!(
+                    "Found {} LiveSocket declarations assigned to `{}`, pass an index to pick one",
+                    positions.len(),
+                    name
+                )),
+            }
+        }
+        LiveSocketTarget::Index(index) if index < names.len() => Ok(index),
+        LiveSocketTarget::Index(index) => Err(format!(
+            "LiveSocket index {} is out of range, the file has {} declarations",
+            index,
+            names.len()
+        )),
+    }
+}
+
+/// Extends the `hooks` object like [`extend_hook_object_to_ast`], on the `new LiveSocket(...)`
+/// declaration picked by `target` for files that initialize more than one socket.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code, or an error message if parsing fails,
+/// the target does not exist, or the target is ambiguous (e.g. [`LiveSocketTarget::Only`]
+/// with several sockets).
+pub fn extend_hook_object_to_ast_for_socket(
+    file_content: &str,
+    new_objects: Vec<&str>,
+    target: LiveSocketTarget,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let (mut module, comments, cm) = parse(file_content)?;
+    let index = resolve_live_socket_target(&module, target)?;
+
+    let mut hook_extender = HookExtender::new("liveSocket", new_objects).with_socket_index(index);
+    module.visit_mut_with(&mut hook_extender);
+
+    if hook_extender.find != FindCondition::Found {
+        return Err(hook_extender.find.message().to_string());
+    }
+
+    code_gen_from_ast_module_with_options(
+        &mut module,
+        comments,
+        cm,
+        file_content,
+        &EmitOptions::default(),
+    )
+}
+
+/// Outcome of [`extend_hook_object_to_ast_with_report`].
+#[derive(Debug)]
+pub struct HookExtension {
+    pub code: String,
+    /// Hook names that were not in the `hooks` object and were added.
+    pub added: Vec<String>,
+    /// Hook names that were already in the `hooks` object and were skipped.
+    pub skipped: Vec<String>,
+}
+
+/// Extends the `hooks` object like [`extend_hook_object_to_ast`], also reporting which hook
+/// names were newly added and which ones were already present.
+pub fn extend_hook_object_to_ast_with_report(
+    file_content: &str,
+    new_objects: Vec<&str>,
+) -> Result<HookExtension, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut hook_extender = HookExtender::new("liveSocket", new_objects);
+
+    let code = code_gen_from_ast_vist(file_content, &mut hook_extender)?;
+    if hook_extender.find == FindCondition::Found {
+        Ok(HookExtension {
+            code,
+            added: hook_extender.added,
+            skipped: hook_extender.skipped,
+        })
+    } else {
+        Err(hook_extender.find.message().to_string())
+    }
+}
+
+/// Strict variant of [`extend_hook_object_to_ast`]: instead of silently skipping hook names
+/// that are already in the `hooks` object (as a shorthand property, or the same `...Name`
+/// spread), it refuses the whole edit and lists them. Members brought in by a spread can't
+/// be known statically, so `Name` is not checked against `...Hooks`.
+///
+/// # Returns
+/// A `Result` containing the updated code, or an error message listing the conflicting
+/// names, or why the `hooks` object could not be edited.
+pub fn extend_hook_object_to_ast_strict(
+    file_content: &str,
+    new_objects: Vec<&str>,
+) -> Result<String, String> {
+    let report = extend_hook_object_to_ast_with_report(file_content, new_objects)?;
+    if report.skipped.is_empty() {
+        Ok(report.code)
+    } else {
+        Err(format!(
+            "Hook names already in the hooks object: {}",
+            report.skipped.join(", ")
+        ))
+    }
+}
+
+pub fn find_live_socket_node_from_ast(file_content: &str) -> Result<bool, bool> {
+    let mut hook_extender = HookExtender::new("liveSocket", vec![]);
+    let _result = code_gen_from_ast_vist(file_content, &mut hook_extender);
+    if hook_extender.find == FindCondition::Found {
+        Ok(true)
+    } else {
+        Err(false)
+    }
+}
+
+/// The `hooks` object of the options of `decl` when it declares
+/// `liveSocket = new LiveSocket(...)`.
+fn live_socket_hooks_mut(decl: &mut VarDeclarator) -> Option<&mut ObjectLit> {
+    let is_live_socket = decl
+        .name
+        .as_ident()
+        .is_some_and(|ident| ident.sym == "liveSocket");
+    let Some(Expr::New(new_expr)) = decl.init.as_deref_mut() else {
+        return None;
+    };
+    if !is_live_socket
+        || !matches!(&*new_expr.callee, Expr::Ident(callee) if callee.sym == "LiveSocket")
+    {
+        return None;
+    }
+
+    let Some(Expr::Object(options)) = new_expr
+        .args
+        .as_mut()
+        .and_then(|args| args.last_mut())
+        .map(|arg| &mut *arg.expr)
+    else {
+        return None;
+    };
+
+    options.props.iter_mut().find_map(|prop| {
+        let PropOrSpread::Prop(prop) = prop else {
+            return None;
+        };
+        match &mut **prop {
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(key),
+                value,
+            }) if key.sym == "hooks" => match &mut **value {
+                Expr::Object(hooks) => Some(hooks),
+                _ => None,
+            },
+            _ => None,
+        }
+    })
+}
+
+struct HookSpreadReplacer<'a> {
+    old_spread: &'a str,
+    new_spread: &'a str,
+    find: FindCondition,
+}
+
+impl VisitMut for HookSpreadReplacer<'_> {
+    fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
+        for decl in &mut var_decl.decls {
+            let Some(hooks) = live_socket_hooks_mut(decl) else {
+                continue;
+            };
+
+            self.find = FindCondition::NotFound(format!(
+                "The hooks object has no `...{}` spread.",
+                self.old_spread
+            ));
+            for hook in &mut hooks.props {
+                if let PropOrSpread::Spread(spread) = hook {
+                    if let Expr::Ident(ident) = &mut *spread.expr {
+                        if ident.sym == self.old_spread {
+                            ident.sym = self.new_spread.into();
+                            self.find = FindCondition::Found;
+                        }
+                    }
+                }
+            }
+        }
+
+        var_decl.visit_mut_children_with(self)
+    }
+}
+
+/// Replaces a spread of the `hooks` object of `liveSocket`, e.g. renames
+/// `hooks: { ...MishkaComponents }` to `hooks: { ...Components }`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `old_spread`: The identifier of the spread to replace, with or without the `...`.
+/// - `new_spread`: The new identifier, with or without the `...`.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code, or the [`FindCondition::NotFound`]
+/// message when `liveSocket`, its `hooks` object or the spread does not exist.
+pub fn replace_hook_spread_ast(
+    file_content: &str,
+    old_spread: &str,
+    new_spread: &str,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut replacer = HookSpreadReplacer {
+        old_spread: old_spread.trim_start_matches("..."),
+        new_spread: new_spread.trim_start_matches("..."),
+        find: FindCondition::NotFound("".to_string()),
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut replacer);
+    if replacer.find == FindCondition::Found {
+        result
+    } else {
+        Err(replacer.find.message().to_string())
+    }
+}
+
+struct HooksOrderNormalizer {
+    find: FindCondition,
+}
+
+/// Spreads come first, then named hooks by name, then anything else (e.g. computed keys).
+fn hook_order_key(prop: &PropOrSpread) -> (u8, String) {
+    match prop {
+        PropOrSpread::Spread(_) => (0, String::new()),
+        PropOrSpread::Prop(prop) => match &**prop {
+            Prop::Shorthand(ident) => (1, ident.sym.to_lowercase()),
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Ident(key),
+                ..
+            }) => (1, key.sym.to_lowercase()),
+            Prop::KeyValue(KeyValueProp {
+                key: PropName::Str(key),
+                ..
+            }) => (1, key.value.to_lowercase()),
+            _ => (2, String::new()),
+        },
+    }
+}
+
+impl VisitMut for HooksOrderNormalizer {
+    fn visit_mut_var_decl(&mut self, var_decl: &mut VarDecl) {
+        for decl in &mut var_decl.decls {
+            if let Some(hooks) = live_socket_hooks_mut(decl) {
+                // A stable sort keeps the spreads in their original order
+                hooks.props.sort_by_key(hook_order_key);
+                self.find = FindCondition::Found;
+            }
+        }
+
+        var_decl.visit_mut_children_with(self)
+    }
+}
+
+/// Reorders the `hooks` object of `liveSocket` to the `hooks: { ...Hooks, NamedHook }`
+/// convention: every spread first, in its original order, followed by the named hooks
+/// sorted alphabetically (ignoring case).
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+///
+/// # Returns
+/// A `Result` containing the regenerated code, or an error message when parsing fails or
+/// `liveSocket` has no `hooks` object.
+pub fn normalize_hooks_order_ast(file_content: &str) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut normalizer = HooksOrderNormalizer {
+        find: FindCondition::NotFound("No `liveSocket` with a `hooks` object found".to_string()),
+    };
+
+    let result = code_gen_from_ast_vist(file_content, &mut normalizer);
+    if normalizer.find == FindCondition::Found {
+        result
+    } else {
+        Err(normalizer.find.message().to_string())
+    }
+}
+
+struct SocketConnectFinder<'a> {
+    socket_var_name: &'a str,
+    found: bool,
+}
+
+impl SocketConnectFinder<'_> {
+    /// The socket itself, or a member path ending in it such as `window.liveSocket`.
+    fn is_socket(&self, expr: &Expr) -> bool {
+        match expr {
+            Expr::Ident(ident) => ident.sym == self.socket_var_name,
+            Expr::Member(MemberExpr {
+                prop: MemberProp::Ident(prop),
+                ..
+            }) => prop.sym == self.socket_var_name,
+            _ => false,
+        }
+    }
+}
+
+impl Visit for SocketConnectFinder<'_> {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        if let Callee::Expr(callee) = &call_expr.callee {
+            if let Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) = &**callee
+            {
+                if prop.sym == "connect" && self.is_socket(obj) {
+                    self.found = true;
+                }
+            }
+        }
+
+        call_expr.visit_children_with(self)
+    }
+}
+
+/// Checks whether the JavaScript source code calls `liveSocket.connect()`, also through a
+/// member path such as `window.liveSocket.connect()`.
+///
+/// This complements [`find_live_socket_node_from_ast`]: a file can create the socket and
+/// still never connect it.
+pub fn has_live_socket_connect_from_ast(file_content: &str) -> Result<bool, bool> {
+    has_socket_connect_from_ast(file_content, "liveSocket")
+}
+
+/// Same as [`has_live_socket_connect_from_ast`], for a socket stored in `socket_var_name`.
+pub fn has_socket_connect_from_ast(
+    file_content: &str,
+    socket_var_name: &str,
+) -> Result<bool, bool> {
+    let (module, _comments, _cm) = parse(file_content).map_err(|_| false)?;
+
+    let mut finder = SocketConnectFinder {
+        socket_var_name,
+        found: false,
+    };
+    module.visit_with(&mut finder);
+
+    if finder.found {
+        Ok(true)
+    } else {
+        Err(false)
+    }
+}
+
+/// Finds a `document.querySelector("meta[name='csrf-token']")` call.
+struct CsrfTokenQueryFinder {
+    found: bool,
+}
+
+impl Visit for CsrfTokenQueryFinder {
+    fn visit_call_expr(&mut self, call_expr: &CallExpr) {
+        let is_query_selector = matches!(&call_expr.callee, Callee::Expr(callee)
+            if matches!(&**callee, Expr::Member(MemberExpr {
+                obj,
+                prop: MemberProp::Ident(prop),
+                ..
+            }) if prop.sym == "querySelector"
+                && matches!(&**obj, Expr::Ident(document) if document.sym == "document")));
+
+        let selector = call_expr.args.first().and_then(|arg| match &*arg.expr {
+            Expr::Lit(Lit::Str(selector)) => Some(
+                selector
+                    .value
+                    .chars()
+                    .filter(|char| !matches!(char, '\'' | '"') && !char.is_whitespace())
+                    .collect::<String>(),
+            ),
+            _ => None,
+        });
+
+        if is_query_selector && selector.as_deref() == Some("meta[name=csrf-token]") {
+            self.found = true;
+            return;
+        }
+
+        call_expr.visit_children_with(self)
+    }
+}
+
+/// Finds a variable whose initializer queries the csrf meta tag.
+struct CsrfTokenSetupFinder {
+    found: bool,
+}
+
+impl Visit for CsrfTokenSetupFinder {
+    fn visit_var_declarator(&mut self, decl: &VarDeclarator) {
+        if let Some(init) = &decl.init {
+            let mut query_finder = CsrfTokenQueryFinder { found: false };
+            init.visit_with(&mut query_finder);
+            self.found |= query_finder.found;
+        }
+
+        decl.visit_children_with(self)
+    }
+}
+
+/// Checks that a variable is initialized from the Phoenix csrf meta tag, as in
+/// `let csrfToken = document.querySelector("meta[name='csrf-token']").getAttribute("content")`.
+///
+/// # Returns
+/// `Ok(true)` when such a variable exists, or `Err(false)` when it does not or parsing fails.
+pub fn has_csrf_token_setup_from_ast(file_content: &str) -> Result<bool, bool> {
+    let (module, _comments, _cm) = parse(file_content).map_err(|_| false)?;
+
+    let mut finder = CsrfTokenSetupFinder { found: false };
+    module.visit_with(&mut finder);
+
+    if finder.found {
+        Ok(true)
+    } else {
+        Err(false)
+    }
+}
+
+/// The source form of a scalar value (string, number, boolean or `null`), `None` otherwise.
+fn scalar_source(expr: &Expr) -> Option<String> {
+    match expr {
+        Expr::Lit(Lit::Str(str)) => Some(
+            str.raw
+                .as_ref()
+                .map(|raw| raw.to_string())
+                .unwrap_or_else(|| format!("{:?}", &*str.value)),
+        ),
+        Expr::Lit(Lit::Num(num)) => Some(
+            num.raw
+                .as_ref()
+                .map(|raw| raw.to_string())
+                .unwrap_or_else(|| num.value.to_string()),
+        ),
+        Expr::Lit(Lit::Bool(bool)) => Some(bool.value.to_string()),
+        Expr::Lit(Lit::Null(_)) => Some("null".to_string()),
+        Expr::Unary(UnaryExpr {
+            op: UnaryOp::Minus,
+            arg,
+            ..
+        }) => scalar_source(arg)
+            .filter(|_| matches!(&**arg, Expr::Lit(Lit::Num(_))))
+            .map(|value| format!("-{}", value)),
+        _ => None,
+    }
+}
+
+/// Lists the top-level keys of the options object of `new LiveSocket(...)`, e.g. `hooks`,
+/// `longPollFallbackMs` and `params`, for a quick summary of the socket setup.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `target`: The `new LiveSocket(...)` declaration to read, usually
+///   `LiveSocketTarget::Name("liveSocket")`.
+///
+/// # Returns
+/// A `Result` containing every key in source order with the source form of its value when
+/// the value is a string, number, boolean or `null` (`None` for objects, identifiers and
+/// other expressions; spreads are listed as `...Name`), or an error message if parsing
+/// fails, the target socket does not exist or it has no options object.
+pub fn get_live_socket_options_from_ast(
+    file_content: &str,
+    target: LiveSocketTarget,
+) -> Result<Vec<(String, Option<String>)>, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    let new_expr = find_live_socket(&module, target)?;
+    let options = live_socket_options(&new_expr)
+        .ok_or_else(|| "`new LiveSocket(...)` has no options object".to_string())?;
+
+    let entries = options
+        .props
+        .iter()
+        .filter_map(|prop| match prop {
+            PropOrSpread::Spread(spread) => match &*spread.expr {
+                Expr::Ident(ident) => Some((format!("...{}", ident.sym), None)),
+                _ => None,
+            },
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(ident) => Some((ident.sym.to_string(), None)),
+                Prop::KeyValue(KeyValueProp { key, value }) => {
+                    let key = match key {
+                        PropName::Ident(ident) => ident.sym.to_string(),
+                        PropName::Str(str) => str.value.to_string(),
+                        PropName::Num(num) => num.value.to_string(),
+                        _ => return None,
+                    };
+                    Some((key, scalar_source(value)))
+                }
+                Prop::Method(MethodProp {
+                    key: PropName::Ident(ident),
+                    ..
+                }) => Some((ident.sym.to_string(), None)),
+                _ => None,
+            },
+        })
+        .collect();
+
+    Ok(entries)
+}
+
+/// How far the LiveSocket initialization gets, see [`diagnose_live_socket_from_ast`].
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct LiveSocketDiagnosis {
+    /// The socket variable is declared.
+    pub live_socket_declared: bool,
+    /// The variable is initialized with `new LiveSocket(...)`.
+    pub new_live_socket: bool,
+    /// The last argument of `new LiveSocket(...)` is an options object.
+    pub has_options: bool,
+    /// The options object has a `hooks` key.
+    pub has_hooks: bool,
+}
+
+impl LiveSocketDiagnosis {
+    /// Why [`extend_hook_object_to_ast`] can not edit the socket, `None` when it can. A
+    /// missing `hooks` key is not a problem, since it is created on the first extension.
+    pub fn problem(&self) -> Option<&'static str> {
+        if !self.live_socket_declared {
+            Some("The socket variable is not declared")
+        } else if !self.new_live_socket {
+            Some("The socket variable is not initialized with `new LiveSocket(...)`")
+        } else if !self.has_options {
+            Some("`new LiveSocket(...)` has no options object as its last argument")
+        } else {
+            None
+        }
+    }
+}
+
+/// Diagnoses the LiveSocket initialization stage by stage, to give a precise reason when
+/// [`extend_hook_object_to_ast`] would fail on a hand-edited `app.js`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `target`: The socket to diagnose, usually `LiveSocketTarget::Name("liveSocket")`. A
+///   name that is declared but not initialized with `new LiveSocket(...)` is reported as
+///   such.
+///
+/// # Returns
+/// A `Result` containing the [`LiveSocketDiagnosis`], or an error message when the code
+/// can not be parsed, e.g. an unterminated `new LiveSocket("/live", Socket, {`, or when
+/// several declarations match `target`.
+pub fn diagnose_live_socket_from_ast(
+    file_content: &str,
+    target: LiveSocketTarget,
+) -> Result<LiveSocketDiagnosis, String> {
+    let (module, _comments, _cm) = parse(file_content)?;
+
+    let LiveSocketDeclCollector { sockets, declared } = collect_declarations(&module);
+    let missing = match target {
+        LiveSocketTarget::Only => sockets.is_empty(),
+        LiveSocketTarget::Name(name) => sockets.iter().all(|(socket, _)| socket != name),
+        LiveSocketTarget::Index(index) => index >= sockets.len(),
+    };
+    if missing {
+        let live_socket_declared = match target {
+            LiveSocketTarget::Name(name) => declared.iter().any(|variable| variable == name),
+            _ => false,
+        };
+        return Ok(LiveSocketDiagnosis {
+            live_socket_declared,
+            ..LiveSocketDiagnosis::default()
+        });
+    }
+
+    let (_, new_expr) = &sockets[live_socket_index(&sockets, target)?];
+    let options = live_socket_options(new_expr);
+    let has_hooks = options.is_some_and(|options| {
+        options.props.iter().any(|prop| match prop {
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(ident) => ident.sym == "hooks",
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    ..
+                }) => key.sym == "hooks",
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Str(key),
+                    ..
+                }) => key.value == "hooks",
+                _ => false,
+            },
+            PropOrSpread::Spread(_) => false,
+        })
+    });
+
+    Ok(LiveSocketDiagnosis {
+        live_socket_declared: true,
+        new_live_socket: true,
+        has_options: options.is_some(),
+        has_hooks,
+    })
+}
+
+/// Matches a hook of the `hooks` object of a LiveSocket, see [`hook_exists_in_ast`].
+struct HookFinder<'a> {
+    hook_name: &'a str,
+}
+
+impl HookFinder<'_> {
+    fn matches(&self, prop: &PropOrSpread) -> bool {
+        let spread_name = self.hook_name.strip_prefix("...");
+        match prop {
+            PropOrSpread::Spread(spread) => matches!(&*spread.expr, Expr::Ident(ident)
+                if ident.sym == spread_name.unwrap_or(self.hook_name)),
+            PropOrSpread::Prop(_) if spread_name.is_some() => false,
+            PropOrSpread::Prop(prop) => match &**prop {
+                Prop::Shorthand(ident) => ident.sym == self.hook_name,
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Ident(key),
+                    ..
+                }) => key.sym == self.hook_name,
+                Prop::KeyValue(KeyValueProp {
+                    key: PropName::Str(key),
+                    ..
+                }) => key.value == self.hook_name,
+                _ => false,
+            },
+        }
+    }
+}
+
+/// Checks whether the `hooks` object of a LiveSocket already registers a hook, e.g. before
+/// adding `CopyMixInstallationHook`.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `hook_name`: The hook name, matched against shorthand properties, property keys and
+///   spreads; `...Hooks` only matches the spread.
+/// - `target`: The `new LiveSocket(...)` declaration to look in, usually
+///   `LiveSocketTarget::Name("liveSocket")`.
+///
+/// # Returns
+/// `Ok(true)` when the hook is registered, `Ok(false)` when it is not, and `Err(false)`
+/// when the code can not be parsed or the target socket does not exist.
+pub fn hook_exists_in_ast(
+    file_content: &str,
+    hook_name: &str,
+    target: LiveSocketTarget,
+) -> Result<bool, bool> {
+    let (module, _comments, _cm) = parse(file_content).map_err(|_| false)?;
+    let new_expr = find_live_socket(&module, target).map_err(|_| false)?;
+
+    let finder = HookFinder { hook_name };
+    let found = live_socket_options(&new_expr)
+        .and_then(live_socket_hooks)
+        .is_some_and(|hooks| hooks.props.iter().any(|hook| finder.matches(hook)));
+
+    Ok(found)
+}
+
+/// Removes specified objects from the `hooks` object in the JavaScript AST.
+///
+/// This function parses the given JavaScript source code, checks for the presence of a
+/// `liveSocket` variable, and removes specified properties from the `hooks` object.
+/// If the `hooks` object or `liveSocket` variable is not found, an appropriate error is returned.
+///
+/// # Arguments
+/// - `file_content`: The JavaScript source code as a string slice.
+/// - `objects_to_remove`: An iterable collection of object names (as strings) to be removed from the `hooks` object.
+///
+/// # Returns
+/// A `Result` containing the updated JavaScript code as a `String` on success,
+/// or an error message if parsing or manipulation fails.
+///
+/// # Behavior
+/// - Ensures the `liveSocket` variable exists in the AST.
+/// - Locates the `hooks` object or initializes it if absent.
+/// - Removes specified properties from the `hooks` object while retaining all others.
+pub fn remove_objects_of_hooks_from_ast(
+    file_content: &str,
+    objects_to_remove: Vec<&str>,
+) -> Result<String, String> {
+    remove_objects_of_hooks_from_ast_with_options(
+        file_content,
+        objects_to_remove,
+        &EmitOptions::default(),
+    )
+}
+
+/// Same as [`remove_objects_of_hooks_from_ast`], emitting the result with the given
+/// [`EmitOptions`].
+pub fn remove_objects_of_hooks_from_ast_with_options(
+    file_content: &str,
+    objects_to_remove: Vec<&str>,
+    options: &EmitOptions,
+) -> Result<String, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut hook_extender = HookExtender::new("liveSocket", vec![]);
+
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    remove_objects_of_hooks_from_module(&mut module, &mut hook_extender, &objects_to_remove);
+
+    let result =
+        code_gen_from_ast_module_with_options(&mut module, comments, cm, file_content, options);
+    if hook_extender.find == FindCondition::Found {
+        result
+    } else {
+        Err(hook_extender.find.message().to_string())
+    }
+}
+
+/// Result of [`remove_objects_of_hooks_from_ast_with_report`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct HookRemoval {
+    pub code: String,
+    /// Hook names that were in the `hooks` object and were removed.
+    pub removed: Vec<String>,
+    /// Hook names that were not in the `hooks` object, so nothing changed for them.
+    pub not_found: Vec<String>,
+}
+
+/// Removes objects from the `hooks` object like [`remove_objects_of_hooks_from_ast`], also
+/// reporting which names were actually removed and which ones were not present, e.g. when
+/// a generator runs a second time.
+pub fn remove_objects_of_hooks_from_ast_with_report(
+    file_content: &str,
+    objects_to_remove: Vec<&str>,
+) -> Result<HookRemoval, String> {
+    ensure_source_not_empty(file_content)?;
+    let mut hook_extender = HookExtender::new("liveSocket", vec![]);
+
+    let (mut module, comments, cm) = parse(file_content)?;
+
+    remove_objects_of_hooks_from_module(&mut module, &mut hook_extender, &objects_to_remove);
+
+    if hook_extender.find != FindCondition::Found {
+        return Err(hook_extender.find.message().to_string());
+    }
+
+    let code = code_gen_from_ast_module(&mut module, comments, cm)?;
+    let mut not_found: Vec<String> = Vec::new();
+    for name in objects_to_remove {
+        if !hook_extender.removed.iter().any(|removed| removed == name)
+            && !not_found.iter().any(|missing| missing == name)
+        {
+            not_found.push(name.to_string());
+        }
+    }
+
+    Ok(HookRemoval {
+        code,
+        removed: hook_extender.removed,
+        not_found,
+    })
+}
+
+/// Dry run of [`extend_hook_object_to_ast`]: reports whether adding `new_objects` to the
+/// `hooks` object would change the file, e.g. `false` when all of them already exist.
+pub fn extend_hook_object_to_ast_would_change(
+    file_content: &str,
+    new_objects: Vec<&str>,
+) -> Result<bool, String> {
     let mut hook_extender = HookExtender::new("liveSocket", new_objects);
 
-    let result = code_gen_from_ast_vist(file_content, &mut hook_extender);
+    let changed = changes_from_ast_vist(file_content, &mut hook_extender)?;
     if hook_extender.find == FindCondition::Found {
-        result
+        Ok(changed)
     } else {
         Err(hook_extender.find.message().to_string())
     }
 }
 
-pub fn find_live_socket_node_from_ast(file_content: &str) -> Result<bool, bool> {
+/// Dry run of [`remove_objects_of_hooks_from_ast`]: reports whether removing
+/// `objects_to_remove` from the `hooks` object would change the file.
+pub fn remove_objects_of_hooks_from_ast_would_change(
+    file_content: &str,
+    objects_to_remove: Vec<&str>,
+) -> Result<bool, String> {
     let mut hook_extender = HookExtender::new("liveSocket", vec![]);
-    let _result = code_gen_from_ast_vist(file_content, &mut hook_extender);
+
+    let (mut module, _comments, _cm) = parse(file_content)?;
+    let original = module.clone();
+
+    remove_objects_of_hooks_from_module(&mut module, &mut hook_extender, &objects_to_remove);
+
     if hook_extender.find == FindCondition::Found {
-        Ok(true)
+        Ok(!original.eq_ignore_span(&module))
     } else {
-        Err(false)
+        Err(hook_extender.find.message().to_string())
     }
 }
 
-/// Removes specified objects from the `hooks` object in the JavaScript AST.
-///
-/// This function parses the given JavaScript source code, checks for the presence of a
-/// `liveSocket` variable, and removes specified properties from the `hooks` object.
-/// If the `hooks` object or `liveSocket` variable is not found, an appropriate error is returned.
+/// Imports a hook module and registers the hook in the `hooks` object of `liveSocket`, in a
+/// single parse and emit.
 ///
 /// # Arguments
 /// - `file_content`: The JavaScript source code as a string slice.
-/// - `objects_to_remove`: An iterable collection of object names (as strings) to be removed from the `hooks` object.
+/// - `import_line`: The import of the hook module, e.g. `import CopyHook from "./copy";`.
+///   It is skipped when the file already has it.
+/// - `hook_name`: The name added to the `hooks` object.
 ///
 /// # Returns
-/// A `Result` containing the updated JavaScript code as a `String` on success,
-/// or an error message if parsing or manipulation fails.
-///
-/// # Behavior
-/// - Ensures the `liveSocket` variable exists in the AST.
-/// - Locates the `hooks` object or initializes it if absent.
-/// - Removes specified properties from the `hooks` object while retaining all others.
-pub fn remove_objects_of_hooks_from_ast(
+/// A `Result` containing the updated JavaScript code, or an error message telling whether
+/// the import or the hook registration step failed.
+pub fn add_hook_ast(
     file_content: &str,
-    objects_to_remove: Vec<&str>,
+    import_line: &str,
+    hook_name: &str,
 ) -> Result<String, String> {
-    let mut hook_extender = HookExtender::new("liveSocket", vec![]);
+    ensure_source_not_empty(file_content)?;
+    let (mut module, comments, cm) = parse(file_content)?;
 
-    let (mut module, comments, cm) = parse(file_content).expect("Failed to parse imports");
+    insert_import_to_module(&mut module, import_line)
+        .map_err(|error| format!("Failed to insert the hook import: {}", error))?;
 
+    let mut hook_extender = HookExtender::new("liveSocket", vec![hook_name]);
     module.visit_mut_with(&mut hook_extender);
+    if hook_extender.find != FindCondition::Found {
+        return Err(format!(
+            "Failed to register the hook: {}",
+            hook_extender.find.message()
+        ));
+    }
+
+    code_gen_from_ast_module(&mut module, comments, cm)
+}
+
+fn remove_objects_of_hooks_from_module(
+    module: &mut Module,
+    hook_extender: &mut HookExtender<'_>,
+    objects_to_remove: &[&str],
+) {
+    module.visit_mut_with(hook_extender);
 
     for item in &mut module.body {
         if let ModuleItem::Stmt(Stmt::Decl(Decl::Var(var_decl))) = item {
@@ -264,7 +1213,7 @@ pub fn remove_objects_of_hooks_from_ast(
                                 if let Expr::Object(obj_expr) = &mut **expr {
                                     hook_extender.remove_objects_from_hooks(
                                         obj_expr,
-                                        objects_to_remove.clone(),
+                                        objects_to_remove.to_vec(),
                                     );
                                 }
                             }
@@ -274,13 +1223,6 @@ pub fn remove_objects_of_hooks_from_ast(
             }
         }
     }
-
-    let result = code_gen_from_ast_module(&mut module, comments, cm);
-    if hook_extender.find == FindCondition::Found {
-        Ok(result)
-    } else {
-        Err(hook_extender.find.message().to_string())
-    }
 }
 
 #[cfg(test)]
@@ -430,4 +1372,545 @@ mod tests {
 
         assert!(result.is_err())
     }
+
+    #[test]
+    fn test_invalid_javascript_returns_error() {
+        let invalid = "let liveSocket = new LiveSocket(;";
+
+        assert!(extend_hook_object_to_ast(invalid, vec!["ObjectOne"]).is_err());
+        assert!(find_live_socket_node_from_ast(invalid).is_err());
+        assert!(remove_objects_of_hooks_from_ast(invalid, vec!["ObjectOne"]).is_err());
+    }
+
+    #[test]
+    fn test_hooks_would_change() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks, CopyMixInstallationHook },
+          longPollFallbackMs: 2500,
+        });
+        "#;
+
+        assert_eq!(
+            extend_hook_object_to_ast_would_change(code, vec!["ObjectOne"]),
+            Ok(true)
+        );
+        assert_eq!(
+            extend_hook_object_to_ast_would_change(code, vec!["CopyMixInstallationHook"]),
+            Ok(false)
+        );
+
+        assert_eq!(
+            remove_objects_of_hooks_from_ast_would_change(code, vec!["CopyMixInstallationHook"]),
+            Ok(true)
+        );
+        assert_eq!(
+            remove_objects_of_hooks_from_ast_would_change(code, vec!["ObjectOne"]),
+            Ok(false)
+        );
+
+        assert!(extend_hook_object_to_ast_would_change("let a = 1;", vec!["ObjectOne"]).is_err());
+    }
+
+    #[test]
+    fn test_add_hook_ast() {
+        let code = r#"
+        import { Socket } from "phoenix";
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks },
+        });
+        "#;
+
+        let result = add_hook_ast(code, "import CopyHook from \"./copy_hook\";", "CopyHook");
+        assert!(result.is_ok(), "Adding the hook failed: {:?}", result.err());
+        let output = result.unwrap();
+        assert!(output.contains("import CopyHook from \"./copy_hook\";"));
+        assert!(output.contains("...Hooks,\n        CopyHook"));
+
+        let result = add_hook_ast(code, "import CopyHook from;", "CopyHook");
+        assert!(result
+            .unwrap_err()
+            .starts_with("Failed to insert the hook import"));
+
+        let result = add_hook_ast(
+            "let socket = 1;",
+            "import CopyHook from \"./copy_hook\";",
+            "CopyHook",
+        );
+        assert!(result
+            .unwrap_err()
+            .starts_with("Failed to register the hook"));
+    }
+
+    #[test]
+    fn test_extend_hook_object_to_ast_with_report() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks, CopyMixInstallationHook },
+        });
+        "#;
+
+        let result = extend_hook_object_to_ast_with_report(
+            code,
+            vec![
+                "ObjectOne",
+                "CopyMixInstallationHook",
+                "...Hooks",
+                "ObjectTwo",
+            ],
+        );
+        assert!(result.is_ok(), "Extending failed: {:?}", result.err());
+        let report = result.unwrap();
+        assert_eq!(report.added, vec!["ObjectOne", "ObjectTwo"]);
+        assert_eq!(report.skipped, vec!["CopyMixInstallationHook", "...Hooks"]);
+        assert!(report.code.contains("ObjectTwo"));
+
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {});
+        "#;
+        let report = extend_hook_object_to_ast_with_report(code, vec!["ObjectOne"]).unwrap();
+        assert_eq!(report.added, vec!["ObjectOne"]);
+        assert!(report.skipped.is_empty());
+
+        assert!(extend_hook_object_to_ast_with_report("let a = 1;", vec!["ObjectOne"]).is_err());
+    }
+
+    #[test]
+    fn test_extend_hook_object_to_ast_with_crlf() {
+        let code = "let liveSocket = new LiveSocket(\"/live\", Socket, {\r\n  hooks: { ...Hooks }\r\n});\r\n";
+        let options = EmitOptions {
+            new_line: NewLine::CrLf,
+            ..Default::default()
+        };
+
+        let result =
+            extend_hook_object_to_ast_with_options(code, vec!["CopyHook"], &options).unwrap();
+        assert!(result.contains("...Hooks,\r\n        CopyHook\r\n"));
+        assert_eq!(result.matches("\r\n").count(), result.matches('\n').count());
+
+        let result =
+            remove_objects_of_hooks_from_ast_with_options(&result, vec!["CopyHook"], &options)
+                .unwrap();
+        assert!(!result.contains("CopyHook"));
+        assert_eq!(result.matches("\r\n").count(), result.matches('\n').count());
+    }
+
+    #[test]
+    fn test_has_live_socket_connect_from_ast() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {});
+        window.addEventListener("load", () => liveSocket.connect());
+        "#;
+        assert_eq!(has_live_socket_connect_from_ast(code), Ok(true));
+        assert_eq!(has_socket_connect_from_ast(code, "socket"), Err(false));
+
+        let code = r#"
+        let socket = new LiveSocket("/live", Socket, {});
+        socket.connect();
+        liveSocket.disconnect();
+        "#;
+        assert_eq!(has_live_socket_connect_from_ast(code), Err(false));
+        assert_eq!(has_socket_connect_from_ast(code, "socket"), Ok(true));
+
+        assert_eq!(has_live_socket_connect_from_ast("let x = ;"), Err(false));
+
+        let code = r#"
+        window.liveSocket = new LiveSocket("/live", Socket, {});
+        window.liveSocket.connect();
+        "#;
+        assert_eq!(has_live_socket_connect_from_ast(code), Ok(true));
+        assert_eq!(
+            has_live_socket_connect_from_ast("window.liveSocketAdmin.connect();"),
+            Err(false)
+        );
+    }
+
+    #[test]
+    fn test_replace_hook_spread_ast() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks, ...MishkaComponents, CopyMixInstallationHook },
+        });
+        "#;
+
+        let result = replace_hook_spread_ast(code, "MishkaComponents", "...Components");
+        assert!(result.is_ok(), "Replacing failed: {:?}", result.err());
+        let output = result.unwrap();
+        assert!(
+            output.contains("...Hooks,\n        ...Components,\n        CopyMixInstallationHook")
+        );
+        assert!(!output.contains("MishkaComponents"));
+
+        let result = replace_hook_spread_ast(code, "...CopyMixInstallationHook", "Other");
+        assert_eq!(
+            result,
+            Err("The hooks object has no `...CopyMixInstallationHook` spread.".to_string())
+        );
+
+        let result = replace_hook_spread_ast("let socket = 1;", "MishkaComponents", "Components");
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn test_normalize_hooks_order_ast() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { TooltipHook, ...Hooks, copyHook: CopyHook, AutoFocus, ...MishkaComponents },
+          longPollFallbackMs: 2500,
+        });
+        "#;
+
+        let result = normalize_hooks_order_ast(code).unwrap();
+        assert!(
+            result.contains(
+                "...Hooks,\n        ...MishkaComponents,\n        AutoFocus,\n        copyHook: CopyHook,\n        TooltipHook\n"
+            ),
+            "{}",
+            result
+        );
+        assert_eq!(normalize_hooks_order_ast(&result), Ok(result.clone()));
+
+        assert!(normalize_hooks_order_ast(
+            "let liveSocket = new LiveSocket(\"/live\", Socket, {});"
+        )
+        .is_err());
+        assert!(normalize_hooks_order_ast("let x = ;").is_err());
+    }
+
+    #[test]
+    fn test_remove_objects_of_hooks_from_ast_with_report() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks, CopyHook, OtherHook },
+        });
+        "#;
+
+        let report =
+            remove_objects_of_hooks_from_ast_with_report(code, vec!["CopyHook", "MissingHook"])
+                .unwrap();
+        assert_eq!(report.removed, vec!["CopyHook".to_string()]);
+        assert_eq!(report.not_found, vec!["MissingHook".to_string()]);
+        assert!(!report.code.contains("CopyHook"));
+        assert!(report.code.contains("OtherHook"));
+
+        let report =
+            remove_objects_of_hooks_from_ast_with_report(&report.code, vec!["CopyHook"]).unwrap();
+        assert!(report.removed.is_empty());
+        assert_eq!(report.not_found, vec!["CopyHook".to_string()]);
+
+        let report = remove_objects_of_hooks_from_ast_with_report(code, vec!["...Hooks"]).unwrap();
+        assert_eq!(report.removed, vec!["...Hooks".to_string()]);
+
+        let code = r#"let otherSocket = new LiveSocket("/live", Socket, { hooks: {} });"#;
+        assert!(remove_objects_of_hooks_from_ast_with_report(code, vec!["CopyHook"]).is_err());
+    }
+
+    #[test]
+    fn test_hook_exists_in_ast() {
+        const LIVE_SOCKET: LiveSocketTarget = LiveSocketTarget::Name("liveSocket");
+
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks, CopyMixInstallationHook, Other: OtherHook },
+        });
+        "#;
+
+        assert_eq!(
+            hook_exists_in_ast(code, "CopyMixInstallationHook", LIVE_SOCKET),
+            Ok(true)
+        );
+        assert_eq!(hook_exists_in_ast(code, "Other", LIVE_SOCKET), Ok(true));
+        assert_eq!(hook_exists_in_ast(code, "...Hooks", LIVE_SOCKET), Ok(true));
+        assert_eq!(hook_exists_in_ast(code, "Hooks", LIVE_SOCKET), Ok(true));
+        assert_eq!(
+            hook_exists_in_ast(code, "...CopyMixInstallationHook", LIVE_SOCKET),
+            Ok(false)
+        );
+        assert_eq!(
+            hook_exists_in_ast(code, "MissingHook", LIVE_SOCKET),
+            Ok(false)
+        );
+
+        let code = r#"let liveSocket = new LiveSocket("/live", Socket, {});"#;
+        assert_eq!(
+            hook_exists_in_ast(code, "CopyMixInstallationHook", LIVE_SOCKET),
+            Ok(false)
+        );
+
+        let code = r#"let socket = new Socket("/socket", {});"#;
+        assert_eq!(
+            hook_exists_in_ast(code, "CopyMixInstallationHook", LIVE_SOCKET),
+            Err(false)
+        );
+        assert_eq!(
+            hook_exists_in_ast("let x = ;", "CopyMixInstallationHook", LIVE_SOCKET),
+            Err(false)
+        );
+
+        let code = r#"
+        let adminSocket = new LiveSocket("/admin/live", Socket, { hooks: { AdminHook } });
+        let liveSocket = new LiveSocket("/live", Socket, { hooks: { CopyHook } });
+        "#;
+        assert_eq!(
+            hook_exists_in_ast(code, "AdminHook", LIVE_SOCKET),
+            Ok(false)
+        );
+        let admin = LiveSocketTarget::Name("adminSocket");
+        assert_eq!(hook_exists_in_ast(code, "AdminHook", admin), Ok(true));
+        let first = LiveSocketTarget::Index(0);
+        assert_eq!(hook_exists_in_ast(code, "AdminHook", first), Ok(true));
+        let only = LiveSocketTarget::Only;
+        assert_eq!(hook_exists_in_ast(code, "AdminHook", only), Err(false));
+    }
+
+    #[test]
+    fn test_get_live_socket_options_from_ast() {
+        use crate::parsers::javascript::formatter::tests::app_js;
+
+        const LIVE_SOCKET: LiveSocketTarget = LiveSocketTarget::Name("liveSocket");
+
+        assert_eq!(
+            get_live_socket_options_from_ast(app_js(), LIVE_SOCKET).unwrap(),
+            vec![
+                ("longPollFallbackMs".to_string(), Some("2500".to_string())),
+                ("params".to_string(), None),
+                ("hooks".to_string(), None),
+            ]
+        );
+
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+            bindingPrefix: 'phx-', debug: false, timeout: -1, ...extra
+        });
+        "#;
+        assert_eq!(
+            get_live_socket_options_from_ast(code, LIVE_SOCKET).unwrap(),
+            vec![
+                ("bindingPrefix".to_string(), Some("'phx-'".to_string())),
+                ("debug".to_string(), Some("false".to_string())),
+                ("timeout".to_string(), Some("-1".to_string())),
+                ("...extra".to_string(), None),
+            ]
+        );
+
+        let code = "let liveSocket = new LiveSocket(\"/live\", Socket);";
+        assert!(get_live_socket_options_from_ast(code, LIVE_SOCKET).is_err());
+        assert!(get_live_socket_options_from_ast("let x = ;", LIVE_SOCKET).is_err());
+
+        let code = r#"
+        let adminSocket = new LiveSocket("/admin/live", Socket, { debug: true });
+        let liveSocket = new LiveSocket("/live", Socket, { debug: false });
+        "#;
+        assert_eq!(
+            get_live_socket_options_from_ast(code, LiveSocketTarget::Name("adminSocket")),
+            Ok(vec![("debug".to_string(), Some("true".to_string()))])
+        );
+        assert!(get_live_socket_options_from_ast(code, LiveSocketTarget::Only).is_err());
+    }
+
+    #[test]
+    fn test_diagnose_live_socket_from_ast() {
+        const LIVE_SOCKET: LiveSocketTarget = LiveSocketTarget::Name("liveSocket");
+
+        let diagnosis = diagnose_live_socket_from_ast(
+            r#"let liveSocket = new LiveSocket("/live", Socket, { hooks: { ...Hooks } });"#,
+            LIVE_SOCKET,
+        )
+        .unwrap();
+        assert_eq!(
+            diagnosis,
+            LiveSocketDiagnosis {
+                live_socket_declared: true,
+                new_live_socket: true,
+                has_options: true,
+                has_hooks: true,
+            }
+        );
+        assert_eq!(diagnosis.problem(), None);
+
+        let diagnosis = diagnose_live_socket_from_ast(
+            r#"let liveSocket = new LiveSocket("/live", Socket, { longPollFallbackMs: 2500 });"#,
+            LIVE_SOCKET,
+        )
+        .unwrap();
+        assert!(diagnosis.has_options && !diagnosis.has_hooks);
+        assert_eq!(diagnosis.problem(), None);
+
+        let diagnosis = diagnose_live_socket_from_ast(
+            r#"let liveSocket = new LiveSocket("/live", Socket);"#,
+            LIVE_SOCKET,
+        )
+        .unwrap();
+        assert!(diagnosis.new_live_socket && !diagnosis.has_options);
+        assert_eq!(
+            diagnosis.problem(),
+            Some("`new LiveSocket(...)` has no options object as its last argument")
+        );
+
+        let diagnosis = diagnose_live_socket_from_ast("let liveSocket = {};", LIVE_SOCKET).unwrap();
+        assert!(diagnosis.live_socket_declared && !diagnosis.new_live_socket);
+        assert_eq!(
+            diagnosis.problem(),
+            Some("The socket variable is not initialized with `new LiveSocket(...)`")
+        );
+
+        let diagnosis =
+            diagnose_live_socket_from_ast("let socket = new Socket(\"/socket\");", LIVE_SOCKET)
+                .unwrap();
+        assert_eq!(diagnosis, LiveSocketDiagnosis::default());
+        assert_eq!(
+            diagnosis.problem(),
+            Some("The socket variable is not declared")
+        );
+
+        assert!(diagnose_live_socket_from_ast(
+            r#"let liveSocket = new LiveSocket("/live", Socket, {"#,
+            LIVE_SOCKET
+        )
+        .is_err());
+
+        let code = r#"
+        let adminSocket = new LiveSocket("/admin/live", Socket);
+        let liveSocket = new LiveSocket("/live", Socket, { hooks: Hooks });
+        "#;
+        let diagnosis =
+            diagnose_live_socket_from_ast(code, LiveSocketTarget::Name("adminSocket")).unwrap();
+        assert!(diagnosis.new_live_socket && !diagnosis.has_options);
+        assert!(
+            diagnose_live_socket_from_ast(code, LiveSocketTarget::Index(1))
+                .unwrap()
+                .has_hooks
+        );
+        assert_eq!(
+            diagnose_live_socket_from_ast(code, LiveSocketTarget::Index(2)),
+            Ok(LiveSocketDiagnosis::default())
+        );
+        assert!(diagnose_live_socket_from_ast(code, LiveSocketTarget::Only).is_err());
+    }
+
+    #[test]
+    fn test_extend_hook_object_to_ast_for_socket() {
+        let code = r#"
+        let adminSocket = new LiveSocket("/admin/live", Socket, {
+            hooks: { AdminHook }
+        });
+        let liveSocket = new LiveSocket("/live", Socket, {
+            hooks: { CopyHook }
+        });
+        "#;
+
+        let result = extend_hook_object_to_ast_for_socket(
+            code,
+            vec!["NewHook"],
+            LiveSocketTarget::Name("adminSocket"),
+        )
+        .unwrap();
+        assert!(result.find("NewHook").unwrap() < result.find("\"/live\"").unwrap());
+        assert_eq!(result.matches("NewHook").count(), 1);
+        assert!(hook_exists_in_ast(&result, "CopyHook").unwrap());
+        assert!(!hook_exists_in_ast(&result, "NewHook").unwrap());
+
+        let result =
+            extend_hook_object_to_ast_for_socket(code, vec!["NewHook"], LiveSocketTarget::Index(1))
+                .unwrap();
+        assert!(hook_exists_in_ast(&result, "NewHook").unwrap());
+
+        assert!(extend_hook_object_to_ast_for_socket(
+            code,
+            vec!["NewHook"],
+            LiveSocketTarget::Only
+        )
+        .is_err());
+        assert!(extend_hook_object_to_ast_for_socket(
+            code,
+            vec!["NewHook"],
+            LiveSocketTarget::Name("otherSocket")
+        )
+        .is_err());
+        assert!(extend_hook_object_to_ast_for_socket(
+            code,
+            vec!["NewHook"],
+            LiveSocketTarget::Index(2)
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_has_csrf_token_setup_from_ast() {
+        let code = r#"
+        let csrfToken = document.querySelector("meta[name='csrf-token']").getAttribute("content");
+        let liveSocket = new LiveSocket("/live", Socket, {
+            params: { _csrf_token: csrfToken }
+        });
+        "#;
+        assert_eq!(has_csrf_token_setup_from_ast(code), Ok(true));
+
+        let code = include_str!("../../../../../test/assets/validApp.js");
+        assert_eq!(has_csrf_token_setup_from_ast(code), Ok(true));
+
+        let code = include_str!("../../../../../test/assets/invalidAppWithoutHooksKey.js");
+        assert_eq!(has_csrf_token_setup_from_ast(code), Err(false));
+
+        let code = r#"document.querySelector("meta[name='csrf-token']");"#;
+        assert_eq!(has_csrf_token_setup_from_ast(code), Err(false));
+        assert_eq!(has_csrf_token_setup_from_ast("let x = ;"), Err(false));
+    }
+
+    #[test]
+    fn test_extend_hook_object_to_ast_strict() {
+        let code = r#"
+        let liveSocket = new LiveSocket("/live", Socket, {
+          hooks: { ...Hooks, CopyMixInstallationHook },
+        });
+        "#;
+
+        let error = extend_hook_object_to_ast_strict(
+            code,
+            vec!["ObjectOne", "CopyMixInstallationHook", "...Hooks"],
+        )
+        .unwrap_err();
+        assert_eq!(
+            error,
+            "Hook names already in the hooks object: CopyMixInstallationHook, ...Hooks"
+        );
+
+        let result = extend_hook_object_to_ast_strict(code, vec!["ObjectOne"]).unwrap();
+        assert!(result.contains("ObjectOne"));
+
+        assert!(extend_hook_object_to_ast_strict("let a = 1;", vec!["ObjectOne"]).is_err());
+    }
+
+    #[test]
+    fn test_hook_editors_on_empty_input() {
+        use crate::helpers::EMPTY_SOURCE_ERROR;
+
+        let empty = Err(EMPTY_SOURCE_ERROR.to_string());
+
+        assert_eq!(extend_hook_object_to_ast("", vec!["CopyHook"]), empty);
+        assert_eq!(
+            extend_hook_object_to_ast_for_socket("", vec!["CopyHook"], LiveSocketTarget::Only),
+            empty
+        );
+        assert_eq!(
+            extend_hook_object_to_ast_strict("", vec!["CopyHook"]),
+            empty
+        );
+        assert_eq!(
+            remove_objects_of_hooks_from_ast("", vec!["CopyHook"]),
+            empty
+        );
+        assert_eq!(
+            remove_objects_of_hooks_from_ast_with_report("", vec!["CopyHook"]),
+            Err(EMPTY_SOURCE_ERROR.to_string())
+        );
+        assert_eq!(
+            add_hook_ast("", "import CopyHook from \"./copy\";", "CopyHook"),
+            empty
+        );
+        assert_eq!(
+            replace_hook_spread_ast("", "...Hooks", "...colocatedHooks"),
+            empty
+        );
+        assert_eq!(normalize_hooks_order_ast(""), empty);
+    }
 }
@@ -0,0 +1,9 @@
+import React from "react";
+import { Socket } from "phoenix";
+import "./styles.css";
+import { useState, useEffect } from "react";
+import "./styles.css";
+import topbar from "../vendor/topbar";
+
+const socket = new Socket("/socket");
+topbar.show();
@@ -0,0 +1,8 @@
+const Components = {};
+
+function register() {
+  const Components = window.Components;
+  return Components;
+}
+
+export default Components;
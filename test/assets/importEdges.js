@@ -0,0 +1,7 @@
+import "phoenix_html";
+import { Socket } from "phoenix";
+import topbar from "../vendor/topbar";
+
+const socket = new Socket("/socket");
+topbar.show();
+const lazy = import("./lazy.js");
@@ -0,0 +1,12 @@
+import "phoenix_html";
+import { Socket } from "phoenix";
+import { LiveSocket } from "phoenix_live_view";
+
+import topbar from "../vendor/topbar";
+import CopyMixInstallationHook from "../vendor/mixCopy";
+
+let liveSocket = new LiveSocket("/live", Socket, {
+  hooks: { CopyMixInstallationHook },
+});
+
+liveSocket.connect();
@@ -0,0 +1,25 @@
+import { Socket } from "phoenix";
+
+/**
+ * Connects the socket of the given user.
+ *
+ * @param {string} token - The user token.
+ */
+export function connect(token) {
+  return new Socket("/socket", { params: { token } });
+}
+
+// Not a JSDoc comment
+export function disconnect(socket) {
+  socket.disconnect();
+}
+
+/** A hook registry. */
+export class Hooks {}
+
+/**
+ * Not exported, so not listed.
+ */
+function helper() {}
+
+export default function () {}
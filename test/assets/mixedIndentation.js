@@ -0,0 +1,12 @@
+// Indented with tabs, except for one function indented with spaces
+function mount() {
+	const el = document.getElementById("app");
+	if (el) {
+		el.classList.add("ready");
+	}
+}
+
+function destroy() {
+    const el = document.getElementById("app");
+    el.remove();
+}
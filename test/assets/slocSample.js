@@ -0,0 +1,15 @@
+// Entry point of the admin bundle
+import { Socket } from "phoenix";
+
+/*
+ * The socket is shared by every page.
+ */
+let socket = new Socket("/socket"); // connects lazily
+
+/* inline */ socket.connect();
+
+
+function greet(name) {
+  // say hello
+  return `Hello ${name}`;
+}
@@ -0,0 +1,16 @@
+import { Socket } from "phoenix";
+
+// TODO: move the socket setup into its own module
+let liveSocket = new LiveSocket("/live", Socket, {
+  hooks: {},
+  /* FIXME the fallback is too short, see TODO below */
+  longPollFallbackMs: 2500,
+});
+
+topbar.config({
+  // TODO(topbar): read the color from the theme
+  barColors: { 0: "#29d" },
+});
+
+// Connect when the page is ready
+liveSocket.connect();
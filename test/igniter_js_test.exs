@@ -0,0 +1,14 @@
+defmodule IgniterJSTest do
+  use ExUnit.Case
+
+  test "Report the native capabilities :: capabilities" do
+    {:ok, :capabilities, capabilities} = assert IgniterJs.capabilities()
+
+    assert capabilities.version == Mix.Project.config()[:version]
+    assert capabilities.css_nesting
+    refute capabilities.typescript
+    assert capabilities.biome_version != ""
+    assert "format_js_nif" in capabilities.operations
+    assert "capabilities_nif" in capabilities.operations
+  end
+end
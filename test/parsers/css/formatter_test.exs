@@ -13,4 +13,14 @@ defmodule IgniterJSTest.Parsers.CSS.FormatterTest do
     {:ok, _, formatted} = assert Formatter.format("body { color: red; }")
     ^formatted = assert "body {\n  color: red;\n}\n"
   end
+
+  test "Format a CSS file read on the Rust side :: format_file" do
+    path = Path.join(System.tmp_dir!(), "igniter_js_format_file_test.css")
+    File.write!(path, "body{color:red}")
+
+    {:ok, :format_file, "body {\n  color: red;\n}\n"} = assert Formatter.format_file(path)
+    File.rm!(path)
+
+    {:error, :format_file, _} = assert Formatter.format_file(path)
+  end
 end
@@ -0,0 +1,274 @@
+defmodule IgniterJSTest.Parsers.CSS.ParserTest do
+  use ExUnit.Case
+  alias IgniterJs.Parsers.CSS.Parser
+
+  @valid_nested_styles "test/assets/nestedStyles.css"
+  @valid_styles "test/assets/validStyles.css"
+  @valid_theme_variables "test/assets/themeVariables.css"
+  @valid_scrollbar_styles "test/assets/scrollbarStyles.css"
+
+  test "Insert some css lines for import :: insert_imports" do
+    code = """
+    @import "base.css";
+
+    body {
+      margin: 0;
+    }
+    """
+
+    imports = """
+    @import "base.css";
+    @import "theme.css" layer(base);
+    """
+
+    {:ok, :insert_imports, output} = assert Parser.insert_imports(code, imports)
+    assert output ==
+             "@import \"base.css\";\n@import \"theme.css\" layer(base);\n\nbody {\n  margin: 0;\n}\n"
+
+    {:error, :insert_imports, _} = assert Parser.insert_imports("body { color: red", imports)
+  end
+
+  test "Insert a rule :: insert_rule" do
+    code = """
+    @import "base.css";
+
+    .btn {
+      color: red;
+    }
+    """
+
+    rule = ".new { color: red; }"
+
+    {:ok, :insert_rule, output} = assert Parser.insert_rule(code, rule)
+    assert output == code <> "\n" <> rule <> "\n"
+
+    {:ok, :insert_rule, output} = assert Parser.insert_rule(code, rule, :top)
+    assert output == "@import \"base.css\";\n\n#{rule}\n\n.btn {\n  color: red;\n}\n"
+
+    {:ok, :insert_rule, output} = assert Parser.insert_rule(code, rule, {:after, ".btn"})
+    assert output == code <> "\n" <> rule <> "\n"
+
+    {:error, :insert_rule, _} = assert Parser.insert_rule(code, rule, {:after, ".missing"})
+    {:error, :insert_rule, _} = assert Parser.insert_rule(code, ".new { color: red;")
+  end
+
+  test "Check an import :: exist_import" do
+    code = """
+    @import url("base.css");
+    @import "print.css" print;
+    """
+
+    {:ok, :exist_import, true} = assert Parser.exist_import(code, ~s(@import "base.css";))
+    {:ok, :exist_import, true} = assert Parser.exist_import(code, ~s(@import "print.css";))
+
+    {:error, :exist_import, false} =
+      assert Parser.exist_import(code, ~s(@import "print.css";), :content, true)
+
+    assert Parser.imported?(code, ~s(@import url(print.css\) print;), :content, true)
+    refute Parser.imported?(code, ~s(@import "missing.css";))
+  end
+
+  test "Check a class declaration :: class_has_declaration" do
+    code = ".btn { display: flex; color: red; }"
+
+    {:ok, :class_has_declaration, true} =
+      assert Parser.class_has_declaration(code, "btn", "display", "flex")
+
+    {:error, :class_has_declaration, false} =
+      assert Parser.class_has_declaration(code, "btn", "display", "grid")
+
+    {:error, :class_has_declaration, message} =
+      assert Parser.class_has_declaration(".btn {", "btn", "display", "flex")
+
+    assert message =~ "Failed to parse stylesheet"
+    assert Parser.class_has_declaration?(code, "btn", "color", " red ")
+  end
+
+  test "Check nested classes exist :: exist_class" do
+    {:ok, :exist_class, true} = assert Parser.exist_class(@valid_nested_styles, "title", :path)
+    {:error, :exist_class, false} = assert Parser.exist_class(@valid_nested_styles, "x", :path)
+    assert Parser.class_exists?(@valid_nested_styles, ".card-body", :path)
+  end
+
+  test "Count class selectors by prefix :: count_selectors_matching" do
+    code = ".tw-flex { display: flex; }\n.tw-p-4, .card { padding: 1rem; }\n"
+
+    {:ok, :count_selectors_matching, 2} = assert Parser.count_selectors_matching(code, "tw-")
+    {:ok, :count_selectors_matching, 0} = assert Parser.count_selectors_matching(code, "btn-")
+  end
+
+  test "Remove empty rules :: remove_empty_rules" do
+    code = "/* Buttons */\n.btn { color: red; }\n.empty {}\n"
+
+    {:ok, :remove_empty_rules, output} = assert Parser.remove_empty_rules(code)
+    assert output == "/* Buttons */\n.btn { color: red; }\n"
+
+    {:error, :remove_empty_rules, _} = assert Parser.remove_empty_rules(".btn { color: red")
+  end
+
+  test "Merge duplicate rules :: merge_duplicate_rules" do
+    code = ".a {color:red}\n.a {background:blue; color: green}\n"
+
+    {:ok, :merge_duplicate_rules, output} = assert Parser.merge_duplicate_rules(code)
+    assert output == ".a { background:blue; color: green; }\n"
+
+    {:error, :merge_duplicate_rules, _} = assert Parser.merge_duplicate_rules(".a { color: red")
+  end
+
+  test "List media queries :: media_queries" do
+    code = """
+    @media (min-width: 768px) { .a { color: red; } }
+    @media (min-width: 1024px) { .a { color: blue; } }
+    @media (min-width: 768px) { .b { color: red; } }
+    """
+
+    {:ok, :media_queries, ["(min-width: 768px)", "(min-width: 1024px)"]} =
+      assert Parser.media_queries(code)
+  end
+
+  test "Rename a class :: rename_class" do
+    code = ".hide-scrollbar::-webkit-scrollbar { display: none; }\n"
+
+    {:ok, :rename_class, ".no-scrollbar::-webkit-scrollbar { display: none; }\n"} =
+      assert Parser.rename_class(code, "hide-scrollbar", "no-scrollbar")
+
+    {:error, :rename_class, _} = assert Parser.rename_class(code, "missing", "no-scrollbar")
+  end
+
+  test "Rename a custom property :: rename_custom_property" do
+    code = File.read!(@valid_theme_variables)
+
+    {:ok, :rename_custom_property, output} =
+      assert Parser.rename_custom_property(code, "--color-primary", "brand")
+
+    assert output == String.replace(code, "--color-primary", "--brand")
+
+    code_with_fallback = ".a { margin: var(--spacing, 8px); }"
+
+    {:ok, :rename_custom_property, ".a { margin: var(--space, 8px); }"} =
+      assert Parser.rename_custom_property(code_with_fallback, "spacing", "space")
+
+    {:error, :rename_custom_property, _} =
+      assert Parser.rename_custom_property(code, "--missing", "--other")
+  end
+
+  test "Validate CSS :: validate" do
+    {:ok, :validate, true} = assert Parser.validate(".card { padding: 1rem; }")
+    {:error, :validate, message} = assert Parser.validate(".card { padding: 1rem;")
+    assert message != ""
+  end
+
+  test "Get statistics of a stylesheet :: statistics" do
+    {:ok, :statistics, statistics} = assert Parser.statistics(@valid_styles, :path)
+
+    assert statistics == %{
+             rules: 4,
+             at_rules: 2,
+             declarations: 7,
+             max_declarations: 3,
+             rule_declarations: [{"body", 2}, {".btn, .button", 3}, {".card", 1}, {".card", 1}]
+           }
+
+    {:error, :statistics, _} = assert Parser.statistics(".btn { color: red")
+  end
+
+  test "Check a custom property :: exist_custom_property" do
+    path = @valid_theme_variables
+
+    {:ok, :exist_custom_property, true} =
+      assert Parser.exist_custom_property(path, "--color-primary", :path, true)
+
+    {:error, :exist_custom_property, false} =
+      assert Parser.exist_custom_property(path, "--color-secondary", :path, true)
+
+    assert Parser.custom_property_exists?(path, "color-secondary", :path)
+    refute Parser.custom_property_exists?(path, "--color-accent", :path)
+  end
+
+  test "Set a custom property :: set_custom_property" do
+    code = File.read!(@valid_theme_variables)
+
+    {:ok, :set_custom_property, output} =
+      assert Parser.set_custom_property(code, "--color-primary", "#000")
+
+    assert output == String.replace(code, "--color-primary: #fff;", "--color-primary: #000;")
+
+    {:ok, :set_custom_property, output} =
+      assert Parser.set_custom_property(code, "--radius", "8px")
+
+    assert output =~ "  --spacing: 4px;\n  --radius: 8px;\n}"
+    assert output =~ "/* Design tokens */"
+  end
+
+  test "Set a declaration :: set_declaration" do
+    code = File.read!(@valid_styles)
+
+    {:ok, :set_declaration, output} =
+      assert Parser.set_declaration(code, ".btn, .button", "color", "blue")
+
+    assert output == String.replace(code, "color: red;", "color: blue;")
+
+    {:ok, :set_declaration, output} =
+      assert Parser.set_declaration(code, "body", "line-height", "1.5")
+
+    assert output =~ "  font-family: sans-serif;\n  line-height: 1.5;\n}"
+
+    {:ok, :set_declaration, ".a { color: blue; }"} =
+      assert Parser.set_declaration(".a { color: red !important; }", ".a", "color", "blue")
+  end
+
+  test "Compute the specificity of rules :: specificity" do
+    {:ok, :specificity, rules} = assert Parser.specificity("#main .btn a { color: red; }")
+    assert rules == [%{selector: "#main .btn a", specificity: {1, 1, 1}}]
+
+    {:ok, :specificity, rules} = assert Parser.specificity(@valid_styles, :path)
+
+    assert Enum.map(rules, &{&1.selector, &1.specificity}) == [
+             {"body", {0, 0, 1}},
+             {".btn, .button", {0, 1, 0}},
+             {".card", {0, 1, 0}}
+           ]
+
+    {:error, :specificity, _} = assert Parser.specificity(".btn { color: red")
+  end
+
+  test "List class and id selectors :: selectors" do
+    {:ok, :selectors, %{classes: classes, ids: ids}} =
+      assert Parser.selectors(@valid_scrollbar_styles, :path)
+
+    assert "hide-scrollbar" in classes
+    assert ids == ["sidebar", "main"]
+
+    {:ok, :selectors, %{classes: ["btn", "button", "card"], ids: []}} =
+      assert Parser.selectors(@valid_styles, :path)
+
+    {:error, :selectors, _} = assert Parser.selectors(".btn { color: red")
+  end
+
+  test "Edit an empty content :: editors" do
+    empty_error = "The source code is empty, there is nothing to edit"
+
+    {:ok, :remove_empty_rules, ""} = assert Parser.remove_empty_rules("")
+    {:ok, :merge_duplicate_rules, ""} = assert Parser.merge_duplicate_rules("")
+
+    {:ok, :insert_imports, "@import \"base.css\";\n"} =
+      assert Parser.insert_imports("", ~s(@import "base.css";))
+
+    {:ok, :insert_rule, ".a { color: red; }\n"} =
+      assert Parser.insert_rule("", ".a { color: red; }")
+
+    {:ok, :set_declaration, ".btn {\n  color: blue;\n}\n"} =
+      assert Parser.set_declaration("", ".btn", "color", "blue")
+
+    {:ok, :set_custom_property, ":root {\n  --radius: 8px;\n}\n"} =
+      assert Parser.set_custom_property("", "--radius", "8px")
+
+    {:error, :insert_rule, ^empty_error} =
+      assert Parser.insert_rule("", ".a { color: red; }", {:after, ".b"})
+
+    {:error, :rename_class, ^empty_error} = assert Parser.rename_class("", "btn", "button")
+
+    {:error, :rename_custom_property, ^empty_error} =
+      assert Parser.rename_custom_property("", "--a", "--b")
+  end
+end
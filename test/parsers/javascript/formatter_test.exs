@@ -16,6 +16,17 @@ defmodule IgniterJSTest.Parsers.Javascript.FormatterTest do
     {:error, _, false} = assert Formatter.is_formatted(js_code_unformatted)
   end
 
+  test "Detect mixed tabs and spaces :: mixed_indentation" do
+    {:ok, :mixed_indentation, true} =
+      assert Formatter.mixed_indentation("test/assets/mixedIndentation.js", :path)
+
+    {:error, :mixed_indentation, false} =
+      assert Formatter.mixed_indentation("test/assets/validApp.js", :path)
+
+    assert Formatter.mixed_indentation?("if (a) {\n \tb();\n}\n")
+    refute Formatter.mixed_indentation?("if (a) {\n\tb();\n}\n")
+  end
+
   test "Format The JS considered:: format" do
     js_code_formatted = """
     function test() {
@@ -30,4 +41,40 @@ defmodule IgniterJSTest.Parsers.Javascript.FormatterTest do
     ^formatted =
       assert "function test() {\n  // expose liveSocket on window for web console debug logs and latency simulation:\n  console.log(\"hello world\");\n  // expose liveSocket on window for web console debug logs and latency simulation:\n}\n"
   end
+
+  test "Format and sort the imports :: format" do
+    js_code = "import b from \"./b\";\nimport \"phoenix_html\";\nimport z from 'z';\nimport a from \"a\";\n"
+
+    {:ok, :format, formatted} = assert Formatter.format(js_code, :content, organize_imports: true)
+
+    ^formatted =
+      assert "import b from \"./b\";\nimport \"phoenix_html\";\nimport a from \"a\";\nimport z from \"z\";\n"
+
+    {:ok, :format, ^formatted} = assert Formatter.format(js_code, organize_imports: true)
+
+    {:ok, :format, formatted} = assert Formatter.format(js_code)
+
+    ^formatted =
+      assert "import b from \"./b\";\nimport \"phoenix_html\";\nimport z from \"z\";\nimport a from \"a\";\n"
+  end
+
+  test "Format a batch of JS contents :: format_batch" do
+    {:ok, :format_batch, results} =
+      assert Formatter.format_batch(["let a=1", "let b = ;", "function c(){return 1}"])
+
+    [{:ok, "let a = 1;\n"}, {:error, _}, {:ok, "function c() {\n  return 1;\n}\n"}] =
+      assert results
+  end
+
+  test "Format only the statements in a range :: format_range" do
+    js_code = "function a(){return   1}\n\nfunction b(){return x}\n"
+    start = :binary.match(js_code, "return x") |> elem(0)
+
+    {:ok, :format_range, formatted} = assert Formatter.format_range(js_code, start, start + 1)
+
+    ^formatted =
+      assert "function a(){return   1}\n\nfunction b() {\n  return x;\n}\n"
+
+    {:error, :format_range, _} = assert Formatter.format_range(js_code, 10, 5)
+  end
 end
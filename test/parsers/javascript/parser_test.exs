@@ -11,6 +11,12 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
   @invalid_error_import "test/assets/errorImport.js"
   @valid_ast_statistics "test/assets/validASTStatistics.js"
   @valid_extend_var_object "test/assets/extendVarObject.js"
+  @valid_extend_var_object_shadowed "test/assets/extendVarObjectShadowed.js"
+  @valid_todo_comments "test/assets/todoComments.js"
+  @valid_sloc_sample "test/assets/slocSample.js"
+  @valid_import_edges "test/assets/importEdges.js"
+  @valid_duplicate_imports "test/assets/duplicateImports.js"
+  @valid_jsdoc_exports "test/assets/jsdocExports.js"
 
   test "User requested module imported? :: module_imported" do
     {:ok, :module_imported, true} =
@@ -84,6 +90,20 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
     assert !Parser.module_imported?("", "")
   end
 
+  test "Static or dynamic module import :: module_import_kind" do
+    code = """
+    const loadChart = () => import("./chart.js");
+    """
+
+    {:ok, :module_import_kind, :dynamic} = assert Parser.module_import_kind(code, "./chart.js")
+
+    {:error, :module_import_kind, false} =
+      assert Parser.module_import_kind(code, "./chart.js", :content, false)
+
+    {:ok, :module_import_kind, :static} =
+      assert Parser.module_import_kind(@valid_app_js, "phoenix", :path)
+  end
+
   test "Insert some js lines for import modules :: insert_imports" do
     imports = """
     import { foo } from "module-name";
@@ -104,6 +124,170 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
     ^js_output = assert considerd_output
   end
 
+  test "Semicolon style option :: insert_imports, remove_imports, extend_hook_object" do
+    code = "import { foo } from \"module-name\"\nlet Hooks = {}\n"
+
+    {:ok, :insert_imports, output} =
+      assert Parser.insert_imports(code, "import bar from \"another-module\"", semicolons: :keep)
+
+    assert output =~ "import bar from \"another-module\""
+    refute output =~ ";"
+
+    {:ok, :insert_imports, output} =
+      assert Parser.insert_imports(code, "import bar from \"another-module\"")
+
+    assert output =~ "let Hooks = {};"
+
+    {:ok, :remove_imports, output} =
+      assert Parser.remove_imports(code, "module-name", :content, semicolons: :keep)
+
+    refute output =~ "module-name"
+    refute output =~ ";"
+
+    {:ok, :insert_imports, output} =
+      assert Parser.insert_imports(
+               @invalid_app_without_live_socket,
+               "import bar from \"another-module\";",
+               :path,
+               semicolons: :as_needed
+             )
+
+    assert output =~ "import bar from \"another-module\""
+    refute output =~ ";"
+
+    hooks_code = "let liveSocket = new LiveSocket(\"/live\", Socket, {\n  hooks: {}\n})\n"
+
+    {:ok, :extend_hook_object, output} =
+      assert Parser.extend_hook_object(hooks_code, "something", semicolons: :keep)
+
+    assert output =~ "something"
+    refute output =~ ";"
+  end
+
+  test "Line ending option :: insert_imports" do
+    code = "import { foo } from \"module-name\";\nlet Hooks = {};\n"
+
+    {:ok, :insert_imports, output} =
+      assert Parser.insert_imports(code, "import bar from \"another-module\";", new_line: :crlf)
+
+    assert output =~ "import bar from \"another-module\";\r\n"
+    refute output =~ ~r/[^\r]\n/
+  end
+
+  test "Blank lines option :: remove_imports" do
+    code = "import a from \"a\";\n\nimport c from \"./c\";\nimport d from \"./d\";\nlet x = 1;\n"
+
+    {:ok, :remove_imports, output} =
+      assert Parser.remove_imports(code, "./d", preserve_blank_lines: true)
+
+    assert output =~ "import a from \"a\";\n\nimport c from \"./c\";"
+
+    {:ok, :remove_imports, output} = assert Parser.remove_imports(code, "./d")
+    assert output =~ "import a from \"a\";\nimport c from \"./c\";"
+  end
+
+  test "ASCII only option :: insert_imports" do
+    code = "let greeting = \"café\";\n"
+
+    {:ok, :insert_imports, output} =
+      assert Parser.insert_imports(code, "import a from \"a\";", ascii_only: true)
+
+    refute output =~ "é"
+    {:ok, :insert_imports, output} = assert Parser.insert_imports(code, "import a from \"a\";")
+    assert output =~ "café"
+  end
+
+  test "Preserve formatting option :: extend_hook_object" do
+    code = "let liveSocket = new LiveSocket(\"/live\", Socket, {\n  hooks: {}\n});\n"
+
+    {:ok, :extend_hook_object, output} =
+      assert Parser.extend_hook_object(code, "CopyHook", preserve_formatting: true)
+
+    assert output =~ "\n  hooks: {\n    CopyHook\n  }\n"
+  end
+
+  test "List the import graph edges :: import_edges" do
+    {:ok, :import_edges, edges} =
+      assert Parser.import_edges(@valid_import_edges, "app.js", :path)
+
+    assert edges == [
+             %{from: "app.js", to: "phoenix_html"},
+             %{from: "app.js", to: "phoenix"},
+             %{from: "app.js", to: "../vendor/topbar"}
+           ]
+  end
+
+  test "Insert an import only when it is missing :: insert_imports_if_missing" do
+    content = File.read!(@valid_app_js)
+
+    {:ok, :insert_imports_if_missing, {^content, false}} =
+      assert Parser.insert_imports_if_missing(content, "import topbar from \"../vendor/topbar\";")
+
+    {:ok, :insert_imports_if_missing, {js_output, true}} =
+      assert Parser.insert_imports_if_missing(@valid_app_js, "import foo from \"foo\";", :path)
+
+    assert Parser.module_imported?(js_output, "import foo from \"foo\";")
+  end
+
+  test "Insert imports and get their ranges :: insert_imports_with_spans" do
+    imports = """
+    import { foo } from "module-name";
+    import bar from "another-module";
+    """
+
+    {:ok, :insert_imports_with_spans, {js_output, [{start1, stop1}, {start2, stop2}]}} =
+      assert Parser.insert_imports_with_spans(@invalid_app_without_live_socket, imports, :path)
+
+    "import { foo } from \"module-name\";" = assert binary_part(js_output, start1, stop1 - start1)
+    "import bar from \"another-module\";" = assert binary_part(js_output, start2, stop2 - start2)
+  end
+
+  test "Insert imports before a marker import :: insert_imports_before" do
+    code = "import { Socket } from \"phoenix\";\nimport topbar from \"../vendor/topbar\";\n"
+
+    {:ok, :insert_imports_before, output} =
+      assert Parser.insert_imports_before(code, "import a from \"a\";", "../vendor/topbar")
+
+    assert output ==
+             "import { Socket } from \"phoenix\";\nimport a from \"a\";\nimport topbar from \"../vendor/topbar\";\n"
+  end
+
+  test "Normalize imports :: normalize_imports" do
+    code = "import { b, a } from \"react\";\nimport { a, c } from \"react\";\n"
+
+    {:ok, :normalize_imports, "import { a, b, c } from \"react\";\n"} =
+      assert Parser.normalize_imports(code)
+  end
+
+  test "Hoist imports :: hoist_imports" do
+    code = "let Hooks = {};\nimport { Socket } from \"phoenix\";\n"
+
+    {:ok, :hoist_imports, "import { Socket } from \"phoenix\";\nlet Hooks = {};\n"} =
+      assert Parser.hoist_imports(code)
+
+    {:error, :hoist_imports, _} = assert Parser.hoist_imports("let x = ;")
+  end
+
+  test "Check if an import is used :: import_used" do
+    {:ok, :import_used, true} =
+      assert Parser.import_used(@valid_app_js, "../vendor/topbar", :path)
+
+    code = "import topbar from \"../vendor/topbar\";\nconst config = { topbar: true };\n"
+    {:error, :import_used, false} = assert Parser.import_used(code, "../vendor/topbar")
+
+    refute Parser.import_used?(code, "./missing")
+  end
+
+  test "List duplicate import sources :: duplicate_import_sources" do
+    {:ok, :duplicate_import_sources, ["react", "./styles.css"]} =
+      assert Parser.duplicate_import_sources(@valid_duplicate_imports, :path)
+
+    {:ok, :duplicate_import_sources, []} =
+      assert Parser.duplicate_import_sources(@valid_import_edges, :path)
+
+    {:error, :duplicate_import_sources, _} = assert Parser.duplicate_import_sources("let x = ;")
+  end
+
   test "Remove imported modules :: remove_imports" do
     none_imported_module_output =
       "import { foo } from \"module-name\";\nimport bar from \"another-module\";\nlet Hooks = {};\n"
@@ -187,6 +371,29 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
     assert !Parser.exist_live_socket?(File.read!(@invalid_app_without_live_socket))
   end
 
+  test "LiveSocket connect call exist :: exist_live_socket_connect" do
+    {:ok, :exist_live_socket_connect, true} =
+      assert Parser.exist_live_socket_connect(@valid_app_js, :path)
+
+    assert Parser.live_socket_connected?(@valid_app_js, :path)
+
+    {:error, :exist_live_socket_connect, false} =
+      assert Parser.exist_live_socket_connect("let liveSocket = new LiveSocket();")
+
+    {:ok, :exist_socket_connect, true} =
+      assert Parser.exist_socket_connect("socket.connect();", "socket")
+  end
+
+  test "Csrf token setup exist :: exist_csrf_token_setup" do
+    {:ok, :exist_csrf_token_setup, true} =
+      assert Parser.exist_csrf_token_setup(@valid_app_js, :path)
+
+    assert Parser.csrf_token_setup?(@valid_app_js, :path)
+
+    {:error, :exist_csrf_token_setup, false} =
+      assert Parser.exist_csrf_token_setup(@invalid_app_without_hooks_key, :path)
+  end
+
   test "Extend hook objects :: extend_hook_object" do
     {:error, :extend_hook_object, _msg} =
       Parser.extend_hook_object(@invalid_app_without_live_socket, "something", :path)
@@ -340,8 +547,7 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
   end
 
   test "Get statistics from the given file or content :: statistics" do
-    {:ok, :statistics, %{functions: 0, imports: 0, classes: 0, debuggers: 0, trys: 0, throws: 0}} =
-      assert Parser.statistics(@invalid_error_import, :path)
+    {:error, :statistics, _} = assert Parser.statistics(@invalid_error_import, :path)
 
     {:ok, :statistics, statistics} = assert Parser.statistics(@valid_ast_statistics, :path)
     1 = assert statistics.functions
@@ -350,6 +556,58 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
     2 = assert statistics.imports
     0 = assert statistics.trys
     0 = assert statistics.throws
+    0 = assert statistics.exports
+    0 = assert statistics.consts
+    0 = assert statistics.lets
+    0 = assert statistics.vars
+    0 = assert statistics.line_comments
+    0 = assert statistics.block_comments
+
+    {:ok, :statistics, %{line_comments: 8, block_comments: 0}} =
+      assert Parser.statistics(@valid_app_js, :path)
+  end
+
+  test "Get statistics with import locations :: statistics_detailed" do
+    {:ok, :statistics_detailed, %{statistics: statistics, imports: imports}} =
+      assert Parser.statistics_detailed(@valid_ast_statistics, :path)
+
+    2 = assert statistics.imports
+
+    [%{source: "bar", line: 1, column: 1}, %{source: "jar", line: 2, column: 1}] =
+      assert imports
+
+    {:error, :statistics_detailed, _} = assert Parser.statistics_detailed("let x = ;")
+  end
+
+  test "Count node kinds :: count_node_kinds" do
+    {:ok, :count_node_kinds, %{"ClassDecl" => 1, "DebuggerStmt" => 2}} =
+      assert Parser.count_node_kinds(@valid_ast_statistics, ["ClassDecl", "DebuggerStmt"], :path)
+
+    {:error, :count_node_kinds, _} = assert Parser.count_node_kinds("let a = 1;", ["Unknown"])
+  end
+
+  test "Preview a var object extension :: extend_var_object_preview" do
+    code = File.read!(@valid_extend_var_object)
+
+    {:ok, :extend_var_object_preview, {output, _message}} =
+      assert Parser.extend_var_object_preview(code, "Components", "TestHook")
+
+    assert output =~ "TestHook"
+
+    {:error, :extend_var_object_preview, {^code, _message}} =
+      assert Parser.extend_var_object_preview(code, "Missing", "TestHook")
+  end
+
+  test "Extend a nested object of a var object :: extend_var_object_at_path" do
+    code = "const config = { params: { token: csrfToken } };"
+
+    {:ok, :extend_var_object_at_path, output} =
+      assert Parser.extend_var_object_at_path(code, "config", "params.user", "userId")
+
+    assert output =~ "user: {\n            userId\n        }"
+
+    {:error, :extend_var_object_at_path, _} =
+      assert Parser.extend_var_object_at_path(code, "config", ["params", "token"], "userId")
   end
 
   test "Extend some objects inside a var object :: extend_var_object_by_object_names" do
@@ -403,6 +661,113 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
     1 = assert string_counter(considerd_output, "(^|[^.])NoneComponent")
   end
 
+  test "Insert statements after imports :: insert_statement_after_imports" do
+    code = """
+    import { Socket } from "phoenix";
+    let liveSocket = new LiveSocket("/live", Socket, {});
+    """
+
+    {:ok, :insert_statement_after_imports, output} =
+      assert Parser.insert_statement_after_imports(code, "window.liveSocket = liveSocket;")
+
+    assert output ==
+             "import { Socket } from \"phoenix\";\nwindow.liveSocket = liveSocket;\nlet liveSocket = new LiveSocket(\"/live\", Socket, {});\n"
+
+    {:error, :insert_statement_after_imports, _} =
+      assert Parser.insert_statement_after_imports(code, "window.liveSocket = ;")
+  end
+
+  test "Add a window event listener once :: ensure_window_event_listener" do
+    {:ok, :ensure_window_event_listener, output} =
+      assert Parser.ensure_window_event_listener(
+               @valid_app_js,
+               "phx:page-loading-stop",
+               "() => console.log(\"stopped\")",
+               :path
+             )
+
+    refute output =~ "stopped"
+
+    {:ok, :ensure_window_event_listener, output} =
+      assert Parser.ensure_window_event_listener(@valid_app_js, "phx:copy", "() => {}", :path)
+
+    assert output =~ "window.addEventListener(\"phx:copy\""
+  end
+
+  test "Guard console logs behind a flag :: guard_console_logs" do
+    js_code = """
+    console.log("booting");
+    console.log("ready");
+    console.error("failed");
+    """
+
+    {:ok, :guard_console_logs, output} =
+      assert Parser.guard_console_logs(js_code, "window.DEBUG")
+
+    assert length(String.split(output, "if (window.DEBUG)")) == 3
+    assert output =~ "\nconsole.error(\"failed\");"
+  end
+
+  test "Add a global assignment once :: ensure_global_assignment" do
+    js_code = "let socket = new Socket(\"/socket\");\n"
+
+    {:ok, :ensure_global_assignment, output} =
+      assert Parser.ensure_global_assignment(js_code, "window.userSocket", "socket")
+
+    {:ok, :ensure_global_assignment, output} =
+      assert Parser.ensure_global_assignment(output, "window.userSocket", "socket")
+
+    assert length(String.split(output, "window.userSocket = socket;")) == 2
+  end
+
+  test "Extend const, let and var objects :: extend_var_object_by_object_names" do
+    for kind <- ["const", "let", "var"] do
+      {:ok, :extend_var_object_by_object_names, output} =
+        assert Parser.extend_var_object_by_object_names("#{kind} Hooks = {};", "Hooks", "Copy")
+
+      assert output == "#{kind} Hooks = {\n    Copy\n};\n"
+    end
+
+    {:ok, :extend_var_object_by_object_names, output} =
+      assert Parser.extend_var_object_by_object_names(
+               @valid_extend_var_object_shadowed,
+               "Components",
+               "ScrollArea",
+               :path
+             )
+
+    assert output =~ "const Components = {\n    ScrollArea\n};"
+    {:ok, :exist_var, true} =
+      assert Parser.exist_var(@valid_extend_var_object_shadowed, "Components", :path)
+  end
+
+  test "Extend a var object with key/value properties :: extend_var_object_properties" do
+    code = "let opts = {};\n"
+
+    {:ok, :extend_var_object_properties, output} =
+      assert Parser.extend_var_object_properties(code, "opts", ["Hooks", {"timeout", "3000"}])
+
+    assert output == "let opts = {\n    Hooks,\n    timeout: 3000\n};\n"
+
+    {:error, :extend_var_object_properties, _} =
+      assert Parser.extend_var_object_properties(code, "opts", [{"timeout", "1 +"}])
+  end
+
+  test "Extend several var objects at once :: extend_var_objects" do
+    code = "const Components = {};\nlet Hooks = {};\n"
+
+    {:ok, :extend_var_objects, %{code: output, extended: extended, failed: ["Missing"]}} =
+      assert Parser.extend_var_objects(code, [
+               {"Components", ["Dialog"]},
+               {"Hooks", "CopyHook"},
+               {"Missing", ["Other"]}
+             ])
+
+    ["Components", "Hooks"] = assert extended
+    assert output =~ "Dialog"
+    assert output =~ "CopyHook"
+  end
+
   test "Check existing vars :: exist_var" do
     code = """
     import { foo } from "module-name";
@@ -425,6 +790,468 @@ defmodule IgniterJSTest.Parsers.Javascript.ParserTest do
     {:ok, :exist_var, true} = assert Parser.exist_var(code, "igniterJS")
   end
 
+  test "Minify the given file or content :: minify" do
+    {:ok, :minify, output} = assert Parser.minify(@valid_extend_var_object, :path)
+    "const Components={};export default Components;" = assert String.trim(output)
+
+    {:error, :minify, _} = assert Parser.minify("let x = ;")
+  end
+
+  test "Strip comments from the given file or content :: strip_comments" do
+    {:ok, :strip_comments, output} = assert Parser.strip_comments(@valid_app_js, :path)
+    refute output =~ "//"
+    refute output =~ "Establish Phoenix Socket"
+    assert output =~ "window.liveSocket = liveSocket;"
+
+    {:error, :strip_comments, _} = assert Parser.strip_comments("let x = ;")
+  end
+
+  test "Fingerprint the given file or content :: fingerprint" do
+    {:ok, :fingerprint, fingerprint} = assert Parser.fingerprint(@valid_app_js, :path)
+
+    {:ok, :fingerprint, ^fingerprint} =
+      assert Parser.fingerprint(File.read!(@valid_app_js) <> "\n// Trailing note\n")
+
+    {:ok, :fingerprint, other} = assert Parser.fingerprint("let x = 1;")
+    assert other != fingerprint
+
+    {:error, :fingerprint, _} = assert Parser.fingerprint("let x = ;")
+  end
+
+  test "Check existing exports :: exist_export" do
+    {:ok, :exist_export, true} =
+      assert Parser.exist_export(@valid_extend_var_object, "default", :path)
+
+    {:error, :exist_export, false} =
+      assert Parser.exist_export(@valid_extend_var_object, "Components", :path)
+
+    assert Parser.export_exists?("export function setup() {}", "setup")
+    assert !Parser.export_exists?("function setup() {}", "setup")
+  end
+
+  test "Convert a default export to a named one :: convert_default_export_to_named" do
+    {:ok, :convert_default_export_to_named, output} =
+      assert Parser.convert_default_export_to_named(@valid_extend_var_object, :path)
+
+    assert output =~ "export { Components };"
+    refute Parser.export_exists?(output, "default")
+
+    {:ok, :convert_default_export_to_named, "export function setup() {}\n"} =
+      assert Parser.convert_default_export_to_named("export default function setup() {}")
+
+    {:ok, :convert_default_export_to_named, "export function setup() {}\n"} =
+      assert Parser.convert_default_export_to_named("export default function () {}",
+               name: "setup"
+             )
+
+    {:error, :convert_default_export_to_named, _} =
+      assert Parser.convert_default_export_to_named("export const a = 1;")
+  end
+
+  test "Extract the JSDoc of exports :: jsdoc" do
+    {:ok, :jsdoc, docs} = assert Parser.jsdoc(@valid_jsdoc_exports, :path)
+
+    assert Enum.map(docs, & &1.name) == ["connect", "disconnect", "Hooks", "default"]
+    assert hd(docs).doc =~ "Connects the socket of the given user.\n\n@param"
+    assert Enum.find(docs, &(&1.name == "disconnect")).doc == nil
+
+    {:error, :jsdoc, _} = assert Parser.jsdoc("let x = ;")
+  end
+
+  test "Remove a named export :: remove_export" do
+    {:ok, :remove_export, output} = assert Parser.remove_export("export const a = 1;", "a")
+    assert output =~ "const a = 1;"
+    refute Parser.export_exists?(output, "a")
+
+    {:ok, :remove_export, output} =
+      assert Parser.remove_export("const a = 1, b = 2;\nexport { a, b };", "b")
+
+    assert Parser.export_exists?(output, "a")
+    refute Parser.export_exists?(output, "b")
+
+    {:error, :remove_export, _} = assert Parser.remove_export("export const a = 1;", "b")
+  end
+
+  test "Wrap a function body in try/catch :: wrap_in_try_catch" do
+    code = "function connect(socket) {\n  socket.connect();\n}\n"
+    {:ok, :wrap_in_try_catch, output} = assert Parser.wrap_in_try_catch(code, "connect")
+    {:ok, :statistics, %{trys: 1}} = assert Parser.statistics(output)
+
+    {:error, :wrap_in_try_catch, _} = assert Parser.wrap_in_try_catch(code, "missing")
+  end
+
+  test "Remove debugger statements :: remove_debuggers" do
+    {:ok, :remove_debuggers, {output, 2}} =
+      assert Parser.remove_debuggers(@valid_ast_statistics, :path)
+
+    {:ok, :statistics, %{debuggers: 0}} = assert Parser.statistics(output)
+  end
+
+  test "Rename a top-level function :: rename_function" do
+    code = "function add(a, b) {\n  return a + b;\n}\nadd(1, 2);\n"
+    {:ok, :rename_function, output} = assert Parser.rename_function(code, "add", "sum")
+    assert output =~ "function sum(a, b)"
+    assert output =~ "sum(1, 2);"
+
+    {:error, :rename_function, _} = assert Parser.rename_function(code, "missing", "sum")
+  end
+
+  test "Rename a top-level binding :: rename_identifier" do
+    {:ok, :rename_identifier, output} =
+      assert Parser.rename_identifier(@valid_app_js, "csrfToken", "token", :path)
+
+    assert output =~ "let token = document"
+    assert output =~ "_csrf_token: token"
+    refute output =~ "csrfToken"
+
+    {:error, :rename_identifier, _} = assert Parser.rename_identifier("let a = 1;", "b", "c")
+  end
+
+  test "Report if an edit would change the file :: would_insert_imports" do
+    {:ok, :would_insert_imports, true} =
+      assert Parser.would_insert_imports(@valid_app_js, "import a from \"a\";", :path)
+
+    {:ok, :would_insert_imports, false} =
+      assert Parser.would_insert_imports(
+               @valid_app_js,
+               "import { LiveSocket } from \"phoenix_live_view\";",
+               :path
+             )
+
+    {:ok, :would_remove_imports, false} =
+      assert Parser.would_remove_imports(@valid_app_js, "import a from \"a\";", :path)
+
+    {:ok, :would_extend_hook_object, true} =
+      assert Parser.would_extend_hook_object(@valid_app_js, "SomeObject", :path)
+
+    {:ok, :would_remove_objects_from_hooks, false} =
+      assert Parser.would_remove_objects_from_hooks(@valid_app_js, "SomeObject", :path)
+  end
+
+  test "Detect the module system :: module_kind" do
+    {:ok, :module_kind, :esm} = assert Parser.module_kind(@valid_app_js, :path)
+    {:ok, :module_kind, :common_js} = assert Parser.module_kind("module.exports = {};")
+    {:ok, :module_kind, :script} = assert Parser.module_kind("window.answer = 42;")
+    {:error, :module_kind, _} = assert Parser.module_kind("let x = ;")
+  end
+
+  test "Report recoverable parse issues :: parse_with_diagnostics" do
+    {:ok, :parse_with_diagnostics, {code, [warning]}} =
+      assert Parser.parse_with_diagnostics("let scope = {};\nwith (scope) {}\n")
+
+    assert code =~ "scope"
+    assert warning =~ "line 2"
+
+    {:ok, :parse_with_diagnostics, {_code, []}} =
+      assert Parser.parse_with_diagnostics(@valid_app_js, :path)
+
+    {:error, :parse_with_diagnostics, _} = assert Parser.parse_with_diagnostics("let x = ;")
+  end
+
+  test "Validate JavaScript :: validate" do
+    {:ok, :validate, true} = assert Parser.validate(@valid_app_js, :path)
+    {:error, :validate, message} = assert Parser.validate("let x = ;")
+    assert message =~ "line 1"
+    {:error, :validate, _} = assert Parser.validate("let scope = {};\nwith (scope) {}\n")
+  end
+
+  test "Import and register a hook :: add_hook" do
+    import_line = "import CopyHook from \"./copy_hook\";"
+
+    {:ok, :add_hook, output} =
+      assert Parser.add_hook(@valid_app_js, import_line, "CopyHook", :path)
+
+    assert output =~ import_line
+    assert output =~ "CopyHook\n    }"
+
+    {:error, :add_hook, "Failed to register the hook" <> _} =
+      assert Parser.add_hook(@invalid_app_without_live_socket, import_line, "CopyHook", :path)
+  end
+
+  test "Report added and skipped hooks :: extend_hook_object_with_report" do
+    {:ok, :extend_hook_object_with_report, %{code: code, added: added, skipped: skipped}} =
+      assert Parser.extend_hook_object_with_report(
+               @valid_app_js,
+               ["CopyMixInstallationHook", "NewHook"],
+               :path
+             )
+
+    assert added == ["NewHook"]
+    assert skipped == ["CopyMixInstallationHook"]
+    assert code =~ "NewHook"
+
+    {:error, :extend_hook_object_with_report, _} =
+      assert Parser.extend_hook_object_with_report(
+               @invalid_app_without_live_socket,
+               "NewHook",
+               :path
+             )
+  end
+
+  test "Refuse conflicting hook names :: extend_hook_object_strict" do
+    {:error, :extend_hook_object_strict, message} =
+      assert Parser.extend_hook_object_strict(
+               @valid_app_js,
+               ["CopyMixInstallationHook", "NewHook"],
+               :path
+             )
+
+    assert message =~ "CopyMixInstallationHook"
+    refute message =~ "NewHook"
+
+    {:ok, :extend_hook_object_strict, code} =
+      assert Parser.extend_hook_object_strict(@valid_app_js, "NewHook", :path)
+
+    assert Parser.hook_exists?(code, "NewHook")
+  end
+
+  test "Extend the hooks of one of several sockets :: extend_socket_hook_object" do
+    code = """
+    let adminSocket = new LiveSocket("/admin/live", Socket, { hooks: { AdminHook } });
+    let liveSocket = new LiveSocket("/live", Socket, { hooks: { CopyHook } });
+    """
+
+    {:ok, :extend_socket_hook_object, updated} =
+      assert Parser.extend_socket_hook_object(code, "NewHook", "adminSocket")
+
+    refute Parser.hook_exists?(updated, "NewHook")
+    assert Parser.hook_exists?(updated, "CopyHook")
+
+    {:ok, :extend_socket_hook_object, updated} =
+      assert Parser.extend_socket_hook_object(code, "NewHook", 1)
+
+    assert Parser.hook_exists?(updated, "NewHook")
+
+    {:error, :extend_socket_hook_object, _} =
+      assert Parser.extend_socket_hook_object(code, "NewHook", nil)
+
+    {:ok, :extend_socket_hook_object, _} =
+      assert Parser.extend_socket_hook_object(@valid_app_js, "NewHook", nil, :path)
+
+    refute Parser.hook_exists?(code, "AdminHook")
+    assert Parser.hook_exists?(code, "AdminHook", socket: "adminSocket")
+    assert Parser.hook_exists?(code, "AdminHook", :content, socket: 0)
+    refute Parser.hook_exists?(code, "AdminHook", socket: nil)
+  end
+
+  test "List the LiveSocket options :: live_socket_options" do
+    {:ok, :live_socket_options, options} =
+      assert Parser.live_socket_options(@valid_app_js, :path)
+
+    assert options == [{"hooks", nil}, {"longPollFallbackMs", "2500"}, {"params", nil}]
+
+    {:error, :live_socket_options, _} =
+      assert Parser.live_socket_options(@invalid_app_without_live_socket, :path)
+
+    {:error, :live_socket_options, _} =
+      assert Parser.live_socket_options(@valid_app_js, :path, socket: "adminSocket")
+  end
+
+  test "Diagnose the LiveSocket initialization :: diagnose_live_socket" do
+    {:ok, :diagnose_live_socket, diagnosis} =
+      assert Parser.diagnose_live_socket(@valid_app_js, :path)
+
+    assert diagnosis == %{
+             live_socket_declared: true,
+             new_live_socket: true,
+             has_options: true,
+             has_hooks: true,
+             problem: nil
+           }
+
+    {:ok, :diagnose_live_socket, %{has_hooks: false, problem: nil}} =
+      assert Parser.diagnose_live_socket(@invalid_app_without_hooks_key, :path)
+
+    {:ok, :diagnose_live_socket, %{live_socket_declared: true, new_live_socket: false}} =
+      assert Parser.diagnose_live_socket(@invalid_app_without_live_socket_object, :path)
+
+    {:ok, :diagnose_live_socket, %{live_socket_declared: false, problem: problem}} =
+      assert Parser.diagnose_live_socket(@invalid_app_without_live_socket, :path)
+
+    assert problem == "The socket variable is not declared"
+
+    {:ok, :diagnose_live_socket, %{live_socket_declared: false}} =
+      assert Parser.diagnose_live_socket(@valid_app_js, :path, socket: "adminSocket")
+
+    {:error, :diagnose_live_socket, _} =
+      assert Parser.diagnose_live_socket(~s(let liveSocket = new LiveSocket("/live", Socket, {))
+  end
+
+  test "Check a registered hook :: exist_hook" do
+    {:ok, :exist_hook, true} =
+      assert Parser.exist_hook(@valid_app_js, "CopyMixInstallationHook", :path)
+
+    {:error, :exist_hook, false} = assert Parser.exist_hook(@valid_app_js, "MissingHook", :path)
+
+    assert Parser.hook_exists?(@valid_app_js, "...Hooks", :path)
+    refute Parser.hook_exists?(@invalid_app_without_live_socket, "CopyMixInstallationHook", :path)
+  end
+
+  test "Report removed and missing hooks :: remove_objects_from_hooks_with_report" do
+    {:ok, :remove_objects_from_hooks_with_report, %{code: code} = report} =
+      assert Parser.remove_objects_from_hooks_with_report(
+               @valid_app_js,
+               ["CopyMixInstallationHook", "MissingHook"],
+               :path
+             )
+
+    assert report.removed == ["CopyMixInstallationHook"]
+    assert report.not_found == ["MissingHook"]
+    refute code =~ "CopyMixInstallationHook"
+
+    {:error, :remove_objects_from_hooks_with_report, _} =
+      assert Parser.remove_objects_from_hooks_with_report(
+               @invalid_app_without_live_socket,
+               "MissingHook",
+               :path
+             )
+  end
+
+  test "Rename a hooks spread :: replace_hook_spread" do
+    {:ok, :replace_hook_spread, output} =
+      assert Parser.replace_hook_spread(@valid_app_js, "Hooks", "Components", :path)
+
+    assert output =~ "...Components,"
+    refute output =~ "...Hooks"
+
+    {:error, :replace_hook_spread, _} =
+      assert Parser.replace_hook_spread(@valid_app_js, "MishkaComponents", "Components", :path)
+  end
+
+  test "Normalize the hooks order :: normalize_hooks_order" do
+    code = """
+    let liveSocket = new LiveSocket("/live", Socket, {
+      hooks: { TooltipHook, ...Hooks, AutoFocus },
+    });
+    """
+
+    {:ok, :normalize_hooks_order, output} = assert Parser.normalize_hooks_order(code)
+    assert output =~ ~r/\.\.\.Hooks,\s+AutoFocus,\s+TooltipHook\s+}/
+
+    {:error, :normalize_hooks_order, _} =
+      assert Parser.normalize_hooks_order(@invalid_app_without_hooks_key, :path)
+  end
+
+  test "Merge a default into a named import :: insert_imports" do
+    code = "import { useState } from \"react\";\n"
+
+    {:ok, :insert_imports, output} =
+      assert Parser.insert_imports(code, "import React, { useState } from \"react\";")
+
+    assert output == "import React, { useState } from \"react\";\n"
+  end
+
+  test "Get the source of a function :: function_source" do
+    code = """
+    function add(a, b) { return a + b; }
+    const double = (value) => value * 2;
+    """
+
+    {:ok, :function_source, "function add(a, b) {\n    return a + b;\n}"} =
+      assert Parser.function_source(code, "add")
+
+    {:ok, :function_source, "const double = (value)=>value * 2;"} =
+      assert Parser.function_source(code, "double")
+
+    {:error, :function_source, _} = assert Parser.function_source(code, "missing")
+  end
+
+  test "Count source lines of code :: sloc" do
+    {:ok, :sloc, 6} = assert Parser.sloc(@valid_sloc_sample, :path)
+    {:ok, :sloc, 0} = assert Parser.sloc("// only a comment\n")
+    {:error, :sloc, _} = assert Parser.sloc("let x = ;")
+  end
+
+  test "List TODO and FIXME comments :: todo_comments" do
+    {:ok, :todo_comments, todos} = assert Parser.todo_comments(@valid_todo_comments, :path)
+
+    assert Enum.map(todos, &{&1.keyword, &1.line}) == [{"TODO", 3}, {"FIXME", 6}, {"TODO", 11}]
+
+    {:ok, :todo_comments, []} =
+      assert Parser.todo_comments(@valid_todo_comments, :path, ["HACK"])
+  end
+
+  test "Extract string literals :: string_literals" do
+    js_code = """
+    import { Socket } from "phoenix";
+    const labels = { "title": "Welcome back" };
+    """
+
+    {:ok, :string_literals, [%{value: "Welcome back", line: 2}]} =
+      assert Parser.string_literals(js_code)
+
+    {:error, :string_literals, _} = assert Parser.string_literals("let x = ;")
+  end
+
+  test "Detect newer syntax features :: features" do
+    js_code = """
+    const token = document.querySelector("meta")?.getAttribute("content");
+    const timeout = window.config?.timeout ?? 2500;
+    """
+
+    {:ok, :features, features} = assert Parser.features(js_code)
+
+    assert features == %{
+             optional_chaining: true,
+             nullish_coalescing: true,
+             top_level_await: false,
+             dynamic_import: false
+           }
+
+    {:error, :features, _} = assert Parser.features("let x = ;")
+  end
+
+  test "Keep and choose import quotes :: insert_imports_with_quotes" do
+    js_code = "import { Socket } from 'phoenix';\n"
+    import = "import { LiveSocket } from \"phoenix_live_view\";"
+
+    {:ok, :insert_imports, output} = assert Parser.insert_imports(js_code, import)
+    assert output =~ "import { Socket } from 'phoenix';"
+
+    {:ok, :insert_imports_with_quotes, output} =
+      assert Parser.insert_imports_with_quotes(js_code, import, :single)
+
+    assert output =~ "import { Socket } from 'phoenix';"
+    assert output =~ "import { LiveSocket } from 'phoenix_live_view';"
+  end
+
+  test "Find an import by its local name :: import_by_local" do
+    {:ok, :import_by_local, %{source: "../vendor/topbar", kind: :default, imported: nil}} =
+      assert Parser.import_by_local(@valid_app_js, "topbar", :path)
+
+    {:ok, :import_by_local, %{source: "phoenix", kind: :named, imported: "Socket"}} =
+      assert Parser.import_by_local(@valid_app_js, "Socket", :path)
+
+    {:error, :import_by_local, _} = assert Parser.import_by_local(@valid_app_js, "none", :path)
+  end
+
+  test "Edit an empty content :: editors" do
+    empty_error = "The source code is empty, there is nothing to edit"
+
+    {:ok, :remove_imports, ""} = assert Parser.remove_imports("", "phoenix")
+    {:ok, :normalize_imports, ""} = assert Parser.normalize_imports("")
+    {:ok, :hoist_imports, ""} = assert Parser.hoist_imports("")
+    {:ok, :strip_comments, ""} = assert Parser.strip_comments("")
+    {:ok, :remove_debuggers, {"", 0}} = assert Parser.remove_debuggers("")
+
+    {:ok, :insert_imports, "import { Socket } from \"phoenix\";\n"} =
+      assert Parser.insert_imports("", "import { Socket } from \"phoenix\";")
+
+    {:error, :extend_hook_object, ^empty_error} =
+      assert Parser.extend_hook_object("", "CopyHook")
+
+    {:error, :remove_objects_from_hooks, ^empty_error} =
+      assert Parser.remove_objects_from_hooks("", "CopyHook")
+
+    {:error, :normalize_hooks_order, ^empty_error} = assert Parser.normalize_hooks_order("")
+
+    {:error, :extend_var_object_by_object_names, ^empty_error} =
+      assert Parser.extend_var_object_by_object_names("", "Hooks", "CopyHook")
+
+    {:error, :rename_identifier, ^empty_error} =
+      assert Parser.rename_identifier("", "total", "sum")
+  end
+
   defp string_counter(string, pattern) do
     Regex.scan(Regex.compile!(pattern), string)
     |> length()